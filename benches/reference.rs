@@ -0,0 +1,73 @@
+//! Benchmarks of parsing, generation, formatting and batch validation
+//!
+//! Run with `cargo bench --features bench`.
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion, Throughput};
+use iso_11649::{is_valid, is_valid_batch, parse_bytes, RfCreditorReference};
+
+/// Number of references of the batch benchmarks
+const BATCH: u64 = 1000;
+
+fn parse(c: &mut Criterion) {
+    let mut group = c.benchmark_group("parse");
+    group.bench_function("parse_str print", |b| {
+        b.iter(|| RfCreditorReference::parse_str(black_box("RF18 5390 0754 7034")));
+    });
+    group.bench_function("parse_str electronic", |b| {
+        b.iter(|| RfCreditorReference::parse_str(black_box("RF18539007547034")));
+    });
+    group.bench_function("parse_bytes", |b| {
+        b.iter(|| parse_bytes(black_box(b"RF18 5390 0754 7034")));
+    });
+    group.bench_function("is_valid", |b| {
+        b.iter(|| is_valid(black_box("RF18 5390 0754 7034")));
+    });
+    group.finish();
+}
+
+fn generate(c: &mut Criterion) {
+    let mut group = c.benchmark_group("generate");
+    group.bench_function("try_new", |b| {
+        b.iter(|| RfCreditorReference::try_new(black_box("539007547034")));
+    });
+    group.bench_function("from_u64", |b| {
+        b.iter(|| RfCreditorReference::from_u64(black_box(539_007_547_034)));
+    });
+    group.finish();
+}
+
+fn format(c: &mut Criterion) {
+    let rf = RfCreditorReference::parse_str("RF18 5390 0754 7034").unwrap();
+
+    let mut group = c.benchmark_group("format");
+    group.bench_function("to_string", |b| b.iter(|| black_box(&rf).to_string()));
+    group.bench_function("to_grouped_string", |b| {
+        b.iter(|| black_box(&rf).to_grouped_string(3));
+    });
+    group.bench_function("alternate", |b| b.iter(|| format!("{:#}", black_box(&rf))));
+    group.finish();
+}
+
+fn batch(c: &mut Criterion) {
+    let bodies = (0..BATCH)
+        .map(|n| (n * 7_919_000_003).to_string())
+        .collect::<Vec<_>>();
+    let references = bodies
+        .iter()
+        .map(|body| RfCreditorReference::new(body).to_electronic_string())
+        .collect::<Vec<_>>();
+    let references = references.iter().map(String::as_str).collect::<Vec<_>>();
+
+    let mut group = c.benchmark_group("batch");
+    let _ = group.throughput(Throughput::Elements(BATCH));
+    group.bench_function("generate_batch", |b| {
+        b.iter(|| RfCreditorReference::generate_batch(bodies.iter().map(String::as_str)));
+    });
+    group.bench_function("is_valid_batch", |b| {
+        b.iter(|| is_valid_batch(black_box(&references)));
+    });
+    group.finish();
+}
+
+criterion_group!(benches, parse, generate, format, batch);
+criterion_main!(benches);
@@ -0,0 +1,185 @@
+//! Columns of creditor references in [Apache Arrow](https://arrow.apache.org)
+//! arrays
+//!
+//! Available with feature `arrow`.
+//!
+//! Reference columns are `Utf8` arrays in canonical electronic format,
+//! marked as extension type [`EXTENSION_NAME`] in the metadata of their
+//! [`field`]. [`RfReferenceArrayBuilder`] builds validated columns and
+//! [`is_valid_rf`] validates existing string columns.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use arrow_array::{Array, StringArray};
+//! use iso_11649::arrow::{is_valid_rf, RfReferenceArrayBuilder};
+//!
+//! let mut builder = RfReferenceArrayBuilder::new();
+//! builder.append_str("RF18 5390 0754 7034").unwrap();
+//! assert!(builder.append_str("RF19 5390 0754 7034").is_err());
+//! builder.append_null();
+//! let column = builder.finish();
+//! assert_eq!(column.value(0), "RF18539007547034");
+//! assert_eq!(column.len(), 2);
+//!
+//! let raw = StringArray::from(vec![Some("RF18539007547034"), Some("RF19539007547034"), None]);
+//! let valid = is_valid_rf(&raw);
+//! assert_eq!(valid.iter().collect::<Vec<_>>(), [Some(true), Some(false), None]);
+//! ```
+
+use std::collections::HashMap;
+
+use ::arrow_array::{
+    builder::{ArrayBuilder, StringBuilder},
+    BooleanArray, StringArray,
+};
+use ::arrow_schema::{DataType, Field};
+
+use crate::{ParseError, RfCreditorReference};
+
+/// Name of the extension type of reference columns
+pub const EXTENSION_NAME: &str = "iso11649.creditor_reference";
+
+/// Key of the extension type name in the metadata of fields
+const EXTENSION_NAME_KEY: &str = "ARROW:extension:name";
+
+/// Creates a `Utf8` [`Field`] of the extension type [`EXTENSION_NAME`]
+#[must_use]
+pub fn field(name: &str, nullable: bool) -> Field {
+    Field::new(name, DataType::Utf8, nullable).with_metadata(HashMap::from([(
+        EXTENSION_NAME_KEY.to_string(),
+        EXTENSION_NAME.to_string(),
+    )]))
+}
+
+/// Checks if `field` is of the extension type [`EXTENSION_NAME`]
+#[must_use]
+pub fn is_reference_field(field: &Field) -> bool {
+    field.data_type() == &DataType::Utf8
+        && field.metadata().get(EXTENSION_NAME_KEY).map(String::as_str) == Some(EXTENSION_NAME)
+}
+
+/// Validates each value of `array` like [`crate::is_valid`]
+///
+/// Null values stay null.
+#[must_use]
+pub fn is_valid_rf(array: &StringArray) -> BooleanArray {
+    array
+        .iter()
+        .map(|reference| reference.map(crate::is_valid))
+        .collect()
+}
+
+/// `RfReferenceArrayBuilder` builds a [`StringArray`] of validated
+/// references in electronic format
+#[derive(Debug, Default)]
+pub struct RfReferenceArrayBuilder {
+    builder: StringBuilder,
+}
+
+impl RfReferenceArrayBuilder {
+    /// Creates an empty [`RfReferenceArrayBuilder`]
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Appends `rf` in electronic format
+    pub fn append_reference(&mut self, rf: &RfCreditorReference<'_>) {
+        self.builder.append_value(rf.as_electronic_str());
+    }
+
+    /// Validates `reference` like [`RfCreditorReference::parse_str`] and
+    /// appends it in electronic format
+    ///
+    /// Invalid references are not appended.
+    ///
+    /// # Errors
+    ///
+    /// Results in [`ParseError`]s when there is some problem with
+    /// parsing the `reference`.
+    ///
+    pub fn append_str(&mut self, reference: &str) -> Result<(), ParseError> {
+        let electronic_reference = crate::parse_bytes(reference.as_bytes())?;
+        self.builder.append_value(electronic_reference.as_str());
+        Ok(())
+    }
+
+    /// Appends a null value
+    pub fn append_null(&mut self) {
+        self.builder.append_null();
+    }
+
+    /// Number of appended values
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.builder.len()
+    }
+
+    /// Checks if no value is appended
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.builder.is_empty()
+    }
+
+    /// Builds the array and resets the builder
+    pub fn finish(&mut self) -> StringArray {
+        self.builder.finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ::arrow_array::Array;
+
+    use super::*;
+
+    #[test]
+    fn field_test() {
+        let field = field("reference", true);
+        assert!(is_reference_field(&field));
+        assert!(field.is_nullable());
+        assert!(!is_reference_field(&Field::new(
+            "reference",
+            DataType::Utf8,
+            true
+        )));
+    }
+
+    #[test]
+    fn builder_test() {
+        let mut builder = RfReferenceArrayBuilder::new();
+        assert!(builder.is_empty());
+        builder.append_reference(&RfCreditorReference::new("2348231"));
+        builder.append_str(" RF18 5390 0754 7034 ").unwrap();
+        assert!(matches!(
+            builder.append_str("RF18 5390 0754 703_"),
+            Err(ParseError::InvalidCharacter { .. })
+        ));
+        builder.append_null();
+        assert_eq!(builder.len(), 3);
+
+        let array = builder.finish();
+        assert_eq!(array.value(0), "RF712348231");
+        assert_eq!(array.value(1), "RF18539007547034");
+        assert!(array.is_null(2));
+        assert!(builder.is_empty());
+        assert!(is_valid_rf(&array).iter().flatten().all(|valid| valid));
+    }
+
+    #[test]
+    fn is_valid_rf_test() {
+        let array = StringArray::from(vec![
+            Some("RF18 5390 0754 7034"),
+            None,
+            Some("RF71 2348 232"),
+            Some(""),
+        ]);
+        let valid = is_valid_rf(&array);
+        assert_eq!(valid.len(), 4);
+        assert_eq!(
+            valid.iter().collect::<Vec<_>>(),
+            [Some(true), None, Some(false), Some(false)]
+        );
+    }
+}
@@ -0,0 +1,116 @@
+//! [Apache Avro](https://avro.apache.org) schema and serialization of
+//! creditor references with [`apache-avro`](https://docs.rs/apache-avro)
+//!
+//! Available with feature `avro`.
+//!
+//! References are Avro strings in canonical electronic format, annotated
+//! with the logical type [`LOGICAL_TYPE`] in [`SCHEMA`]. Readers without
+//! knowledge of the logical type still read plain strings. Serialization
+//! goes through the `serde` implementations, so `apache_avro::to_value`
+//! and `apache_avro::from_value` validate references like
+//! [`RfCreditorReference::parse_str`]. Records deriving `AvroSchema` may
+//! contain [`RfCreditorReference`] fields.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use apache_avro::types::Value;
+//! use iso_11649::RfCreditorReference;
+//!
+//! let rf = RfCreditorReference::new("539007547034");
+//! let value = apache_avro::to_value(&rf).unwrap();
+//! assert_eq!(value, Value::String("RF18539007547034".to_string()));
+//! assert_eq!(apache_avro::from_value::<RfCreditorReference>(&value).unwrap(), rf);
+//!
+//! let invalid = Value::String("RF19539007547034".to_string());
+//! assert!(apache_avro::from_value::<RfCreditorReference>(&invalid).is_err());
+//! ```
+
+use std::collections::HashMap;
+
+use ::apache_avro::{
+    schema::{derive::AvroSchemaComponent, Name, Namespace, Schema},
+    types::Value,
+};
+
+use crate::RfCreditorReference;
+
+/// Name of the logical type of references
+pub const LOGICAL_TYPE: &str = "iso11649-creditor-reference";
+
+/// Canonical Avro schema fragment of references
+///
+/// Usable as type of record fields in `.avsc` files and schema registries.
+pub const SCHEMA: &str = r#"{"type":"string","logicalType":"iso11649-creditor-reference","doc":"ISO 11649 creditor reference in electronic format"}"#;
+
+/// Parses [`SCHEMA`]
+///
+/// `apache-avro` resolves the unknown logical type to its underlying
+/// [`Schema::String`].
+#[must_use]
+pub fn schema() -> Schema {
+    Schema::parse_str(SCHEMA).unwrap_or(Schema::String)
+}
+
+/// Schema of [`RfCreditorReference`] fields of records deriving
+/// `AvroSchema`
+impl AvroSchemaComponent for RfCreditorReference<'_> {
+    fn get_schema_in_ctxt(
+        _named_schemas: &mut HashMap<Name, Schema>,
+        _enclosing_namespace: &Namespace,
+    ) -> Schema {
+        schema()
+    }
+}
+
+/// Keeps the reference in electronic format.
+impl From<&RfCreditorReference<'_>> for Value {
+    fn from(rf: &RfCreditorReference<'_>) -> Self {
+        Value::String(rf.to_electronic_string())
+    }
+}
+
+/// Keeps the reference in electronic format.
+impl From<RfCreditorReference<'_>> for Value {
+    fn from(rf: RfCreditorReference<'_>) -> Self {
+        Value::from(&rf)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ::apache_avro::{from_avro_datum, to_avro_datum};
+
+    use super::*;
+
+    #[test]
+    fn schema_test() {
+        assert_eq!(schema(), Schema::String);
+        assert!(SCHEMA.contains(LOGICAL_TYPE));
+
+        let mut names = HashMap::new();
+        assert_eq!(
+            RfCreditorReference::get_schema_in_ctxt(&mut names, &None),
+            Schema::String
+        );
+        assert!(names.is_empty());
+    }
+
+    #[test]
+    fn datum_test() {
+        let rf = RfCreditorReference::parse_str("RF71 2348 231").unwrap();
+        let schema = schema();
+        let datum = to_avro_datum(&schema, Value::from(&rf)).unwrap();
+        assert_eq!(datum, b"\x16RF712348231");
+
+        let value = from_avro_datum(&schema, &mut datum.as_slice(), None).unwrap();
+        assert_eq!(
+            ::apache_avro::from_value::<RfCreditorReference>(&value).unwrap(),
+            rf
+        );
+
+        let value = Value::String("RF71 2348 23_".to_string());
+        assert!(::apache_avro::from_value::<RfCreditorReference>(&value).is_err());
+        assert!(::apache_avro::from_value::<RfCreditorReference>(&Value::Int(4711)).is_err());
+    }
+}
@@ -0,0 +1,164 @@
+//! Validation and generation of streams of references with reused buffers
+//!
+//! [`BatchValidator`] and [`BatchGenerator`] collect the results of
+//! pushed references until [`BatchValidator::finish`] or
+//! [`BatchGenerator::finish`] drains them. The buffers keep their
+//! capacity, so long-running services process batch after batch without
+//! allocation churn.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use iso_11649::batch::{BatchGenerator, BatchValidator};
+//!
+//! let mut generator = BatchGenerator::new();
+//! let mut validator = BatchValidator::new();
+//!
+//! for body in ["539007547034", "4711"] {
+//!     generator.push(body);
+//! }
+//! for rf in generator.finish() {
+//!     assert!(validator.push(rf.unwrap().as_electronic_str()));
+//! }
+//! assert!(!validator.push("RF19 5390 0754 7034"));
+//!
+//! let results = validator.finish().collect::<Vec<_>>();
+//! assert_eq!(results[1].as_ref().unwrap().as_str(), "RF714711");
+//! assert!(results[2].is_err());
+//! ```
+
+use crate::{parse_bytes, ElectronicReference, ParseError, RfCreditorReference};
+
+/// `BatchValidator` validates references without heap allocation of valid
+/// references
+///
+/// Valid references are kept as inline [`ElectronicReference`]s.
+#[derive(Clone, Debug, Default)]
+pub struct BatchValidator {
+    results: Vec<Result<ElectronicReference, ParseError>>,
+}
+
+impl BatchValidator {
+    /// Creates an empty [`BatchValidator`]
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Validates `reference` like [`parse_bytes`] and keeps the result
+    ///
+    /// Returns if `reference` is valid.
+    pub fn push(&mut self, reference: &str) -> bool {
+        let result = parse_bytes(reference.as_bytes());
+        let valid = result.is_ok();
+        self.results.push(result);
+        valid
+    }
+
+    /// Number of pending results
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.results.len()
+    }
+
+    /// Checks if no result is pending
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.results.is_empty()
+    }
+
+    /// Drains the pending results in the order of [`Self::push`]
+    pub fn finish(&mut self) -> impl Iterator<Item = Result<ElectronicReference, ParseError>> + '_ {
+        self.results.drain(..)
+    }
+}
+
+/// `BatchGenerator` generates references with a single allocation per
+/// reference
+#[derive(Clone, Debug, Default)]
+pub struct BatchGenerator {
+    results: Vec<Result<RfCreditorReference<'static>, ParseError>>,
+}
+
+impl BatchGenerator {
+    /// Creates an empty [`BatchGenerator`]
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Generates the reference of `reference` like
+    /// [`RfCreditorReference::try_new`] and keeps the result
+    ///
+    /// Returns if the reference could be generated.
+    pub fn push(&mut self, reference: &str) -> bool {
+        let result = RfCreditorReference::generate(reference);
+        let generated = result.is_ok();
+        self.results.push(result);
+        generated
+    }
+
+    /// Number of pending results
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.results.len()
+    }
+
+    /// Checks if no result is pending
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.results.is_empty()
+    }
+
+    /// Drains the pending results in the order of [`Self::push`]
+    pub fn finish(
+        &mut self,
+    ) -> impl Iterator<Item = Result<RfCreditorReference<'static>, ParseError>> + '_ {
+        self.results.drain(..)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn batch_validator_test() {
+        let mut validator = BatchValidator::new();
+        assert!(validator.is_empty());
+        assert!(validator.push("RF18 5390 0754 7034"));
+        assert!(!validator.push("RF18 5390 0754 7035"));
+        assert_eq!(validator.len(), 2);
+
+        let results = validator.finish().collect::<Vec<_>>();
+        assert_eq!(results[0].as_ref().unwrap().as_str(), "RF18539007547034");
+        assert!(matches!(
+            results[1],
+            Err(ParseError::InvalidChecksum { .. })
+        ));
+        assert!(validator.is_empty());
+
+        assert!(validator.push("RF714711"));
+        assert_eq!(validator.finish().count(), 1);
+    }
+
+    #[test]
+    fn batch_generator_test() {
+        let bodies = [
+            "539007547034",
+            "RF00 4711",
+            "ABC_",
+            "1234567890123456789012",
+        ];
+
+        let mut generator = BatchGenerator::new();
+        for body in bodies {
+            let _ = generator.push(body);
+        }
+        assert_eq!(generator.len(), bodies.len());
+        for (body, result) in bodies.iter().zip(generator.finish()) {
+            assert_eq!(result, RfCreditorReference::try_new(body));
+        }
+        assert!(generator.is_empty());
+    }
+}
@@ -0,0 +1,76 @@
+//! Serialization of [`RfCreditorReference`]s with
+//! [`borsh`](https://docs.rs/borsh)
+//!
+//! Available with feature `borsh`.
+//!
+//! A reference is encoded like a borsh string in the canonical electronic
+//! format. Decoding validates the reference like
+//! [`RfCreditorReference::parse_str`].
+
+use std::io::{self, Read, Write};
+
+use ::borsh::{BorshDeserialize, BorshSerialize};
+
+use crate::RfCreditorReference;
+
+/// Encodes the reference as borsh string in electronic format
+///
+/// # Examples
+///
+/// ```rust
+/// use iso_11649::RfCreditorReference;
+///
+/// let rf = RfCreditorReference::new("2348231");
+/// let bytes = borsh::to_vec(&rf).unwrap();
+///
+/// assert_eq!(bytes, b"\x0b\0\0\0RF712348231");
+/// assert_eq!(borsh::from_slice::<RfCreditorReference>(&bytes).unwrap(), rf);
+/// ```
+impl BorshSerialize for RfCreditorReference<'_> {
+    fn serialize<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+        self.as_electronic_str().serialize(writer)
+    }
+}
+
+/// Decodes a borsh string and validates the reference
+///
+/// Invalid references result in an [`io::Error`] of kind
+/// [`io::ErrorKind::InvalidData`] with the [`ParseError`](crate::ParseError)
+/// as inner error.
+impl BorshDeserialize for RfCreditorReference<'_> {
+    fn deserialize_reader<R: Read>(reader: &mut R) -> io::Result<Self> {
+        let reference = String::deserialize_reader(reader)?;
+        RfCreditorReference::parse_str(&reference)
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ParseError;
+
+    #[test]
+    fn roundtrip_test() {
+        let rf = RfCreditorReference::parse_str("RF18 5390 0754 7034").unwrap();
+        let bytes = ::borsh::to_vec(&rf).unwrap();
+        assert_eq!(bytes, b"\x10\0\0\0RF18539007547034");
+        assert_eq!(
+            ::borsh::from_slice::<RfCreditorReference>(&bytes).unwrap(),
+            rf
+        );
+    }
+
+    #[test]
+    fn invalid_test() {
+        let bytes = ::borsh::to_vec("RF19539007547034").unwrap();
+        let err = ::borsh::from_slice::<RfCreditorReference>(&bytes).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+        assert!(matches!(
+            err.get_ref().unwrap().downcast_ref::<ParseError>(),
+            Some(ParseError::InvalidChecksum { .. })
+        ));
+
+        assert!(::borsh::from_slice::<RfCreditorReference>(b"\x10\0\0\0RF18").is_err());
+    }
+}
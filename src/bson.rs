@@ -0,0 +1,135 @@
+//! Conversions of [`RfCreditorReference`]s to and from [`Bson`] values of
+//! the [`bson`](https://docs.rs/bson) crate
+//!
+//! Available with feature `bson`.
+//!
+//! References are stored as BSON strings in canonical electronic format.
+//! Converting a [`Bson`] value back validates the reference like
+//! [`RfCreditorReference::parse_str`]. With feature `serde` too, documents
+//! of the `mongodb` driver serialize and deserialize references the same
+//! way.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use bson::Bson;
+//! use iso_11649::RfCreditorReference;
+//!
+//! let rf = RfCreditorReference::new("539007547034");
+//! let value = Bson::from(&rf);
+//! assert_eq!(value, Bson::String("RF18539007547034".to_string()));
+//! assert_eq!(RfCreditorReference::try_from(value).unwrap(), rf);
+//!
+//! assert!(RfCreditorReference::try_from(Bson::Int32(4711)).is_err());
+//! ```
+
+use ::bson::{spec::ElementType, Bson};
+
+use crate::{ParseError, RfCreditorReference};
+
+/// The `BsonError` enum is a collection of all the possible reasons
+/// converting a [`Bson`] value to [`RfCreditorReference`] fails.
+///
+/// New variants may be added in future versions.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum BsonError {
+    /// the value is no string
+    UnexpectedType(ElementType),
+    /// the string is no valid reference
+    Parse(ParseError),
+}
+
+impl std::fmt::Display for BsonError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> Result<(), std::fmt::Error> {
+        match self {
+            BsonError::UnexpectedType(element_type) => {
+                write!(f, "expected string, found {:?}", element_type)
+            }
+            BsonError::Parse(err) => write!(f, "{}", err),
+        }
+    }
+}
+
+impl std::error::Error for BsonError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            BsonError::Parse(err) => Some(err),
+            BsonError::UnexpectedType(_) => None,
+        }
+    }
+}
+
+impl From<ParseError> for BsonError {
+    fn from(err: ParseError) -> Self {
+        BsonError::Parse(err)
+    }
+}
+
+/// Converts `&RfCreditorReference` too, by the blanket implementation of
+/// [`Bson`] for references of cloneable types.
+impl From<RfCreditorReference<'_>> for Bson {
+    fn from(rf: RfCreditorReference<'_>) -> Self {
+        Bson::String(rf.to_electronic_string())
+    }
+}
+
+/// Validates a [`Bson::String`] like [`RfCreditorReference::parse_str`]
+impl TryFrom<&Bson> for RfCreditorReference<'_> {
+    type Error = BsonError;
+
+    fn try_from(value: &Bson) -> Result<Self, Self::Error> {
+        match value {
+            Bson::String(reference) => Ok(RfCreditorReference::parse_str(reference)?),
+            _ => Err(BsonError::UnexpectedType(value.element_type())),
+        }
+    }
+}
+
+/// Validates a [`Bson::String`] like [`RfCreditorReference::parse_str`]
+impl TryFrom<Bson> for RfCreditorReference<'_> {
+    type Error = BsonError;
+
+    fn try_from(value: Bson) -> Result<Self, Self::Error> {
+        RfCreditorReference::try_from(&value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn try_from_test() {
+        let value = Bson::String("RF71 2348 231".to_string());
+        assert_eq!(
+            RfCreditorReference::try_from(&value).unwrap(),
+            RfCreditorReference::new("2348231")
+        );
+
+        assert!(matches!(
+            RfCreditorReference::try_from(Bson::String("RF70 2348 231".to_string())),
+            Err(BsonError::Parse(ParseError::InvalidChecksum { .. }))
+        ));
+        let err = RfCreditorReference::try_from(Bson::Null).unwrap_err();
+        assert_eq!(err, BsonError::UnexpectedType(ElementType::Null));
+        assert_eq!(err.to_string(), "expected string, found Null");
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_test() {
+        let rf = RfCreditorReference::new("539007547034");
+        let value = ::bson::to_bson(&rf).unwrap();
+        assert_eq!(value, Bson::from(&rf));
+        assert_eq!(::bson::from_bson::<RfCreditorReference>(value).unwrap(), rf);
+
+        let document = ::bson::doc! { "reference": "RF19539007547034" };
+        assert!(
+            ::bson::from_document::<std::collections::HashMap<String, RfCreditorReference>>(
+                document
+            )
+            .is_err()
+        );
+    }
+}
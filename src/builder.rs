@@ -0,0 +1,234 @@
+//! Building creditor references from segments
+
+use crate::{compute_checksum, ParseError, RfCreditorReference, MAX_BODY_LEN};
+
+/// `RfReferenceBuilder` builds a creditor reference from segments like
+/// customer id and invoice number
+///
+/// The segments are concatenated to the reference body, optionally padded
+/// with leading zeros, and the check digits are computed.
+///
+/// # Examples
+///
+/// ```rust
+/// use iso_11649::builder::RfReferenceBuilder;
+///
+/// let rf = RfReferenceBuilder::new()
+///     .segment("4711")
+///     .segment("2024")
+///     .segment("0815")
+///     .pad_to(16)
+///     .build()
+///     .unwrap();
+///
+/// assert_eq!(rf.to_string(), "RF68 0000 4711 2024 0815");
+///
+/// assert!(RfReferenceBuilder::new().segment("INV-1").build().is_err());
+/// ```
+///
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct RfReferenceBuilder {
+    /// Only digits are accepted in segments
+    numeric_only: bool,
+    /// Length the body is padded to with leading zeros
+    pad_to: usize,
+    /// Segments of the body in order
+    segments: Vec<String>,
+}
+
+impl RfReferenceBuilder {
+    /// Creates an empty [`RfReferenceBuilder`]
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Appends a `segment` to the body
+    ///
+    /// Valid characters are 0-9, a-z and A-Z. Invalid segments are
+    /// reported by [`Self::build`].
+    #[must_use]
+    pub fn segment(mut self, segment: impl Into<String>) -> Self {
+        self.segments.push(segment.into());
+        self
+    }
+
+    /// Sets if segments may only contain digits
+    ///
+    /// Some banks reject letters in creditor references. With `true`
+    /// letters are reported by [`Self::build`]. Default is `false`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use iso_11649::RfReferenceBuilder;
+    ///
+    /// let builder = RfReferenceBuilder::new().numeric_only(true);
+    ///
+    /// assert!(builder.clone().segment("4711").build().is_ok());
+    /// assert!(builder.segment("INV4711").build().is_err());
+    /// ```
+    ///
+    #[must_use]
+    pub fn numeric_only(mut self, numeric_only: bool) -> Self {
+        self.numeric_only = numeric_only;
+        self
+    }
+
+    /// Sets the length the body is padded to with leading zeros
+    ///
+    /// Longer bodies are not affected. Default is no padding.
+    #[must_use]
+    pub fn pad_to(mut self, len: usize) -> Self {
+        self.pad_to = len;
+        self
+    }
+
+    /// Builds the [`RfCreditorReference`] with computed check digits
+    ///
+    /// # Errors
+    ///
+    /// Results in [`ParseError::InvalidCharacter`] with the position in the
+    /// segment when a segment contains invalid characters or letters with
+    /// [`Self::numeric_only`], and in
+    /// [`ParseError::TooShort`] or [`ParseError::BodyTooLong`] when the body
+    /// has not 1 to 21 characters.
+    ///
+    pub fn build(&self) -> Result<RfCreditorReference<'static>, ParseError> {
+        for segment in &self.segments {
+            if let Some((position, character)) = segment.chars().enumerate().find(|&(_, c)| {
+                !c.is_ascii_alphanumeric() || (self.numeric_only && !c.is_ascii_digit())
+            }) {
+                return Err(ParseError::InvalidCharacter {
+                    input: segment.clone(),
+                    character,
+                    position,
+                });
+            }
+        }
+
+        let body = self.segments.concat();
+        if self.pad_to > MAX_BODY_LEN {
+            return Err(ParseError::BodyTooLong {
+                input: body,
+                len: self.pad_to,
+                max: MAX_BODY_LEN,
+            });
+        }
+        let body = format!("{:0>width$}", body, width = self.pad_to);
+        let checksum = compute_checksum(&body)?;
+
+        Ok(RfCreditorReference::from_checksum(checksum, &body))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn build_test() {
+        assert_eq!(
+            RfReferenceBuilder::new().segment("539007547034").build(),
+            Ok(RfCreditorReference::new("539007547034"))
+        );
+        assert_eq!(
+            RfReferenceBuilder::new()
+                .segment("5390")
+                .segment(String::from("0754"))
+                .segment("7034")
+                .build()
+                .unwrap()
+                .to_string(),
+            "RF18 5390 0754 7034"
+        );
+
+        // body starting with identifier is not confused with a reference
+        let rf = RfReferenceBuilder::new().segment("RF12").build().unwrap();
+        assert_eq!(&rf.as_electronic_str()[4..], "RF12");
+    }
+
+    #[test]
+    fn pad_to_test() {
+        let rf = RfReferenceBuilder::new()
+            .segment("42")
+            .pad_to(8)
+            .build()
+            .unwrap();
+        assert_eq!(&rf.as_electronic_str()[4..], "00000042");
+
+        let rf = RfReferenceBuilder::new()
+            .segment("4711")
+            .pad_to(2)
+            .build()
+            .unwrap();
+        assert_eq!(&rf.as_electronic_str()[4..], "4711");
+    }
+
+    #[test]
+    fn numeric_only_test() {
+        let builder = RfReferenceBuilder::new().numeric_only(true);
+        assert_eq!(
+            builder.clone().segment("5390").segment("07547034").build(),
+            Ok(RfCreditorReference::new("539007547034"))
+        );
+        assert!(matches!(
+            builder.clone().segment("4711").segment("2O24").build(),
+            Err(ParseError::InvalidCharacter {
+                character: 'O',
+                position: 1,
+                ..
+            })
+        ));
+        assert!(matches!(
+            builder.segment("12-3").build(),
+            Err(ParseError::InvalidCharacter { character: '-', .. })
+        ));
+    }
+
+    #[test]
+    fn error_test() {
+        assert!(matches!(
+            RfReferenceBuilder::new()
+                .segment("4711")
+                .segment("INV 1")
+                .build(),
+            Err(ParseError::InvalidCharacter {
+                character: ' ',
+                position: 3,
+                ..
+            })
+        ));
+        assert!(matches!(
+            RfReferenceBuilder::new().build(),
+            Err(ParseError::TooShort { .. })
+        ));
+        assert!(matches!(
+            RfReferenceBuilder::new()
+                .segment("1234567890")
+                .segment("12345678901")
+                .segment("2")
+                .build(),
+            Err(ParseError::BodyTooLong {
+                len: 22,
+                max: 21,
+                ..
+            })
+        ));
+        assert!(matches!(
+            RfReferenceBuilder::new().segment("1").pad_to(22).build(),
+            Err(ParseError::BodyTooLong { .. })
+        ));
+        assert!(matches!(
+            RfReferenceBuilder::new()
+                .segment("1")
+                .pad_to(usize::MAX)
+                .build(),
+            Err(ParseError::BodyTooLong {
+                len: usize::MAX,
+                max: 21,
+                ..
+            })
+        ));
+    }
+}
@@ -0,0 +1,265 @@
+//! Memoized validation of repeated references
+//!
+//! A [`CachedValidator`] keeps the parse results of the most recently
+//! used references, e.g. of retries or duplicate statement lines, and
+//! parses each of them only once.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use iso_11649::cache::CachedValidator;
+//!
+//! let mut validator = CachedValidator::new(1000);
+//!
+//! assert!(validator.parse("RF18 5390 0754 7034").is_ok());
+//! assert!(validator.parse("RF18539007547034").is_ok());
+//! assert!(!validator.is_valid("RF19 5390 0754 7034"));
+//!
+//! let stats = validator.stats();
+//! assert_eq!((stats.hits(), stats.misses()), (1, 2));
+//! ```
+
+use std::collections::{BTreeMap, HashMap};
+
+use crate::{ParseError, RfCreditorReference};
+
+/// Maximum number of results preallocated by [`CachedValidator::new`]
+const PREALLOCATED: usize = 1024;
+
+/// `CacheStats` counts the lookups of a [`CachedValidator`]
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct CacheStats {
+    hits: u64,
+    misses: u64,
+    evictions: u64,
+}
+
+impl CacheStats {
+    /// Number of lookups answered from the cache
+    #[must_use]
+    pub fn hits(self) -> u64 {
+        self.hits
+    }
+
+    /// Number of lookups parsing the reference
+    #[must_use]
+    pub fn misses(self) -> u64 {
+        self.misses
+    }
+
+    /// Number of results evicted from the full cache
+    #[must_use]
+    pub fn evictions(self) -> u64 {
+        self.evictions
+    }
+
+    /// Ratio of hits to all lookups, `0.0` without lookups
+    #[must_use]
+    pub fn hit_rate(self) -> f64 {
+        let lookups = self.hits + self.misses;
+        if lookups == 0 {
+            0.0
+        } else {
+            #[allow(clippy::cast_precision_loss)]
+            let rate = self.hits as f64 / lookups as f64;
+            rate
+        }
+    }
+}
+
+/// Cached result with its last use
+#[derive(Clone, Debug)]
+struct Entry {
+    result: Result<RfCreditorReference<'static>, ParseError>,
+    used: u64,
+}
+
+/// `CachedValidator` parses references with a bounded LRU cache of the
+/// results
+///
+/// The cache is keyed on the electronic format, so spellings with
+/// different spaces share valid results. Errors refer to the rejected
+/// input with its positions, so they are only shared by the same input.
+/// The least recently used result is evicted when the cache is full.
+#[derive(Clone, Debug)]
+pub struct CachedValidator {
+    /// Maximum number of cached results
+    capacity: usize,
+    /// Cached results by electronic format
+    entries: HashMap<String, Entry>,
+    /// Electronic formats by last use for eviction
+    recency: BTreeMap<u64, String>,
+    /// Counter of lookups ordering the uses
+    clock: u64,
+    /// Buffer of the key of a lookup
+    key: String,
+    stats: CacheStats,
+}
+
+impl CachedValidator {
+    /// Creates an empty [`CachedValidator`] caching at most `capacity`
+    /// results
+    ///
+    /// Nothing is cached with a `capacity` of 0. Memory for large
+    /// capacities is allocated as the cache fills.
+    #[must_use]
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            entries: HashMap::with_capacity(capacity.min(PREALLOCATED)),
+            recency: BTreeMap::new(),
+            clock: 0,
+            key: String::new(),
+            stats: CacheStats::default(),
+        }
+    }
+
+    /// Parses a `reference` like [`RfCreditorReference::parse_str`] or
+    /// returns the cached result
+    ///
+    /// # Errors
+    ///
+    /// Results in [`ParseError`]s when there is some problem with
+    /// parsing the `reference`.
+    ///
+    pub fn parse(&mut self, reference: &str) -> Result<RfCreditorReference<'static>, ParseError> {
+        self.key.clear();
+        self.key.extend(reference.chars().filter(|&c| c != ' '));
+        self.clock += 1;
+
+        if let Some(entry) = self.entries.get_mut(&self.key) {
+            let hit = match &entry.result {
+                Ok(_) => true,
+                Err(err) => err.input() == reference,
+            };
+            if hit {
+                let _ = self.recency.remove(&entry.used);
+                let _ = self.recency.insert(self.clock, self.key.clone());
+                entry.used = self.clock;
+                self.stats.hits += 1;
+                return entry.result.clone();
+            }
+        }
+
+        self.stats.misses += 1;
+        let result = RfCreditorReference::parse_str(reference);
+        if self.capacity > 0 {
+            self.insert(result.clone());
+        }
+        result
+    }
+
+    /// Checks like [`crate::is_valid`] if `reference` is valid with the
+    /// cached result
+    pub fn is_valid(&mut self, reference: &str) -> bool {
+        self.parse(reference).is_ok()
+    }
+
+    /// Statistics of the lookups
+    #[must_use]
+    pub fn stats(&self) -> CacheStats {
+        self.stats
+    }
+
+    /// Number of cached results
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Checks if no result is cached
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Removes all cached results and keeps the statistics
+    pub fn clear(&mut self) {
+        self.entries.clear();
+        self.recency.clear();
+    }
+
+    /// Inserts `result` for the current key and evicts the least recently
+    /// used result of a full cache
+    fn insert(&mut self, result: Result<RfCreditorReference<'static>, ParseError>) {
+        let entry = Entry {
+            result,
+            used: self.clock,
+        };
+        if let Some(replaced) = self.entries.insert(self.key.clone(), entry) {
+            let _ = self.recency.remove(&replaced.used);
+        } else if self.entries.len() > self.capacity {
+            if let Some(used) = self.recency.keys().next().copied() {
+                if let Some(evicted) = self.recency.remove(&used) {
+                    let _ = self.entries.remove(&evicted);
+                    self.stats.evictions += 1;
+                }
+            }
+        }
+        let _ = self.recency.insert(self.clock, self.key.clone());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_test() {
+        let mut validator = CachedValidator::new(10);
+        let rf = validator.parse("RF18 5390 0754 7034").unwrap();
+        assert_eq!(validator.parse("RF18539007547034"), Ok(rf.clone()));
+        assert_eq!(validator.parse(" RF18 5390 0754 7034 "), Ok(rf));
+        assert_eq!(validator.len(), 1);
+
+        let err = validator.parse("RF18 5390 0754 703_").unwrap_err();
+        assert_eq!(validator.parse("RF18 5390 0754 703_"), Err(err));
+        // the error of another spelling has other positions
+        assert!(matches!(
+            validator.parse("RF185390 0754703_"),
+            Err(ParseError::InvalidCharacter { position: 16, .. })
+        ));
+        assert_eq!(validator.len(), 2);
+
+        let stats = validator.stats();
+        assert_eq!((stats.hits(), stats.misses()), (3, 3));
+        assert!((stats.hit_rate() - 0.5).abs() < f64::EPSILON);
+
+        validator.clear();
+        assert!(validator.is_empty());
+        assert_eq!(validator.stats(), stats);
+
+        let mut validator = CachedValidator::new(usize::MAX);
+        assert!(validator.is_valid("RF18 5390 0754 7034"));
+        assert_eq!(validator.len(), 1);
+    }
+
+    #[test]
+    fn eviction_test() {
+        let refs = ["1", "2", "3"]
+            .iter()
+            .map(|body| RfCreditorReference::new(body).to_electronic_string())
+            .collect::<Vec<_>>();
+
+        let mut validator = CachedValidator::new(2);
+        assert!(validator.is_valid(&refs[0]));
+        assert!(validator.is_valid(&refs[1]));
+        assert!(validator.is_valid(&refs[0]));
+        // evicts the least recently used refs[1]
+        assert!(validator.is_valid(&refs[2]));
+        assert_eq!(validator.len(), 2);
+        assert!(validator.is_valid(&refs[0]));
+        assert!(validator.is_valid(&refs[1]));
+
+        let stats = validator.stats();
+        assert_eq!((stats.hits(), stats.misses()), (2, 4));
+        assert_eq!(stats.evictions(), 2);
+
+        let mut validator = CachedValidator::new(0);
+        assert!(validator.is_valid(&refs[0]));
+        assert!(validator.is_valid(&refs[0]));
+        assert!(validator.is_empty());
+        assert_eq!(validator.stats().misses(), 2);
+        assert!(validator.stats().hit_rate().abs() < f64::EPSILON);
+    }
+}
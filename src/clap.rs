@@ -0,0 +1,137 @@
+//! Arguments of creditor references for command line tools with
+//! [clap](https://docs.rs/clap)
+//!
+//! Available with feature `clap`.
+//!
+//! [`RfValueParser`] validates argument values like
+//! [`RfCreditorReference::parse_str`] and reports invalid references with
+//! the [`ParseError`](crate::ParseError) message before the main logic
+//! runs. `clap::value_parser!(RfCreditorReference)` selects it, so derived
+//! arguments of type `RfCreditorReference<'static>` use it too.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use clap::{value_parser, Arg, Command};
+//! use iso_11649::RfCreditorReference;
+//!
+//! let cmd = Command::new("pay").arg(
+//!     Arg::new("reference")
+//!         .long("reference")
+//!         .value_name("RF")
+//!         .value_parser(value_parser!(RfCreditorReference)),
+//! );
+//!
+//! let matches = cmd
+//!     .clone()
+//!     .try_get_matches_from(["pay", "--reference", "RF18 5390 0754 7034"])
+//!     .unwrap();
+//! assert_eq!(
+//!     matches.get_one::<RfCreditorReference>("reference").unwrap().as_electronic_str(),
+//!     "RF18539007547034"
+//! );
+//!
+//! assert!(cmd
+//!     .try_get_matches_from(["pay", "--reference", "RF19 5390 0754 7034"])
+//!     .is_err());
+//! ```
+
+use std::ffi::OsStr;
+
+use ::clap::{
+    builder::{TypedValueParser, ValueParserFactory},
+    error::ErrorKind,
+    Arg, Command, Error,
+};
+
+use crate::RfCreditorReference;
+
+/// `RfValueParser` parses argument values to [`RfCreditorReference`]s
+#[derive(Clone, Copy, Debug, Default)]
+pub struct RfValueParser;
+
+impl RfValueParser {
+    /// Creates [`RfValueParser`]
+    #[must_use]
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl TypedValueParser for RfValueParser {
+    type Value = RfCreditorReference<'static>;
+
+    fn parse_ref(
+        &self,
+        cmd: &Command,
+        arg: Option<&Arg>,
+        value: &OsStr,
+    ) -> Result<Self::Value, Error> {
+        let arg = arg.map_or_else(|| "...".to_string(), ToString::to_string);
+        let value = value.to_str().ok_or_else(|| {
+            Error::raw(
+                ErrorKind::InvalidUtf8,
+                format!("invalid UTF-8 for '{}'\n", arg),
+            )
+            .with_cmd(cmd)
+        })?;
+        RfCreditorReference::parse_str(value).map_err(|err| {
+            Error::raw(
+                ErrorKind::ValueValidation,
+                format!("invalid value '{}' for '{}': {}\n", value, arg, err),
+            )
+            .with_cmd(cmd)
+        })
+    }
+}
+
+impl ValueParserFactory for RfCreditorReference<'static> {
+    type Parser = RfValueParser;
+
+    fn value_parser() -> Self::Parser {
+        RfValueParser
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ::clap::value_parser;
+
+    use super::*;
+
+    fn cmd() -> Command {
+        Command::new("pay").arg(
+            Arg::new("reference")
+                .long("reference")
+                .value_name("RF")
+                .value_parser(value_parser!(RfCreditorReference)),
+        )
+    }
+
+    #[test]
+    fn parse_test() {
+        let matches = cmd()
+            .try_get_matches_from(["pay", "--reference", " RF71 2348 231"])
+            .unwrap();
+        assert_eq!(
+            matches.get_one::<RfCreditorReference>("reference"),
+            Some(&RfCreditorReference::new("2348231"))
+        );
+    }
+
+    #[test]
+    fn error_test() {
+        let err = cmd()
+            .try_get_matches_from(["pay", "--reference", "RF70 2348 231"])
+            .unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::ValueValidation);
+        let message = err.to_string();
+        assert!(message.contains("'--reference <RF>'"));
+        assert!(message.contains("checksum 70 does not match expected 71"));
+
+        let err = RfValueParser::new()
+            .parse_ref(&cmd(), None, OsStr::new("RF71 2348 23_"))
+            .unwrap_err();
+        assert!(err.to_string().contains("'RF71 2348 23_'"));
+    }
+}
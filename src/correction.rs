@@ -0,0 +1,391 @@
+//! Correction suggestions for mistyped creditor references
+
+use crate::{ParseError, RfCreditorReference, IDENTIFIER, MAX_LEN, MIN_LEN};
+
+/// Valid characters of a creditor reference in uppercase
+const ALPHABET: &str = "0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZ";
+
+/// Visually confusable letters and their likely intended digit
+const CONFUSABLES: &[(char, char)] = &[('I', '1'), ('l', '1'), ('O', '0'), ('o', '0'), ('S', '5')];
+
+/// A character replaced by [`normalize_confusables`]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Substitution {
+    /// The char index of the replaced character in the input
+    pub position: usize,
+    /// The character found in the input
+    pub from: char,
+    /// The character used instead
+    pub to: char,
+}
+
+/// Parses a `reference` with visually confusable characters replaced
+///
+/// If `reference` is not valid as it is, confusable letters (`O`, `I`,
+/// `l`, `S`) are replaced by their likely intended digits and validation
+/// is retried. First all confusable letters are replaced, then only the
+/// ones in the check digits.
+///
+/// Returns the parsed reference together with the applied
+/// [`Substitution`]s, so that the caller can confirm them.
+///
+/// # Examples
+///
+/// ```rust
+/// use iso_11649::correction::normalize_confusables;
+///
+/// let (rf, substitutions) = normalize_confusables("RFI8 539O 0754 7034").unwrap();
+///
+/// assert_eq!(rf.to_string(), "RF18 5390 0754 7034");
+/// assert_eq!(substitutions.len(), 2);
+/// assert_eq!(substitutions[0].position, 2);
+/// assert_eq!(substitutions[0].from, 'I');
+/// assert_eq!(substitutions[0].to, '1');
+/// ```
+///
+/// # Errors
+///
+/// Results in the [`ParseError`] of the unchanged `reference` when no
+/// replacement leads to a valid reference.
+///
+pub fn normalize_confusables(
+    reference: &str,
+) -> Result<(RfCreditorReference<'static>, Vec<Substitution>), ParseError> {
+    let err = match RfCreditorReference::parse_str(reference) {
+        Ok(rf) => return Ok((rf, Vec::new())),
+        Err(err) => err,
+    };
+
+    for check_digits_only in [false, true] {
+        let mut substitutions = Vec::new();
+        let candidate = reference
+            .chars()
+            .enumerate()
+            .scan(0, |n, (position, c)| {
+                // index of `c` in electronic format
+                let i = *n;
+                if c != ' ' {
+                    *n += 1;
+                }
+                let replacement = if (2..4).contains(&i) || (i >= 4 && !check_digits_only) {
+                    CONFUSABLES.iter().find(|&&(from, _)| from == c)
+                } else {
+                    None
+                };
+                Some(match replacement {
+                    Some(&(from, to)) => {
+                        substitutions.push(Substitution { position, from, to });
+                        to
+                    }
+                    None => c,
+                })
+            })
+            .collect::<String>();
+
+        if !substitutions.is_empty() {
+            if let Ok(rf) = RfCreditorReference::parse_str(&candidate) {
+                return Ok((rf, substitutions));
+            }
+        }
+    }
+
+    Err(err)
+}
+
+/// Suggests valid creditor references for a `reference` failing validation
+///
+/// Enumerates candidates with a single substituted character or two
+/// transposed adjacent characters and returns up to `max` of them
+/// passing the checksum validation. A lowercase identifier is corrected
+/// to `RF` first, then transpositions are suggested.
+///
+/// Returns no suggestions if `reference` is already valid or has an
+/// invalid length.
+///
+/// # Examples
+///
+/// ```rust
+/// use iso_11649::correction::suggest_corrections;
+///
+/// let suggestions = suggest_corrections("RF18 5390 0754 7043", 10);
+///
+/// assert!(suggestions
+///     .iter()
+///     .any(|rf| rf.to_string() == "RF18 5390 0754 7034"));
+/// ```
+///
+#[must_use]
+pub fn suggest_corrections(reference: &str, max: usize) -> Vec<RfCreditorReference<'static>> {
+    let mut chars = reference.chars().filter(|&c| c != ' ').collect::<Vec<_>>();
+    let mut suggestions = Vec::new();
+
+    if max == 0
+        || chars.len() < MIN_LEN
+        || chars.len() > MAX_LEN
+        || RfCreditorReference::parse_str(reference).is_ok()
+    {
+        return suggestions;
+    }
+
+    for c in chars.iter_mut().take(IDENTIFIER.len()) {
+        c.make_ascii_uppercase();
+    }
+    if push_valid(&mut suggestions, &chars, max) {
+        return suggestions;
+    }
+
+    for i in IDENTIFIER.len()..chars.len() - 1 {
+        if chars[i] != chars[i + 1] {
+            let mut candidate = chars.clone();
+            candidate.swap(i, i + 1);
+            if push_valid(&mut suggestions, &candidate, max) {
+                return suggestions;
+            }
+        }
+    }
+
+    for i in 0..chars.len() {
+        let replacements = match i {
+            0 | 1 => &IDENTIFIER[i..=i],
+            2 | 3 => &ALPHABET[..10],
+            _ => ALPHABET,
+        };
+        for c in replacements.chars() {
+            if !c.eq_ignore_ascii_case(&chars[i]) {
+                let mut candidate = chars.clone();
+                candidate[i] = c;
+                if push_valid(&mut suggestions, &candidate, max) {
+                    return suggestions;
+                }
+            }
+        }
+    }
+
+    suggestions
+}
+
+/// Finds the candidate closest to a mistyped `input`
+///
+/// The edit distance counts inserted, deleted and substituted characters
+/// and transposed adjacent characters. It is calculated on the electronic
+/// format ignoring case. Returns the first candidate with the smallest
+/// distance of at most `max_distance`, together with the distance.
+///
+/// # Examples
+///
+/// ```rust
+/// use iso_11649::{correction::find_closest, RfCreditorReference};
+///
+/// let outstanding = [
+///     RfCreditorReference::new("539007547034"),
+///     RfCreditorReference::new("539007547035"),
+///     RfCreditorReference::new("2348231"),
+/// ];
+///
+/// let (rf, distance) = find_closest("RF18 5390 0754 734", &outstanding, 2).unwrap();
+/// assert_eq!(rf, &outstanding[0]);
+/// assert_eq!(distance, 1);
+///
+/// assert!(find_closest("RF00 4711", &outstanding, 2).is_none());
+/// ```
+///
+#[must_use]
+pub fn find_closest<'c, 'r, I>(
+    input: &str,
+    candidates: I,
+    max_distance: usize,
+) -> Option<(&'c RfCreditorReference<'r>, usize)>
+where
+    I: IntoIterator<Item = &'c RfCreditorReference<'r>>,
+    'r: 'c,
+{
+    let input = input
+        .chars()
+        .filter(|&c| c != ' ')
+        .map(|c| c.to_ascii_uppercase())
+        .collect::<Vec<_>>();
+
+    let mut closest: Option<(&RfCreditorReference, usize)> = None;
+    for candidate in candidates {
+        let chars = candidate
+            .electronic_chars()
+            .map(|c| c.to_ascii_uppercase())
+            .collect::<Vec<_>>();
+        let distance = edit_distance(&input, &chars);
+        if distance <= max_distance && closest.map_or(true, |(_, closest)| distance < closest) {
+            closest = Some((candidate, distance));
+        }
+    }
+
+    closest
+}
+
+/// Calculates the optimal string alignment distance of `a` and `b`
+fn edit_distance(a: &[char], b: &[char]) -> usize {
+    // rows of the distance matrix for the last two and the current char of `a`
+    let mut before = vec![0; b.len() + 1];
+    let mut previous = (0..=b.len()).collect::<Vec<_>>();
+    let mut current = vec![0; b.len() + 1];
+
+    for i in 1..=a.len() {
+        current[0] = i;
+        for j in 1..=b.len() {
+            let cost = usize::from(a[i - 1] != b[j - 1]);
+            current[j] = (previous[j] + 1)
+                .min(current[j - 1] + 1)
+                .min(previous[j - 1] + cost);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                current[j] = current[j].min(before[j - 2] + 1);
+            }
+        }
+        std::mem::swap(&mut before, &mut previous);
+        std::mem::swap(&mut previous, &mut current);
+    }
+
+    previous[b.len()]
+}
+
+/// Pushes `candidate` to `suggestions` if it is a new valid reference
+///
+/// Returns true when `max` suggestions are reached.
+fn push_valid(
+    suggestions: &mut Vec<RfCreditorReference<'static>>,
+    candidate: &[char],
+    max: usize,
+) -> bool {
+    if let Ok(rf) = RfCreditorReference::parse_str(&String::from_iter(candidate)) {
+        if !suggestions.contains(&rf) {
+            suggestions.push(rf);
+        }
+    }
+
+    suggestions.len() >= max
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CORRECT_REF: &str = "RF18 5390 0754 7034";
+
+    fn suggests(reference: &str, expected: &str) -> bool {
+        suggest_corrections(reference, usize::MAX)
+            .iter()
+            .any(|rf| rf.to_string() == expected)
+    }
+
+    #[test]
+    fn substitution_test() {
+        assert!(suggests("RF18 5390 0754 7035", CORRECT_REF));
+        assert!(suggests("RF18 5390 O754 7034", CORRECT_REF));
+        assert!(suggests("RF19 5390 0754 7034", CORRECT_REF));
+        assert!(suggests("RE18 5390 0754 7034", CORRECT_REF));
+        assert!(suggests("RF18 5390 07_4 7034", CORRECT_REF));
+    }
+
+    #[test]
+    fn lowercase_identifier_test() {
+        assert_eq!(
+            suggest_corrections("rf18 5390 0754 7034", 1),
+            [RfCreditorReference::new("539007547034")]
+        );
+        assert!(suggests("rF18 5390 0754 7034", CORRECT_REF));
+        assert!(suggests("rf18 5390 0754 7043", CORRECT_REF));
+        assert!(suggests("rf19 5390 0754 7034", CORRECT_REF));
+    }
+
+    #[test]
+    fn transposition_test() {
+        assert!(suggests("RF18 5390 0754 7043", CORRECT_REF));
+        assert!(suggests("RF81 5390 0754 7034", CORRECT_REF));
+        assert!(suggests("RF18 3590 0754 7034", CORRECT_REF));
+    }
+
+    #[test]
+    fn normalize_confusables_test() {
+        let (rf, substitutions) = normalize_confusables(CORRECT_REF).unwrap();
+        assert_eq!(rf.to_string(), CORRECT_REF);
+        assert!(substitutions.is_empty());
+
+        let (rf, substitutions) = normalize_confusables("RF18 S39O O7S4 7O34").unwrap();
+        assert_eq!(rf.to_string(), CORRECT_REF);
+        assert_eq!(substitutions.len(), 5);
+
+        let (rf, substitutions) = normalize_confusables("RFl8 5390 0754 7034").unwrap();
+        assert_eq!(rf.to_string(), CORRECT_REF);
+        assert_eq!(
+            substitutions,
+            vec![Substitution {
+                position: 2,
+                from: 'l',
+                to: '1'
+            }]
+        );
+
+        // body letters are kept when only check digits are confusable
+        let (rf, substitutions) = normalize_confusables("RFO6 SOS").unwrap();
+        assert_eq!(rf.to_string(), "RF06 SOS");
+        assert_eq!(substitutions.len(), 1);
+
+        assert_eq!(
+            normalize_confusables("RF18 539O 0754 7035"),
+            Err(RfCreditorReference::parse_str("RF18 539O 0754 7035").unwrap_err())
+        );
+    }
+
+    #[test]
+    fn edit_distance_test() {
+        let distance = |a: &str, b: &str| {
+            edit_distance(
+                &a.chars().collect::<Vec<_>>(),
+                &b.chars().collect::<Vec<_>>(),
+            )
+        };
+        assert_eq!(distance("", ""), 0);
+        assert_eq!(distance("RF18", ""), 4);
+        assert_eq!(distance("", "RF18"), 4);
+        assert_eq!(distance("RF18", "RF18"), 0);
+        assert_eq!(distance("RF18", "RF81"), 1);
+        assert_eq!(distance("RF18", "RF19"), 1);
+        assert_eq!(distance("RF18", "RF1"), 1);
+        assert_eq!(distance("RF18", "RFX18"), 1);
+        assert_eq!(distance("RF18539", "RF81593"), 2);
+    }
+
+    #[test]
+    fn find_closest_test() {
+        let candidates = [
+            RfCreditorReference::new("539007547034"),
+            RfCreditorReference::new("2348231"),
+            RfCreditorReference::parse_str("RF63 ABCD 0754 EFGH").unwrap(),
+        ];
+
+        let (rf, distance) = find_closest("rf63abcd0754efgh", &candidates, 0).unwrap();
+        assert_eq!(rf, &candidates[2]);
+        assert_eq!(distance, 0);
+
+        let (rf, distance) = find_closest("RF71 2348 213", &candidates, 3).unwrap();
+        assert_eq!(rf, &candidates[1]);
+        assert_eq!(distance, 1);
+
+        let (rf, _) = find_closest("RF18 5390 0754 7034 9", candidates.iter(), 1).unwrap();
+        assert_eq!(rf, &candidates[0]);
+
+        assert!(find_closest("RF71 2348 213", &candidates, 0).is_none());
+        assert!(find_closest("RF71 2348 213", &[], 10).is_none());
+    }
+
+    #[test]
+    fn max_test() {
+        assert!(suggest_corrections("RF18 5390 0754 7035", 0).is_empty());
+        assert_eq!(suggest_corrections("RF18 5390 0754 7035", 1).len(), 1);
+        assert!(suggest_corrections("RF18 5390 0754 7035", 3).len() <= 3);
+    }
+
+    #[test]
+    fn no_suggestion_test() {
+        assert!(suggest_corrections(CORRECT_REF, 10).is_empty());
+        assert!(suggest_corrections("RF18", 10).is_empty());
+        assert!(suggest_corrections("RF18539007547034928TOOLONG", 10).is_empty());
+    }
+}
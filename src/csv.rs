@@ -0,0 +1,268 @@
+//! Validation of reference columns of CSV files with the
+//! [`csv`](https://docs.rs/csv) crate
+//!
+//! Available with feature `csv`.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use iso_11649::csv::{annotate_csv, validate_csv};
+//!
+//! let data = "invoice,reference\n4711,RF18 5390 0754 7034\n4712,RF19 5390 0754 7034\n";
+//!
+//! let mut reader = csv::Reader::from_reader(data.as_bytes());
+//! let results = validate_csv(&mut reader, "reference").unwrap();
+//! assert!(matches!(results[0], (2, Ok(_))));
+//! assert!(matches!(results[1], (3, Err(_))));
+//!
+//! let mut reader = csv::Reader::from_reader(data.as_bytes());
+//! let mut writer = csv::Writer::from_writer(Vec::new());
+//! let _ = annotate_csv(&mut reader, &mut writer, 1, "error").unwrap();
+//! assert_eq!(
+//!     String::from_utf8(writer.into_inner().unwrap()).unwrap(),
+//!     "invoice,reference,error\n\
+//!      4711,RF18 5390 0754 7034,\n\
+//!      4712,RF19 5390 0754 7034,checksum 19 does not match expected 18 [RF19 5390 0754 7034]\n"
+//! );
+//! ```
+
+use std::io::{Read, Write};
+
+use ::csv::{Position, Reader, StringRecord, Writer};
+
+use crate::{ParseError, RfCreditorReference};
+
+/// Results of the records with their line numbers
+pub type CsvResults = Vec<(u64, Result<RfCreditorReference<'static>, ParseError>)>;
+
+/// `Column` selects the column of the references
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Column<'a> {
+    /// Index of the column starting at 0
+    Index(usize),
+    /// Name of the column in the headers
+    Name(&'a str),
+}
+
+impl Column<'_> {
+    /// Index of the column in the records of `reader`
+    fn index<R: Read>(self, reader: &mut Reader<R>) -> Result<usize, CsvError> {
+        match self {
+            Column::Index(index) => Ok(index),
+            Column::Name(name) => reader
+                .headers()?
+                .iter()
+                .position(|header| header == name)
+                .ok_or_else(|| CsvError::UnknownColumn {
+                    name: name.to_string(),
+                }),
+        }
+    }
+}
+
+impl From<usize> for Column<'_> {
+    fn from(index: usize) -> Self {
+        Column::Index(index)
+    }
+}
+
+impl<'a> From<&'a str> for Column<'a> {
+    fn from(name: &'a str) -> Self {
+        Column::Name(name)
+    }
+}
+
+/// The `CsvError` enum is a collection of all the possible reasons
+/// processing a CSV file fails.
+///
+/// Invalid references are no `CsvError`, but part of the results.
+///
+/// New variants may be added in future versions.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum CsvError {
+    /// reading or writing the CSV file failed
+    Csv(::csv::Error),
+    /// no column of the headers has the name
+    UnknownColumn {
+        /// the name of the column
+        name: String,
+    },
+}
+
+impl std::fmt::Display for CsvError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> Result<(), std::fmt::Error> {
+        match self {
+            CsvError::Csv(err) => write!(f, "{}", err),
+            CsvError::UnknownColumn { name } => write!(f, "unknown column {}", name),
+        }
+    }
+}
+
+impl std::error::Error for CsvError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            CsvError::Csv(err) => Some(err),
+            CsvError::UnknownColumn { .. } => None,
+        }
+    }
+}
+
+impl From<::csv::Error> for CsvError {
+    fn from(err: ::csv::Error) -> Self {
+        CsvError::Csv(err)
+    }
+}
+
+/// Validates the references in `column` of the records of `reader` like
+/// [`RfCreditorReference::parse_str`]
+///
+/// Returns the results with the line numbers of the records. A record
+/// without the column results in [`ParseError::TooShort`].
+///
+/// # Errors
+///
+/// Results in [`CsvError::UnknownColumn`] when no header has the name of
+/// `column` and [`CsvError::Csv`] when reading fails.
+///
+pub fn validate_csv<'c, R: Read>(
+    reader: &mut Reader<R>,
+    column: impl Into<Column<'c>>,
+) -> Result<CsvResults, CsvError> {
+    let index = column.into().index(reader)?;
+
+    let mut results = Vec::new();
+    let mut record = StringRecord::new();
+    while reader.read_record(&mut record)? {
+        results.push(validate_record(&record, index));
+    }
+    Ok(results)
+}
+
+/// Validates the references in `column` like [`validate_csv`] and writes
+/// the records of `reader` with an additional column of the error
+/// messages to `writer`
+///
+/// The additional column is empty for valid references and has the header
+/// `error_header` when `reader` has headers. Shorter records are padded
+/// with empty fields, so the additional column stays aligned.
+///
+/// # Errors
+///
+/// Results in [`CsvError::UnknownColumn`] when no header has the name of
+/// `column` and [`CsvError::Csv`] when reading or writing fails.
+///
+pub fn annotate_csv<'c, R: Read, W: Write>(
+    reader: &mut Reader<R>,
+    writer: &mut Writer<W>,
+    column: impl Into<Column<'c>>,
+    error_header: &str,
+) -> Result<CsvResults, CsvError> {
+    let index = column.into().index(reader)?;
+
+    let mut width = index + 1;
+    if reader.has_headers() {
+        let mut headers = reader.headers()?.clone();
+        width = width.max(headers.len());
+        headers.push_field(error_header);
+        writer.write_record(&headers)?;
+    }
+
+    let mut results = Vec::new();
+    let mut record = StringRecord::new();
+    while reader.read_record(&mut record)? {
+        let result = validate_record(&record, index);
+        while record.len() < width {
+            record.push_field("");
+        }
+        match &result.1 {
+            Ok(_) => record.push_field(""),
+            Err(err) => record.push_field(&err.to_string()),
+        }
+        writer.write_record(&record)?;
+        results.push(result);
+    }
+    writer.flush().map_err(::csv::Error::from)?;
+
+    Ok(results)
+}
+
+/// Validates the reference at `index` of `record`
+fn validate_record(
+    record: &StringRecord,
+    index: usize,
+) -> (u64, Result<RfCreditorReference<'static>, ParseError>) {
+    let line = record.position().map_or(0, Position::line);
+    let reference = record.get(index).unwrap_or_default();
+    (line, RfCreditorReference::parse_str(reference))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DATA: &str = "invoice,reference\n\
+                        4711,RF18 5390 0754 7034\n\
+                        4712,RF18 5390 0754 703_\n\
+                        4713\n";
+
+    #[test]
+    fn validate_csv_test() {
+        let mut reader = ::csv::ReaderBuilder::new()
+            .flexible(true)
+            .from_reader(DATA.as_bytes());
+        let results = validate_csv(&mut reader, "reference").unwrap();
+        assert_eq!(results.len(), 3);
+        assert!(matches!(results[0], (2, Ok(_))));
+        assert!(matches!(
+            results[1],
+            (3, Err(ParseError::InvalidCharacter { .. }))
+        ));
+        assert!(matches!(results[2], (4, Err(ParseError::TooShort { .. }))));
+
+        let mut reader = ::csv::ReaderBuilder::new()
+            .has_headers(false)
+            .flexible(true)
+            .from_reader(DATA.as_bytes());
+        let results = validate_csv(&mut reader, 1).unwrap();
+        assert_eq!(results.len(), 4);
+        assert!(matches!(results[0], (1, Err(_))));
+
+        let mut reader = Reader::from_reader(DATA.as_bytes());
+        assert!(matches!(
+            validate_csv(&mut reader, "ref"),
+            Err(CsvError::UnknownColumn { .. })
+        ));
+    }
+
+    #[test]
+    fn annotate_csv_test() {
+        let mut reader = ::csv::ReaderBuilder::new()
+            .flexible(true)
+            .from_reader(DATA.as_bytes());
+        let mut writer = ::csv::WriterBuilder::new()
+            .flexible(true)
+            .from_writer(Vec::new());
+        let results = annotate_csv(&mut reader, &mut writer, "reference", "error").unwrap();
+        assert_eq!(results.len(), 3);
+
+        let annotated = String::from_utf8(writer.into_inner().unwrap()).unwrap();
+        let lines = annotated.lines().collect::<Vec<_>>();
+        assert_eq!(lines[0], "invoice,reference,error");
+        assert_eq!(lines[1], "4711,RF18 5390 0754 7034,");
+        assert!(lines[2].starts_with("4712,RF18 5390 0754 703_,invalid character"));
+        assert!(lines[3].starts_with("4713,,reference is too short"));
+
+        let mut reader = ::csv::ReaderBuilder::new()
+            .flexible(true)
+            .from_reader("invoice,reference,amount\n4713\n".as_bytes());
+        let mut writer = ::csv::WriterBuilder::new()
+            .flexible(true)
+            .from_writer(Vec::new());
+        let _ = annotate_csv(&mut reader, &mut writer, "reference", "error").unwrap();
+        let annotated = String::from_utf8(writer.into_inner().unwrap()).unwrap();
+        let lines = annotated.lines().collect::<Vec<_>>();
+        assert_eq!(lines[0], "invoice,reference,amount,error");
+        assert!(lines[1].starts_with("4713,,,reference is too short"));
+    }
+}
@@ -0,0 +1,307 @@
+//! Parsing of creditor references without heap allocation
+
+use std::{borrow::Cow, fmt::Display};
+
+use crate::{
+    iso7064::{CheckCharacterSystem, Mod97_10},
+    print::PrintReference,
+    ParseError, RfCreditorReference, GEN_PREFIX, IDENTIFIER, MAX_LEN, MIN_LEN,
+};
+
+/// `ElectronicReference` is a valid creditor reference in electronic
+/// format stored inline
+///
+/// Created by [`parse_bytes`] without heap allocation.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct ElectronicReference {
+    buf: [u8; MAX_LEN],
+    len: usize,
+    checksum: u8,
+}
+
+impl ElectronicReference {
+    /// Borrows the reference in electronic format
+    #[must_use]
+    pub fn as_str(&self) -> &str {
+        // always ASCII after validation
+        std::str::from_utf8(&self.buf[..self.len]).unwrap_or_default()
+    }
+
+    /// Borrows the body without identifier and check digits
+    #[must_use]
+    pub fn body(&self) -> &str {
+        &self.as_str()[GEN_PREFIX.len()..]
+    }
+
+    /// Checksum of the check digits
+    #[must_use]
+    pub fn checksum(&self) -> u8 {
+        self.checksum
+    }
+}
+
+impl AsRef<str> for ElectronicReference {
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl Display for ElectronicReference {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.pad(self.as_str())
+    }
+}
+
+impl From<ElectronicReference> for RfCreditorReference<'_> {
+    fn from(electronic_reference: ElectronicReference) -> Self {
+        Self {
+            checksum: electronic_reference.checksum(),
+            creditor_reference: PrintReference::new(electronic_reference.as_str()),
+            electronic_reference: Cow::from(electronic_reference.as_str().to_string()),
+        }
+    }
+}
+
+/// Parses a creditor reference given as UTF-8 bytes without heap
+/// allocation
+///
+/// Validation and checksum are done in a single pass. Spaces are ignored
+/// and errors are the same like with
+/// [`RfCreditorReference::parse_str`](crate::RfCreditorReference::parse_str),
+/// which is built on top. Positions in errors are char indices. Only
+/// the errors allocate.
+///
+/// # Examples
+///
+/// ```rust
+/// let rf = iso_11649::parse_bytes(b"RF18 5390 0754 7034").unwrap();
+/// assert_eq!(rf.as_str(), "RF18539007547034");
+/// assert_eq!(rf.body(), "539007547034");
+/// assert_eq!(rf.checksum(), 18);
+///
+/// assert!(iso_11649::parse_bytes(b"RF19 5390 0754 7034").is_err());
+/// ```
+///
+/// # Errors
+///
+/// Results in [`ParseError`]s when there is some problem with
+/// parsing the `reference`.
+///
+pub fn parse_bytes(reference: &[u8]) -> Result<ElectronicReference, ParseError> {
+    let input = || String::from_utf8_lossy(reference).into_owned();
+
+    let mut electronic = ElectronicReference {
+        buf: [0; MAX_LEN],
+        len: 0,
+        checksum: 0,
+    };
+    // number of chars without spaces
+    let mut len = 0;
+    // byte index and char index of the check digits
+    let mut check_digits = [(0, 0); 2];
+    // byte index and char index of the first invalid char of the body
+    let mut invalid = None;
+    let mut remainder = 0;
+    // number of continuation bytes expected after a UTF-8 lead byte
+    let mut continuation = 0;
+
+    let chars = reference
+        .iter()
+        .enumerate()
+        .filter(|&(_, &b)| {
+            if b & 0xc0 == 0x80 && continuation > 0 {
+                continuation -= 1;
+                return false;
+            }
+            continuation = match b {
+                0xc0..=0xdf => 1,
+                0xe0..=0xef => 2,
+                0xf0..=0xf7 => 3,
+                _ => 0,
+            };
+            true
+        })
+        .enumerate();
+    for (position, (index, &b)) in chars {
+        if b == b' ' {
+            continue;
+        }
+
+        if len < MAX_LEN {
+            electronic.buf[len] = b;
+        }
+        match len {
+            0 | 1 => {}
+            2 | 3 => check_digits[len - 2] = (index, position),
+            _ => match Mod97_10::push_byte(remainder, b) {
+                Some(r) => remainder = r,
+                None => {
+                    if invalid.is_none() {
+                        invalid = Some((index, position));
+                    }
+                }
+            },
+        }
+        len += 1;
+    }
+
+    if len < MIN_LEN {
+        return Err(ParseError::TooShort {
+            input: input(),
+            len,
+            min: MIN_LEN,
+        });
+    } else if len > MAX_LEN {
+        return Err(ParseError::TooLong {
+            input: input(),
+            len,
+            max: MAX_LEN,
+        });
+    } else if &electronic.buf[..2] != IDENTIFIER.as_bytes() {
+        return Err(ParseError::InvalidIdentifier { input: input() });
+    } else if let Some((index, position)) = invalid {
+        return Err(ParseError::InvalidCharacter {
+            input: input(),
+            character: char_at(reference, index),
+            position,
+        });
+    }
+
+    let digits = &electronic.buf[2..4];
+    if !digits.iter().all(u8::is_ascii_digit) {
+        return Err(invalid_check_digits(reference, check_digits));
+    }
+    let checksum = (digits[0] - b'0') * 10 + (digits[1] - b'0');
+
+    let expected = IDENTIFIER
+        .chars()
+        .try_fold(remainder, Mod97_10::push)
+        .unwrap_or_default();
+    let valid = [digits[0], digits[1]]
+        .iter()
+        .try_fold(expected, |r, &b| Mod97_10::push_byte(r, b))
+        == Some(1);
+
+    if valid {
+        electronic.len = len;
+        electronic.checksum = checksum;
+        Ok(electronic)
+    } else {
+        let expected = ['0', '0']
+            .iter()
+            .try_fold(expected, |r, &c| Mod97_10::push(r, c))
+            .unwrap_or_default();
+        #[allow(clippy::cast_possible_truncation)]
+        Err(ParseError::InvalidChecksum {
+            input: input(),
+            expected: (98 - expected) as u8,
+            found: checksum,
+        })
+    }
+}
+
+/// Error of the check digits at the byte and char indices `check_digits`
+fn invalid_check_digits(reference: &[u8], check_digits: [(usize, usize); 2]) -> ParseError {
+    let input = String::from_utf8_lossy(reference).into_owned();
+    let digits = check_digits
+        .iter()
+        .map(|&(index, _)| char_at(reference, index))
+        .collect::<String>();
+
+    match digits.parse::<u8>() {
+        Err(source) => ParseError::InvalidCheckDigits { input, source },
+        // a sign is no digit
+        Ok(_) => ParseError::InvalidCharacter {
+            input,
+            character: char_at(reference, check_digits[0].0),
+            position: check_digits[0].1,
+        },
+    }
+}
+
+/// Char starting at byte `index` of `reference`
+fn char_at(reference: &[u8], index: usize) -> char {
+    String::from_utf8_lossy(&reference[index..])
+        .chars()
+        .next()
+        .unwrap_or(char::REPLACEMENT_CHARACTER)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_bytes_test() {
+        let rf = parse_bytes(" RF63 ABCD 0754 efgh ".as_bytes()).unwrap();
+        assert_eq!(rf.as_str(), "RF63ABCD0754efgh");
+        assert_eq!(rf.body(), "ABCD0754efgh");
+        assert_eq!(rf.checksum(), 63);
+        assert_eq!(format!("{:>18}", rf), "  RF63ABCD0754efgh");
+
+        let reference = RfCreditorReference::from(rf);
+        assert_eq!(reference, RfCreditorReference::new("ABCD0754efgh"));
+        assert_eq!(reference.to_string(), "RF63 ABCD 0754 efgh");
+
+        assert!(matches!(
+            parse_bytes("RF18 5390 0754 7034 ä".as_bytes()),
+            Err(ParseError::InvalidCharacter {
+                character: 'ä',
+                position: 20,
+                ..
+            })
+        ));
+        assert!(matches!(
+            parse_bytes(b"RF18 5390 0754 \xff"),
+            Err(ParseError::InvalidCharacter {
+                character: char::REPLACEMENT_CHARACTER,
+                position: 15,
+                ..
+            })
+        ));
+        assert!(matches!(
+            parse_bytes(b"RF18\x80539007547034"),
+            Err(ParseError::InvalidCharacter {
+                character: char::REPLACEMENT_CHARACTER,
+                position: 4,
+                ..
+            })
+        ));
+        assert!(matches!(
+            parse_bytes(b"RF+1539007547034"),
+            Err(ParseError::InvalidCharacter {
+                character: '+',
+                position: 2,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn errors_test() {
+        assert!(matches!(
+            parse_bytes(b"RF 1 8"),
+            Err(ParseError::TooShort { len: 4, .. })
+        ));
+        assert!(matches!(
+            parse_bytes(b"RF93539007547034928301234X"),
+            Err(ParseError::TooLong { len: 26, .. })
+        ));
+        assert!(matches!(
+            parse_bytes(b"rf18539007547034"),
+            Err(ParseError::InvalidIdentifier { .. })
+        ));
+        assert!(matches!(
+            parse_bytes("RFä1539007547034".as_bytes()),
+            Err(ParseError::InvalidCheckDigits { .. })
+        ));
+        assert_eq!(
+            parse_bytes(b"RF19 5390 0754 7034"),
+            Err(ParseError::InvalidChecksum {
+                input: "RF19 5390 0754 7034".to_string(),
+                expected: 18,
+                found: 19
+            })
+        );
+    }
+}
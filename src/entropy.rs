@@ -0,0 +1,160 @@
+//! Entropy of random references and collision estimates
+//!
+//! A [`RandomConfig`] chooses charset and body length of random
+//! references, so that the probability of any collision within a
+//! population of references stays below a target. With feature `rand`
+//! references are generated with `RandomConfig::generate`.
+//!
+//! # Guarantees
+//!
+//! The estimate is the birthday bound `1 - exp(-n(n - 1) / 2N)` for a
+//! population of `n` references out of `N` possible bodies. It holds for
+//! uniformly distributed bodies of a cryptographically secure random
+//! number generator only. Letters are counted ignoring case, because
+//! the check digits are computed case-insensitively and generated letters
+//! are uppercase.
+//!
+//! A low collision probability is no guarantee of uniqueness. Reject
+//! the rare duplicates with a [`ReferenceRegistry`](crate::registry::ReferenceRegistry)
+//! where this matters.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use iso_11649::{entropy::RandomConfig, layout::Charset};
+//!
+//! // one million payment links with a collision probability of 1e-6
+//! let config = RandomConfig::for_collision_probability(1_000_000, 1e-6).unwrap();
+//! assert_eq!(config.charset(), Charset::Numeric);
+//! assert_eq!(config.body_len(), 18);
+//! assert!(config.collision_probability(1_000_000) <= 1e-6);
+//!
+//! // one billion only fits with letters
+//! let config = RandomConfig::for_collision_probability(1_000_000_000, 1e-9).unwrap();
+//! assert_eq!(config.charset(), Charset::Alphanumeric);
+//! assert_eq!(config.body_len(), 18);
+//!
+//! assert!(RandomConfig::for_collision_probability(1_000_000_000_000, 1e-12).is_none());
+//! ```
+
+use crate::{layout::Charset, MAX_BODY_LEN};
+
+/// `RandomConfig` is the charset and body length of random references
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct RandomConfig {
+    charset: Charset,
+    len: usize,
+}
+
+impl RandomConfig {
+    /// Chooses the shortest body with a collision probability of at most
+    /// `probability` within `population` references
+    ///
+    /// Digits are preferred, because some banks reject letters. Letters
+    /// and digits are chosen when digits do not fit into [`MAX_BODY_LEN`].
+    ///
+    /// Returns `None` if no body fits or `probability` is not within `0.0`
+    /// and `1.0` exclusive.
+    #[must_use]
+    pub fn for_collision_probability(population: u64, probability: f64) -> Option<Self> {
+        Self::with_charset(Charset::Numeric, population, probability)
+            .or_else(|| Self::with_charset(Charset::Alphanumeric, population, probability))
+    }
+
+    /// Chooses the shortest body of `charset` with a collision probability
+    /// of at most `probability` within `population` references
+    ///
+    /// Returns `None` if no body fits into [`MAX_BODY_LEN`] or
+    /// `probability` is not within `0.0` and `1.0` exclusive.
+    #[must_use]
+    pub fn with_charset(charset: Charset, population: u64, probability: f64) -> Option<Self> {
+        if !(probability > 0.0 && probability < 1.0) {
+            return None;
+        }
+
+        (1..=MAX_BODY_LEN)
+            .map(|len| Self { charset, len })
+            .find(|config| config.collision_probability(population) <= probability)
+    }
+
+    /// Charset of the body
+    #[must_use]
+    pub fn charset(self) -> Charset {
+        self.charset
+    }
+
+    /// Length of the body
+    #[must_use]
+    pub fn body_len(self) -> usize {
+        self.len
+    }
+
+    /// Entropy of the body in bits
+    #[must_use]
+    pub fn bits(self) -> f64 {
+        f64::from(self.charset.size()).log2() * self.len_f64()
+    }
+
+    /// Estimates the probability of any collision within `population`
+    /// references
+    #[must_use]
+    pub fn collision_probability(self, population: u64) -> f64 {
+        #[allow(clippy::cast_precision_loss)]
+        let n = population as f64;
+        let space = f64::from(self.charset.size()).powf(self.len_f64());
+
+        -(-(n * (n - 1.0) / (2.0 * space))).exp_m1()
+    }
+
+    /// Length of the body as `f64`
+    #[allow(clippy::cast_precision_loss)]
+    fn len_f64(self) -> f64 {
+        self.len as f64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn for_collision_probability_test() {
+        let config = RandomConfig::for_collision_probability(100, 0.5).unwrap();
+        assert_eq!((config.charset(), config.body_len()), (Charset::Numeric, 4));
+
+        let config = RandomConfig::for_collision_probability(1_000_000_000, 1e-9).unwrap();
+        assert_eq!(
+            (config.charset(), config.body_len()),
+            (Charset::Alphanumeric, 18)
+        );
+
+        assert!(RandomConfig::for_collision_probability(1, 0.0).is_none());
+        assert!(RandomConfig::for_collision_probability(1, 1.0).is_none());
+        assert!(RandomConfig::for_collision_probability(1, f64::NAN).is_none());
+        assert!(RandomConfig::for_collision_probability(u64::MAX, 0.5).is_none());
+    }
+
+    #[test]
+    fn with_charset_test() {
+        let config = RandomConfig::with_charset(Charset::Alphabetic, 1_000_000, 1e-6).unwrap();
+        assert_eq!(config.body_len(), 13);
+        assert!(RandomConfig::with_charset(Charset::Numeric, 1_000_000_000, 1e-9).is_none());
+
+        // no collision is possible with a single reference
+        let config = RandomConfig::with_charset(Charset::Numeric, 1, 1e-12).unwrap();
+        assert_eq!(config.body_len(), 1);
+    }
+
+    #[test]
+    fn collision_probability_test() {
+        let config = RandomConfig::with_charset(Charset::Numeric, 2, 0.5).unwrap();
+        assert_eq!(config.body_len(), 1);
+        assert!((config.collision_probability(2) - 0.095).abs() < 0.001);
+        assert!(config.collision_probability(0).abs() < f64::EPSILON);
+
+        let config = RandomConfig::for_collision_probability(1_000_000, 1e-6).unwrap();
+        assert!(config.collision_probability(1_000_000) <= 1e-6);
+        assert!(config.collision_probability(10_000_000) > 1e-6);
+        assert!((config.bits() - 59.79).abs() < 0.01);
+    }
+}
@@ -0,0 +1,568 @@
+//! Extraction of creditor references from free text
+//!
+//! [`find_references`] scans remittance information like
+//! `"payment for RF18 5390 0754 7034 thanks"` for creditor references in
+//! print or electronic format and yields the valid ones.
+//!
+//! A candidate starts with `RF` at the beginning of a word and continues
+//! with words of 0-9, a-z and A-Z separated by single spaces or dashes.
+//! The longest candidate with a valid checksum is taken, so trailing words
+//! are no part of the reference.
+//!
+//! # Examples
+//!
+//! ```rust
+//! let text = "payment for RF18 5390 0754 7034 thanks, RF19 is no reference, RF712348231.";
+//!
+//! let references = iso_11649::find_references(text)
+//!     .map(|rf| rf.to_string())
+//!     .collect::<Vec<_>>();
+//!
+//! assert_eq!(references, ["RF18 5390 0754 7034", "RF71 2348 231"]);
+//! ```
+//!
+//! [`find_matches`] yields the positions of the references in the text
+//! together with the surrounding words, e.g. to highlight them or for
+//! audit trails.
+//!
+//! ```rust
+//! use iso_11649::extract::find_matches;
+//!
+//! let text = "Invoice 4711, payment for RF18 5390 0754 7034 thanks, John";
+//! let m = find_matches(text, 2).next().unwrap();
+//!
+//! assert_eq!(m.range, 26..45);
+//! assert_eq!(&text[m.range.clone()], "RF18 5390 0754 7034");
+//! assert_eq!(m.context, "payment for RF18 5390 0754 7034 thanks, John");
+//! assert_eq!(m.reference.to_string(), "RF18 5390 0754 7034");
+//! ```
+//!
+//! [`find_repaired_matches`] additionally repairs candidates with
+//! confusable characters of OCR, e.g. of scanned paper remittance advices.
+//!
+//! [`normalize_in_place`] rewrites the references of a text to their
+//! canonical electronic format, so that exact matches work.
+//!
+//! ```rust
+//! use iso_11649::extract::normalize_in_place;
+//!
+//! assert_eq!(
+//!     normalize_in_place("payment for RF18-5390-0754-7034 thanks"),
+//!     "payment for RF18539007547034 thanks"
+//! );
+//! ```
+
+use std::{borrow::Cow, ops::Range};
+
+use crate::{correction::Substitution, RfCreditorReference, GEN_PREFIX, IDENTIFIER, MAX_LEN};
+
+/// `Match` is a creditor reference found in a text
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Match<'t> {
+    /// The valid reference
+    pub reference: RfCreditorReference<'static>,
+    /// The byte range of the reference in the text
+    pub range: Range<usize>,
+    /// The reference with the surrounding words of the text
+    pub context: &'t str,
+    /// The substitutions of confusable characters repairing the reference
+    ///
+    /// The positions are relative to the start of the reference in the
+    /// text. Always empty without repair, see [`find_repaired_matches`].
+    pub substitutions: Vec<Substitution>,
+}
+
+impl Match<'_> {
+    /// Checks if the reference is repaired by substitutions
+    #[must_use]
+    pub fn is_repaired(&self) -> bool {
+        !self.substitutions.is_empty()
+    }
+}
+
+/// Finds the valid creditor references in `text`
+///
+/// See the [module](crate::extract) for the rules of the scanner.
+pub fn find_references(text: &str) -> impl Iterator<Item = RfCreditorReference<'static>> + '_ {
+    Scanner::new(text, 0).map(|(_, rf, _)| rf)
+}
+
+/// Finds the valid creditor references in `text` with their byte ranges
+/// and up to `context_words` surrounding words before and after them
+///
+/// Words are separated by whitespace. Punctuation next to a reference
+/// counts as a word.
+///
+/// See the [module](crate::extract) for the rules of the scanner.
+pub fn find_matches(text: &str, context_words: usize) -> impl Iterator<Item = Match<'_>> {
+    matches(Scanner::new(text, 0), context_words)
+}
+
+/// Finds the creditor references in `text` like [`find_matches`] and
+/// repairs invalid candidates with OCR errors
+///
+/// Confusable characters `O`/`0`, `I`/`l`/`1`, `B`/`8` and `S`/`5` of
+/// invalid candidates are substituted by each other, with the fewest of at
+/// most `max_substitutions` substitutions. Digits are only replaced by
+/// letters in words with letters. `max_substitutions` is bounded by
+/// [`MAX_SUBSTITUTIONS`], because each substitution multiplies the
+/// attempts.
+///
+/// Valid candidates are preferred over repaired ones. Each substitution
+/// raises the chance of a wrong reference with a valid checksum, so the
+/// caller should confirm repaired matches.
+///
+/// # Examples
+///
+/// ```rust
+/// use iso_11649::extract::find_repaired_matches;
+///
+/// let m = find_repaired_matches("paid RF18 539O 0754 7O34", 0, 2).next().unwrap();
+///
+/// assert!(m.is_repaired());
+/// assert_eq!(m.reference.to_string(), "RF18 5390 0754 7034");
+/// assert_eq!(m.substitutions.len(), 2);
+/// assert_eq!(m.substitutions[0].position, 8);
+/// assert_eq!((m.substitutions[0].from, m.substitutions[0].to), ('O', '0'));
+/// ```
+///
+pub fn find_repaired_matches(
+    text: &str,
+    context_words: usize,
+    max_substitutions: usize,
+) -> impl Iterator<Item = Match<'_>> {
+    matches(Scanner::new(text, max_substitutions), context_words)
+}
+
+/// Matches of `scanner` with up to `context_words` surrounding words
+fn matches(scanner: Scanner<'_>, context_words: usize) -> impl Iterator<Item = Match<'_>> {
+    let text = scanner.text;
+    scanner.map(move |(range, reference, substitutions)| {
+        let context = context(text, &range, context_words);
+        Match {
+            reference,
+            range,
+            context: &text[context],
+            substitutions,
+        }
+    })
+}
+
+/// Rewrites the valid creditor references in `text` to their electronic
+/// format in uppercase and leaves the rest untouched
+///
+/// Borrows `text` if there is nothing to rewrite.
+///
+/// See the [module](crate::extract) for the rules of the scanner.
+#[must_use]
+pub fn normalize_in_place(text: &str) -> Cow<'_, str> {
+    let mut normalized = String::new();
+    // end of the text copied to `normalized`
+    let mut copied = 0;
+
+    for (range, rf, _) in Scanner::new(text, 0) {
+        let canonical = rf.as_electronic_str().to_ascii_uppercase();
+        if text[range.clone()] != canonical {
+            normalized.push_str(&text[copied..range.start]);
+            normalized.push_str(&canonical);
+            copied = range.end;
+        }
+    }
+
+    if copied == 0 {
+        Cow::Borrowed(text)
+    } else {
+        normalized.push_str(&text[copied..]);
+        Cow::Owned(normalized)
+    }
+}
+
+/// Byte range of `range` of `text` extended by `words` words before and
+/// after
+fn context(text: &str, range: &Range<usize>, words: usize) -> Range<usize> {
+    let mut start = range.start;
+    let mut end = range.end;
+    for _ in 0..words {
+        let before = text[..start].trim_end();
+        start = before.trim_end_matches(|c: char| !c.is_whitespace()).len();
+
+        let after = text[end..].trim_start();
+        end = text.len() - after.len() + after.find(char::is_whitespace).unwrap_or(after.len());
+    }
+    start..end
+}
+
+/// Maximum number of substitutions of [`find_repaired_matches`]
+pub const MAX_SUBSTITUTIONS: usize = 3;
+
+/// Confusable characters of OCR and their replacements
+///
+/// Digits are only replaced by letters in words with letters.
+const OCR_CONFUSABLES: &[(u8, u8)] = &[
+    (b'O', b'0'),
+    (b'I', b'1'),
+    (b'l', b'1'),
+    (b'B', b'8'),
+    (b'S', b'5'),
+    (b'0', b'O'),
+    (b'1', b'I'),
+    (b'8', b'B'),
+    (b'5', b'S'),
+];
+
+/// `Candidate` is a candidate in electronic format without separators
+struct Candidate {
+    buf: [u8; MAX_LEN],
+    len: usize,
+    /// Byte offsets of the characters in the candidate with separators
+    offsets: [usize; MAX_LEN],
+    /// If the word of the characters has letters
+    has_letters: [bool; MAX_LEN],
+}
+
+impl Candidate {
+    /// Creates [`Candidate`] of the ASCII `bytes` with separators
+    fn new(bytes: &[u8]) -> Self {
+        let mut candidate = Self {
+            buf: [0; MAX_LEN],
+            len: 0,
+            offsets: [0; MAX_LEN],
+            has_letters: [false; MAX_LEN],
+        };
+        let mut word_start = 0;
+        for (offset, &b) in bytes.iter().enumerate() {
+            if b.is_ascii_alphanumeric() {
+                candidate.buf[candidate.len] = b;
+                candidate.offsets[candidate.len] = offset;
+                candidate.len += 1;
+            } else {
+                candidate.mark_letters(word_start);
+                word_start = candidate.len;
+            }
+        }
+        candidate.mark_letters(word_start);
+        candidate
+    }
+
+    /// Marks the characters of the last word starting at `word_start` if it
+    /// has letters
+    fn mark_letters(&mut self, word_start: usize) {
+        let word = word_start..self.len;
+        let has_letters = self.buf[word.clone()].iter().any(u8::is_ascii_alphabetic);
+        for has in &mut self.has_letters[word] {
+            *has = has_letters;
+        }
+    }
+
+    /// Parses the candidate
+    fn parse(&self) -> Option<RfCreditorReference<'static>> {
+        let candidate = std::str::from_utf8(&self.buf[..self.len]).unwrap_or_default();
+        RfCreditorReference::parse_str(candidate).ok()
+    }
+
+    /// Repairs the candidate with the fewest of at most `max` substitutions
+    /// of confusable characters
+    fn repair(&mut self, max: usize) -> Option<(RfCreditorReference<'static>, Vec<Substitution>)> {
+        let mut replacements = Vec::new();
+        for i in IDENTIFIER.len()..self.len {
+            for &(from, to) in OCR_CONFUSABLES {
+                let digit_to_letter = from.is_ascii_digit();
+                if self.buf[i] == from
+                    && (!digit_to_letter || (i >= GEN_PREFIX.len() && self.has_letters[i]))
+                {
+                    replacements.push((i, to));
+                }
+            }
+        }
+
+        let mut chosen = Vec::new();
+        for count in 1..=max {
+            if let Some(rf) = self.search(&replacements, 0, count, &mut chosen) {
+                let substitutions = chosen
+                    .iter()
+                    .map(|&(i, from)| Substitution {
+                        position: self.offsets[i],
+                        from: char::from(from),
+                        to: char::from(self.buf[i]),
+                    })
+                    .collect();
+                return Some((rf, substitutions));
+            }
+        }
+        None
+    }
+
+    /// Searches the valid candidate with `count` of the `replacements`
+    /// starting at `next`
+    ///
+    /// `chosen` holds the indices and original characters of the applied
+    /// replacements.
+    fn search(
+        &mut self,
+        replacements: &[(usize, u8)],
+        next: usize,
+        count: usize,
+        chosen: &mut Vec<(usize, u8)>,
+    ) -> Option<RfCreditorReference<'static>> {
+        if count == 0 {
+            return self.parse();
+        }
+
+        for (n, &(i, to)) in replacements.iter().enumerate().skip(next) {
+            if chosen.iter().any(|&(j, _)| j == i) {
+                continue;
+            }
+            chosen.push((i, self.buf[i]));
+            self.buf[i] = to;
+            if let Some(rf) = self.search(replacements, n + 1, count - 1, chosen) {
+                return Some(rf);
+            }
+            if let Some((_, from)) = chosen.pop() {
+                self.buf[i] = from;
+            }
+        }
+        None
+    }
+}
+
+/// `Scanner` iterates over the byte ranges, references and substitutions
+/// of the valid candidates of a text
+#[derive(Clone, Debug)]
+struct Scanner<'t> {
+    text: &'t str,
+    /// Byte index to continue the search
+    position: usize,
+    /// Maximum number of substitutions to repair candidates
+    max_substitutions: usize,
+}
+
+impl<'t> Scanner<'t> {
+    /// Creates [`Scanner`] of `text` repairing candidates with at most
+    /// `max_substitutions`
+    fn new(text: &'t str, max_substitutions: usize) -> Self {
+        Self {
+            text,
+            position: 0,
+            max_substitutions: max_substitutions.min(MAX_SUBSTITUTIONS),
+        }
+    }
+
+    /// Byte ends of the candidates starting at `start`, shortest first
+    ///
+    /// Returns the ends and their number.
+    fn candidate_ends(&self, start: usize) -> ([usize; MAX_LEN], usize) {
+        let bytes = self.text.as_bytes();
+        let mut ends = [0; MAX_LEN];
+        let mut count = 0;
+        let mut len = 0;
+        let mut i = start;
+
+        while count < MAX_LEN {
+            let end = i + bytes[i..]
+                .iter()
+                .take_while(|b| b.is_ascii_alphanumeric())
+                .count();
+            len += end - i;
+            if end == i || len > MAX_LEN {
+                break;
+            }
+            ends[count] = end;
+            count += 1;
+
+            // a single separator continues the candidate
+            if matches!(bytes.get(end), Some(b' ' | b'-')) {
+                i = end + 1;
+            } else {
+                break;
+            }
+        }
+
+        (ends, count)
+    }
+
+    /// Checks if `start` is the beginning of a word
+    fn is_word_start(&self, start: usize) -> bool {
+        self.text[..start]
+            .chars()
+            .next_back()
+            .map_or(true, |c| !c.is_alphanumeric())
+    }
+}
+
+impl Iterator for Scanner<'_> {
+    type Item = (
+        Range<usize>,
+        RfCreditorReference<'static>,
+        Vec<Substitution>,
+    );
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some(offset) = self.text[self.position..].find(IDENTIFIER) {
+            let start = self.position + offset;
+            // the identifier is ASCII
+            self.position = start + 1;
+
+            if !self.is_word_start(start) {
+                continue;
+            }
+
+            // valid candidates are preferred over repaired ones
+            let (ends, count) = self.candidate_ends(start);
+            let bytes = self.text.as_bytes();
+            for &end in ends[..count].iter().rev() {
+                if let Some(rf) = Candidate::new(&bytes[start..end]).parse() {
+                    self.position = end;
+                    return Some((start..end, rf, Vec::new()));
+                }
+            }
+            if self.max_substitutions > 0 {
+                for &end in ends[..count].iter().rev() {
+                    let mut candidate = Candidate::new(&bytes[start..end]);
+                    if let Some((rf, substitutions)) = candidate.repair(self.max_substitutions) {
+                        self.position = end;
+                        return Some((start..end, rf, substitutions));
+                    }
+                }
+            }
+        }
+
+        self.position = self.text.len();
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn find(text: &str) -> Vec<String> {
+        find_references(text).map(|rf| rf.to_string()).collect()
+    }
+
+    #[test]
+    fn find_references_test() {
+        assert_eq!(
+            find("payment for RF18 5390 0754 7034 thanks"),
+            ["RF18 5390 0754 7034"]
+        );
+        assert_eq!(find("RF18539007547034"), ["RF18 5390 0754 7034"]);
+        assert_eq!(
+            find("RF18 5390 0754 7034/RF71 2348 231"),
+            ["RF18 5390 0754 7034", "RF71 2348 231"]
+        );
+        assert_eq!(find("(RF71 2348 231)"), ["RF71 2348 231"]);
+        assert_eq!(
+            find("Rechnung RF71 2348 231 über 12,00 €"),
+            ["RF71 2348 231"]
+        );
+        // trailing word of digits
+        assert_eq!(find("RF71 2348 231 100"), ["RF71 2348 231"]);
+        // double spaces end the reference
+        assert_eq!(find("RF18 5390  0754 7034"), Vec::<String>::new());
+    }
+
+    #[test]
+    fn no_references_test() {
+        assert!(find("").is_empty());
+        assert!(find("RF").is_empty());
+        assert!(find("RF19 5390 0754 7034").is_empty());
+        assert!(find("XRF18 5390 0754 7034").is_empty());
+        assert!(find("äRF18 5390 0754 7034").is_empty());
+        assert!(find("rf18 5390 0754 7034").is_empty());
+        assert!(find("RF18539007547034RF18539007547034").is_empty());
+    }
+
+    #[test]
+    fn find_matches_test() {
+        let text = "a b c RF71 2348 231 d e\nf";
+        let m = find_matches(text, 0).next().unwrap();
+        assert_eq!(m.range, 6..19);
+        assert_eq!(m.context, "RF71 2348 231");
+        assert_eq!(
+            find_matches(text, 1).next().unwrap().context,
+            "c RF71 2348 231 d"
+        );
+        assert_eq!(find_matches(text, 3).next().unwrap().context, text);
+        assert_eq!(find_matches(text, 9).next().unwrap().context, text);
+
+        let contexts = find_matches("(RF71 2348 231), RF18539007547034.", 1)
+            .map(|m| m.context)
+            .collect::<Vec<_>>();
+        assert_eq!(contexts, ["(RF71 2348 231),", "231), RF18539007547034."]);
+    }
+
+    #[test]
+    fn separators_test() {
+        assert_eq!(find("RF18-5390-0754-7034"), ["RF18 5390 0754 7034"]);
+        assert_eq!(find("RF18 5390-0754 7034"), ["RF18 5390 0754 7034"]);
+        assert_eq!(find("RF71-2348-231-"), ["RF71 2348 231"]);
+        assert!(find("RF18--5390-0754-7034").is_empty());
+        assert!(find("RF18_5390_0754_7034").is_empty());
+    }
+
+    #[test]
+    fn normalize_in_place_test() {
+        assert!(matches!(
+            normalize_in_place("no reference"),
+            Cow::Borrowed("no reference")
+        ));
+        assert!(matches!(
+            normalize_in_place("ref RF712348231"),
+            Cow::Borrowed(_)
+        ));
+        assert_eq!(
+            normalize_in_place("RF63 abcd 0754 efgh, RF712348231 and RF71-2348-231."),
+            "RF63ABCD0754EFGH, RF712348231 and RF712348231."
+        );
+        assert_eq!(normalize_in_place("RF71 2348 231"), "RF712348231");
+    }
+
+    #[test]
+    fn find_repaired_matches_test() {
+        let text = "RFI8 5390 0754 7034, RF71 2348 23l, RF18 5390 O754 7O34, RF71 2348 231";
+        let matches = find_repaired_matches(text, 0, 1).collect::<Vec<_>>();
+        assert_eq!(matches.len(), 3);
+        assert_eq!(matches[0].reference.to_string(), "RF18 5390 0754 7034");
+        assert_eq!(
+            matches[0].substitutions,
+            [Substitution {
+                position: 2,
+                from: 'I',
+                to: '1'
+            }]
+        );
+        assert_eq!(matches[1].reference.to_string(), "RF71 2348 231");
+        assert_eq!(matches[1].substitutions[0].position, 12);
+        assert!(!matches[2].is_repaired());
+        assert_eq!(matches[2].range, 57..70);
+
+        // 2 substitutions are needed
+        let matches = find_repaired_matches(text, 0, 2).collect::<Vec<_>>();
+        assert_eq!(matches.len(), 4);
+        assert_eq!(matches[2].substitutions.len(), 2);
+
+        // digits become letters in words with letters only
+        let rf = RfCreditorReference::new("ABOB1234");
+        let text = rf.to_string().replace("ABOB", "AB0B");
+        let m = find_repaired_matches(&text, 0, 1).next().unwrap();
+        assert_eq!(m.reference, rf);
+        assert_eq!(m.substitutions[0].to, 'O');
+        let text = rf.to_string().replace("1234", "I234");
+        assert!(find_repaired_matches(&text, 0, 1).next().is_some());
+
+        assert_eq!(
+            find_repaired_matches(text.as_str(), 0, 0).count(),
+            find_matches(text.as_str(), 0).count()
+        );
+    }
+
+    #[test]
+    fn scanner_test() {
+        let text = "ref: RF71 2348 231, RF18 5390 0754 7034";
+        let ranges = Scanner::new(text, 0)
+            .map(|(range, _, _)| range)
+            .collect::<Vec<_>>();
+        assert_eq!(ranges, [5..18, 20..39]);
+        assert_eq!(&text[5..18], "RF71 2348 231");
+    }
+}
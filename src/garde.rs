@@ -0,0 +1,92 @@
+//! Integration with the [`garde`](https://docs.rs/garde) crate
+//!
+//! Available with feature `garde`.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use garde::Validate;
+//! use iso_11649::{garde::rf_reference, RfCreditorReference};
+//!
+//! #[derive(Validate)]
+//! struct PaymentRequest {
+//!     #[garde(custom(rf_reference))]
+//!     reference: String,
+//!     #[garde(dive)]
+//!     parsed: RfCreditorReference<'static>,
+//! }
+//!
+//! let mut request = PaymentRequest {
+//!     reference: "RF18 5390 0754 7034".to_string(),
+//!     parsed: RfCreditorReference::new("2348231"),
+//! };
+//! assert!(request.validate().is_ok());
+//!
+//! request.reference = "RF19 5390 0754 7034".to_string();
+//! let report = request.validate().unwrap_err();
+//! let (path, error) = report.iter().next().unwrap();
+//! assert_eq!(path.to_string(), "reference");
+//! assert_eq!(
+//!     error.message(),
+//!     "checksum 19 does not match expected 18 [RF19 5390 0754 7034]"
+//! );
+//! ```
+
+use ::garde::{Error, Path, Report, Validate};
+
+use crate::RfCreditorReference;
+
+/// Validates `value` as creditor reference
+///
+/// Usable with `#[garde(custom(rf_reference))]` in any validation context.
+///
+/// # Errors
+///
+/// Results in an [`Error`] with the message of the
+/// [`ParseError`](crate::ParseError).
+///
+pub fn rf_reference<T, C>(value: &T, _context: &C) -> ::garde::Result
+where
+    T: AsRef<str> + ?Sized,
+{
+    RfCreditorReference::parse_str(value.as_ref())
+        .map(|_| ())
+        .map_err(|err| Error::new(err.to_string()))
+}
+
+/// [`RfCreditorReference`] is always valid, because it is validated on
+/// construction.
+impl Validate for RfCreditorReference<'_> {
+    type Context = ();
+
+    fn validate_into(
+        &self,
+        _ctx: &Self::Context,
+        _parent: &mut dyn FnMut() -> Path,
+        _report: &mut Report,
+    ) {
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rf_reference_test() {
+        assert!(rf_reference("RF18 5390 0754 7034", &()).is_ok());
+        assert!(rf_reference(&"RF18 5390 0754 7034".to_string(), &()).is_ok());
+
+        let err = rf_reference("RF19 5390 0754 7034", &()).unwrap_err();
+        assert_eq!(
+            err.message(),
+            "checksum 19 does not match expected 18 [RF19 5390 0754 7034]"
+        );
+    }
+
+    #[test]
+    fn validate_test() {
+        let rf = RfCreditorReference::new("539007547034");
+        assert!(rf.validate().is_ok());
+    }
+}
@@ -0,0 +1,234 @@
+//! GraphQL scalar `CreditorReference`
+//!
+//! Input values are validated like [`RfCreditorReference::parse_str`].
+//! [`RfCreditorReference`] outputs the print format, the wrapper
+//! [`ElectronicScalar`] outputs the electronic format instead. Both are
+//! the scalar `CreditorReference`, so a schema uses only one of them.
+//!
+//! - feature `async-graphql`: `ScalarType` for
+//!   [async-graphql](https://docs.rs/async-graphql)
+//! - feature `juniper`: `GraphQLScalar` for
+//!   [juniper](https://docs.rs/juniper)
+//!
+//! # Examples
+//!
+//! ```rust
+//! # #[cfg(feature = "async-graphql")]
+//! # {
+//! use async_graphql::{ScalarType, Value};
+//! use iso_11649::{graphql::ElectronicScalar, RfCreditorReference};
+//!
+//! let rf = RfCreditorReference::parse(Value::from("RF18539007547034")).unwrap();
+//! assert_eq!(rf.to_value(), Value::from("RF18 5390 0754 7034"));
+//!
+//! let scalar = ElectronicScalar::from(rf);
+//! assert_eq!(scalar.to_value(), Value::from("RF18539007547034"));
+//!
+//! assert!(RfCreditorReference::parse(Value::from("RF19539007547034")).is_err());
+//! # }
+//! ```
+
+use crate::RfCreditorReference;
+
+/// `ElectronicScalar` is the scalar `CreditorReference` in electronic
+/// format
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct ElectronicScalar(pub RfCreditorReference<'static>);
+
+impl From<RfCreditorReference<'static>> for ElectronicScalar {
+    fn from(rf: RfCreditorReference<'static>) -> Self {
+        Self(rf)
+    }
+}
+
+impl From<ElectronicScalar> for RfCreditorReference<'static> {
+    fn from(scalar: ElectronicScalar) -> Self {
+        scalar.0
+    }
+}
+
+#[cfg(feature = "async-graphql")]
+mod async_graphql {
+    use ::async_graphql::{InputValueError, InputValueResult, Scalar, ScalarType, Value};
+
+    use super::ElectronicScalar;
+    use crate::RfCreditorReference;
+
+    /// Validates [`Value::String`]s like [`RfCreditorReference::parse_str`]
+    fn parse(value: Value) -> InputValueResult<RfCreditorReference<'static>> {
+        match value {
+            Value::String(reference) => {
+                RfCreditorReference::parse_str(&reference).map_err(InputValueError::custom)
+            }
+            value => Err(InputValueError::expected_type(value)),
+        }
+    }
+
+    /// Outputs the print format.
+    #[Scalar(
+        name = "CreditorReference",
+        specified_by_url = "https://www.iso.org/standard/50649.html"
+    )]
+    impl ScalarType for RfCreditorReference<'static> {
+        fn parse(value: Value) -> InputValueResult<Self> {
+            parse(value)
+        }
+
+        fn to_value(&self) -> Value {
+            Value::String(self.to_string())
+        }
+    }
+
+    /// Outputs the electronic format.
+    #[Scalar(
+        name = "CreditorReference",
+        specified_by_url = "https://www.iso.org/standard/50649.html"
+    )]
+    impl ScalarType for ElectronicScalar {
+        fn parse(value: Value) -> InputValueResult<Self> {
+            parse(value).map_err(InputValueError::propagate).map(Self)
+        }
+
+        fn to_value(&self) -> Value {
+            Value::String(self.0.to_electronic_string())
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use ::async_graphql::Pos;
+
+        use super::*;
+
+        #[test]
+        fn scalar_test() {
+            let rf =
+                <RfCreditorReference as ScalarType>::parse(Value::from("RF71 2348 231")).unwrap();
+            assert_eq!(rf, RfCreditorReference::new("2348231"));
+            assert_eq!(rf.to_value(), Value::from("RF71 2348 231"));
+
+            let scalar = ElectronicScalar::parse(Value::from("RF71 2348 231")).unwrap();
+            assert_eq!(scalar.to_value(), Value::from("RF712348231"));
+            assert_eq!(RfCreditorReference::from(scalar), rf);
+        }
+
+        #[test]
+        fn invalid_test() {
+            let err = <RfCreditorReference as ScalarType>::parse(Value::from("RF70 2348 231"))
+                .unwrap_err()
+                .into_server_error(Pos::default());
+            assert!(err.message.contains("RF70 2348 231"));
+
+            assert!(ElectronicScalar::parse(Value::from(4711)).is_err());
+            assert!(ElectronicScalar::parse(Value::Null).is_err());
+        }
+    }
+}
+
+#[cfg(feature = "juniper")]
+mod juniper {
+    use ::juniper::{graphql_scalar, InputValue, ScalarValue};
+
+    use super::ElectronicScalar;
+    use crate::RfCreditorReference;
+
+    /// Validates string values like [`RfCreditorReference::parse_str`]
+    fn from_input<S: ScalarValue>(
+        value: &InputValue<S>,
+    ) -> Result<RfCreditorReference<'static>, String> {
+        value
+            .as_string_value()
+            .ok_or_else(|| format!("Expected `String`, found: {}", value))
+            .and_then(|reference| {
+                RfCreditorReference::parse_str(reference).map_err(|err| err.to_string())
+            })
+    }
+
+    /// Outputs the print format.
+    #[graphql_scalar(
+        name = "CreditorReference",
+        specified_by_url = "https://www.iso.org/standard/50649.html",
+        with = print,
+        parse_token(String)
+    )]
+    type PrintScalar = RfCreditorReference<'static>;
+
+    mod print {
+        use ::juniper::{InputValue, ScalarValue, Value};
+
+        use super::PrintScalar;
+
+        pub(super) fn to_output<S: ScalarValue>(rf: &PrintScalar) -> Value<S> {
+            Value::scalar(rf.to_string())
+        }
+
+        pub(super) fn from_input<S: ScalarValue>(
+            value: &InputValue<S>,
+        ) -> Result<PrintScalar, String> {
+            super::from_input(value)
+        }
+    }
+
+    /// Outputs the electronic format.
+    #[graphql_scalar(
+        name = "CreditorReference",
+        specified_by_url = "https://www.iso.org/standard/50649.html",
+        with = electronic,
+        parse_token(String)
+    )]
+    type Electronic = ElectronicScalar;
+
+    mod electronic {
+        use ::juniper::{InputValue, ScalarValue, Value};
+
+        use super::Electronic;
+
+        pub(super) fn to_output<S: ScalarValue>(scalar: &Electronic) -> Value<S> {
+            Value::scalar(scalar.0.to_electronic_string())
+        }
+
+        pub(super) fn from_input<S: ScalarValue>(
+            value: &InputValue<S>,
+        ) -> Result<Electronic, String> {
+            super::from_input(value).map(super::ElectronicScalar)
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use ::juniper::{DefaultScalarValue, FromInputValue, ToInputValue};
+
+        use super::*;
+
+        #[test]
+        fn scalar_test() {
+            let input = InputValue::<DefaultScalarValue>::scalar("RF71 2348 231");
+            let rf = RfCreditorReference::from_input_value(&input).unwrap();
+            assert_eq!(rf, RfCreditorReference::new("2348231"));
+            assert_eq!(
+                rf.to_input_value(),
+                InputValue::<DefaultScalarValue>::scalar("RF71 2348 231")
+            );
+
+            let scalar = ElectronicScalar::from_input_value(&input).unwrap();
+            assert_eq!(
+                scalar.to_input_value(),
+                InputValue::<DefaultScalarValue>::scalar("RF712348231")
+            );
+        }
+
+        #[test]
+        fn invalid_test() {
+            let input = InputValue::<DefaultScalarValue>::scalar("RF70 2348 231");
+            let err = RfCreditorReference::from_input_value(&input).unwrap_err();
+            assert!(err.message().contains("RF70 2348 231"));
+
+            let input = InputValue::<DefaultScalarValue>::scalar(4711);
+            assert!(ElectronicScalar::from_input_value(&input).is_err());
+            assert!(
+                ElectronicScalar::from_input_value(&InputValue::<DefaultScalarValue>::null())
+                    .is_err()
+            );
+        }
+    }
+}
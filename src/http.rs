@@ -0,0 +1,689 @@
+//! Creditor references in routes of web frameworks
+//!
+//! Path segments, query and form values are validated like
+//! [`RfCreditorReference::parse_str`](crate::RfCreditorReference::parse_str).
+//! Rejected references respond with `400 Bad Request` and the
+//! [problem details](https://www.rfc-editor.org/rfc/rfc7807) of
+//! [`ParseError::to_problem_details`] with this status.
+//!
+//! - feature `actix`: the extractors [`RfPath`] and [`RfQuery`] for
+//!   [actix-web](https://actix.rs). Their error [`ReferenceRejection`]
+//!   responds with the problem details, or with problem details of a
+//!   missing reference when the parameter is missing.
+//! - feature `axum`: the same extractors for [axum](https://docs.rs/axum),
+//!   their [`ReferenceRejection`] renders the same response
+//! - feature `rocket`: `FromParam` and `FromFormField` for
+//!   [Rocket](https://rocket.rs), so routes like
+//!   `#[get("/invoices/<reference>")]` take references directly. Invalid
+//!   path segments forward to other routes, a parameter of type
+//!   `Result<RfCreditorReference<'_>, ParseError>` gets the [`ParseError`]
+//!   instead, which responds with the problem details. Invalid form
+//!   fields are validation errors of the form.
+//!
+//! [`ParseError`]: crate::ParseError
+//! [`ParseError::to_problem_details`]: crate::ParseError::to_problem_details
+
+#[cfg(all(feature = "rocket", not(any(feature = "actix", feature = "axum"))))]
+use ::rocket::serde::json::serde_json;
+
+#[cfg(any(feature = "actix", feature = "axum"))]
+use crate::RfCreditorReference;
+#[cfg(any(feature = "actix", feature = "axum", feature = "rocket"))]
+use crate::{ParseError, ProblemDetails};
+
+/// Name of the path or query parameter of references
+#[cfg(any(feature = "actix", feature = "axum"))]
+pub const PARAMETER_NAME: &str = "reference";
+
+/// Media type of responses with problem details
+#[cfg(any(feature = "actix", feature = "axum", feature = "rocket"))]
+const PROBLEM_JSON: &str = "application/problem+json";
+
+/// HTTP status code of rejected references
+#[cfg(any(feature = "actix", feature = "axum", feature = "rocket"))]
+const BAD_REQUEST: u16 = 400;
+
+/// Problem details of [`ParseError::to_problem_details`] with status
+/// [`BAD_REQUEST`]
+#[cfg(any(feature = "actix", feature = "axum", feature = "rocket"))]
+fn bad_request(err: &ParseError) -> ProblemDetails {
+    ProblemDetails {
+        status: BAD_REQUEST,
+        ..err.to_problem_details()
+    }
+}
+
+/// Members of the problem details `problem`
+#[cfg(any(feature = "actix", feature = "axum", feature = "rocket"))]
+fn problem_members(problem: &ProblemDetails) -> serde_json::Map<String, serde_json::Value> {
+    let mut members = serde_json::Map::new();
+    let _ = members.insert("type".to_string(), problem.type_uri.into());
+    let _ = members.insert("title".to_string(), problem.title.into());
+    let _ = members.insert("status".to_string(), problem.status.into());
+    let _ = members.insert("detail".to_string(), problem.detail.clone().into());
+    let _ = members.insert("code".to_string(), problem.code.into());
+    members
+}
+
+/// Body of responses with the problem details `problem`
+#[cfg(feature = "rocket")]
+fn problem_body(problem: &ProblemDetails) -> String {
+    serde_json::Value::Object(problem_members(problem)).to_string()
+}
+
+/// `ReferenceRejection` is the error of extracting a reference from a
+/// request
+///
+/// New variants may be added in future versions.
+#[cfg(any(feature = "actix", feature = "axum"))]
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum ReferenceRejection {
+    /// the request has no parameter [`PARAMETER_NAME`]
+    Missing,
+    /// the reference is invalid
+    Invalid(ParseError),
+}
+
+#[cfg(any(feature = "actix", feature = "axum"))]
+impl ReferenceRejection {
+    /// HTTP status code of the rejection, `400` for missing and invalid
+    /// references
+    #[must_use]
+    pub fn status(&self) -> u16 {
+        BAD_REQUEST
+    }
+
+    /// Problem details of RFC 7807 describing the rejection
+    ///
+    /// Invalid references have the members of
+    /// [`ParseError::to_problem_details`] with the [`Self::status`] and the
+    /// fields of the serialized [`ParseError`] too.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use iso_11649::{http::ReferenceRejection, RfCreditorReference};
+    ///
+    /// let err = RfCreditorReference::parse_str("RF70 2348 231").unwrap_err();
+    /// let problem = ReferenceRejection::Invalid(err).problem_details();
+    ///
+    /// assert_eq!(problem["type"], "urn:iso11649:problem:invalid-checksum");
+    /// assert_eq!(problem["status"], 400);
+    /// assert_eq!(problem["code"], "ISO11649-E003-INVALID-CHECKSUM");
+    /// assert_eq!(problem["expected"], 71);
+    /// ```
+    ///
+    #[must_use]
+    pub fn problem_details(&self) -> ::serde_json::Value {
+        let problem = match self {
+            ReferenceRejection::Missing => {
+                let mut problem = ::serde_json::Map::new();
+                let _ = problem.insert(
+                    "type".to_string(),
+                    "urn:iso11649:problem:missing-reference".into(),
+                );
+                let _ = problem.insert("title".to_string(), "Missing creditor reference".into());
+                let _ = problem.insert("status".to_string(), self.status().into());
+                let _ = problem.insert("detail".to_string(), self.to_string().into());
+                problem
+            }
+            ReferenceRejection::Invalid(err) => {
+                let mut problem = match ::serde_json::to_value(err) {
+                    Ok(::serde_json::Value::Object(fields)) => fields,
+                    _ => ::serde_json::Map::new(),
+                };
+                problem.extend(problem_members(&bad_request(err)));
+                problem
+            }
+        };
+        ::serde_json::Value::Object(problem)
+    }
+}
+
+#[cfg(any(feature = "actix", feature = "axum"))]
+impl std::fmt::Display for ReferenceRejection {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> Result<(), std::fmt::Error> {
+        match self {
+            ReferenceRejection::Missing => {
+                write!(f, "missing parameter `{}`", PARAMETER_NAME)
+            }
+            ReferenceRejection::Invalid(err) => write!(f, "{}", err),
+        }
+    }
+}
+
+#[cfg(any(feature = "actix", feature = "axum"))]
+impl std::error::Error for ReferenceRejection {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            ReferenceRejection::Invalid(err) => Some(err),
+            ReferenceRejection::Missing => None,
+        }
+    }
+}
+
+#[cfg(any(feature = "actix", feature = "axum"))]
+impl From<ParseError> for ReferenceRejection {
+    fn from(err: ParseError) -> Self {
+        ReferenceRejection::Invalid(err)
+    }
+}
+
+/// `RfPath` extracts the validated path parameter [`PARAMETER_NAME`],
+/// or the only path parameter of the route
+#[cfg(any(feature = "actix", feature = "axum"))]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RfPath(pub RfCreditorReference<'static>);
+
+/// `RfQuery` extracts the validated query parameter [`PARAMETER_NAME`]
+#[cfg(any(feature = "actix", feature = "axum"))]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RfQuery(pub RfCreditorReference<'static>);
+
+/// Query parameters of [`RfQuery`]
+#[cfg(any(feature = "actix", feature = "axum"))]
+#[derive(::serde::Deserialize)]
+struct Params {
+    reference: Option<String>,
+}
+
+/// Validates the path parameter of [`RfPath`] in `params`
+#[cfg(any(feature = "actix", feature = "axum"))]
+fn path_reference<'a>(
+    params: impl IntoIterator<Item = (&'a str, &'a str)>,
+) -> Result<RfPath, ReferenceRejection> {
+    let mut reference = None;
+    for (count, (name, value)) in params.into_iter().enumerate() {
+        if name == PARAMETER_NAME {
+            reference = Some(value);
+            break;
+        }
+        reference = if count == 0 { Some(value) } else { None };
+    }
+    let reference = reference.ok_or(ReferenceRejection::Missing)?;
+    Ok(RfPath(RfCreditorReference::parse_str(reference)?))
+}
+
+/// Validates the query parameter of [`RfQuery`] in `params`
+#[cfg(any(feature = "actix", feature = "axum"))]
+fn query_reference(params: Option<Params>) -> Result<RfQuery, ReferenceRejection> {
+    let reference = params
+        .and_then(|params| params.reference)
+        .ok_or(ReferenceRejection::Missing)?;
+    Ok(RfQuery(RfCreditorReference::parse_str(&reference)?))
+}
+
+#[cfg(feature = "actix")]
+mod actix {
+    use std::future::{ready, Ready};
+
+    use ::actix_web::{
+        dev::Payload, http::StatusCode, web::Query, FromRequest, HttpRequest, HttpResponse,
+        ResponseError,
+    };
+
+    use super::{
+        path_reference, query_reference, ReferenceRejection, RfPath, RfQuery, PROBLEM_JSON,
+    };
+
+    impl FromRequest for RfPath {
+        type Error = ReferenceRejection;
+        type Future = Ready<Result<Self, Self::Error>>;
+
+        fn from_request(req: &HttpRequest, _payload: &mut Payload) -> Self::Future {
+            ready(path_reference(req.match_info().iter()))
+        }
+    }
+
+    impl FromRequest for RfQuery {
+        type Error = ReferenceRejection;
+        type Future = Ready<Result<Self, Self::Error>>;
+
+        fn from_request(req: &HttpRequest, _payload: &mut Payload) -> Self::Future {
+            ready(query_reference(
+                Query::from_query(req.query_string())
+                    .ok()
+                    .map(Query::into_inner),
+            ))
+        }
+    }
+
+    /// Responds with [`ReferenceRejection::status`] and
+    /// [`ReferenceRejection::problem_details`].
+    impl ResponseError for ReferenceRejection {
+        fn status_code(&self) -> StatusCode {
+            StatusCode::from_u16(self.status()).unwrap_or(StatusCode::BAD_REQUEST)
+        }
+
+        fn error_response(&self) -> HttpResponse {
+            HttpResponse::build(self.status_code())
+                .content_type(PROBLEM_JSON)
+                .body(self.problem_details().to_string())
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use ::actix_web::{
+            body::to_bytes,
+            rt::System,
+            test::{call_service, init_service, read_body, TestRequest},
+            web, App,
+        };
+
+        use super::*;
+        use crate::RfCreditorReference;
+
+        async fn invoice(RfPath(rf): RfPath) -> String {
+            rf.to_electronic_string()
+        }
+
+        async fn payment(RfQuery(rf): RfQuery) -> String {
+            rf.to_electronic_string()
+        }
+
+        #[test]
+        fn path_test() {
+            let req = TestRequest::default()
+                .param("reference", "RF71 2348 231")
+                .to_http_request();
+            assert_eq!(
+                System::new().block_on(RfPath::extract(&req)).unwrap(),
+                RfPath(RfCreditorReference::new("2348231"))
+            );
+
+            let req = TestRequest::default()
+                .param("id", "RF712348231")
+                .to_http_request();
+            assert!(System::new().block_on(RfPath::extract(&req)).is_ok());
+
+            let req = TestRequest::default().to_http_request();
+            assert_eq!(
+                System::new().block_on(RfPath::extract(&req)),
+                Err(ReferenceRejection::Missing)
+            );
+        }
+
+        #[test]
+        fn query_test() {
+            let req = TestRequest::with_uri("/invoices?reference=RF71+2348+231").to_http_request();
+            assert_eq!(
+                System::new().block_on(RfQuery::extract(&req)).unwrap(),
+                RfQuery(RfCreditorReference::new("2348231"))
+            );
+
+            let req = TestRequest::with_uri("/invoices?reference=RF70+2348+231").to_http_request();
+            assert!(matches!(
+                System::new().block_on(RfQuery::extract(&req)),
+                Err(ReferenceRejection::Invalid(_))
+            ));
+        }
+
+        #[test]
+        fn response_test() {
+            let err = RfCreditorReference::parse_str("RF71 2348 23_").unwrap_err();
+            let response = ReferenceRejection::from(err).error_response();
+            assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+            assert_eq!(
+                response.headers().get("content-type").unwrap(),
+                "application/problem+json"
+            );
+
+            let body = System::new()
+                .block_on(to_bytes(response.into_body()))
+                .unwrap();
+            let problem = ::serde_json::from_slice::<::serde_json::Value>(&body).unwrap();
+            assert_eq!(problem["title"], "Invalid character");
+            assert_eq!(problem["kind"], "InvalidCharacter");
+            assert_eq!(problem["character"], "_");
+            assert_eq!(problem["position"], 12);
+            assert_eq!(problem["code"], "ISO11649-E001-INVALID-CHAR");
+
+            let response = ReferenceRejection::Missing.error_response();
+            assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        }
+
+        #[test]
+        fn router_test() {
+            System::new().block_on(async {
+                let app = init_service(
+                    App::new()
+                        .route("/invoices/{reference}", web::get().to(invoice))
+                        .route("/payments", web::get().to(payment)),
+                )
+                .await;
+
+                let req = TestRequest::get().uri("/invoices/RF712348231").to_request();
+                let response = call_service(&app, req).await;
+                assert_eq!(response.status(), StatusCode::OK);
+                assert_eq!(read_body(response).await, "RF712348231");
+
+                let req = TestRequest::get().uri("/invoices/RF702348231").to_request();
+                let response = call_service(&app, req).await;
+                assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+                let problem =
+                    ::serde_json::from_slice::<::serde_json::Value>(&read_body(response).await)
+                        .unwrap();
+                assert_eq!(problem["type"], "urn:iso11649:problem:invalid-checksum");
+
+                let req = TestRequest::get()
+                    .uri("/payments?reference=RF71+2348+231")
+                    .to_request();
+                let response = call_service(&app, req).await;
+                assert_eq!(response.status(), StatusCode::OK);
+                assert_eq!(read_body(response).await, "RF712348231");
+
+                let req = TestRequest::get().uri("/payments").to_request();
+                let response = call_service(&app, req).await;
+                assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+            });
+        }
+    }
+}
+
+#[cfg(feature = "axum")]
+mod axum {
+    use ::axum_crate::{
+        async_trait,
+        extract::{FromRequestParts, Query, RawPathParams},
+        http::{header, request::Parts, StatusCode},
+        response::{IntoResponse, Response},
+    };
+
+    use super::{
+        path_reference, query_reference, ReferenceRejection, RfPath, RfQuery, PROBLEM_JSON,
+    };
+
+    #[async_trait]
+    impl<S: Send + Sync> FromRequestParts<S> for RfPath {
+        type Rejection = ReferenceRejection;
+
+        async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+            let params = RawPathParams::from_request_parts(parts, state)
+                .await
+                .map_err(|_| ReferenceRejection::Missing)?;
+            path_reference(params.iter())
+        }
+    }
+
+    #[async_trait]
+    impl<S: Send + Sync> FromRequestParts<S> for RfQuery {
+        type Rejection = ReferenceRejection;
+
+        async fn from_request_parts(
+            parts: &mut Parts,
+            _state: &S,
+        ) -> Result<Self, Self::Rejection> {
+            query_reference(Query::try_from_uri(&parts.uri).ok().map(|query| query.0))
+        }
+    }
+
+    /// Responds with [`ReferenceRejection::status`] and
+    /// [`ReferenceRejection::problem_details`].
+    impl IntoResponse for ReferenceRejection {
+        fn into_response(self) -> Response {
+            (
+                StatusCode::from_u16(self.status()).unwrap_or(StatusCode::BAD_REQUEST),
+                [(header::CONTENT_TYPE, PROBLEM_JSON)],
+                self.problem_details().to_string(),
+            )
+                .into_response()
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use ::axum_crate::http::Uri;
+
+        use super::*;
+        use crate::RfCreditorReference;
+
+        #[test]
+        fn query_test() {
+            let uri = Uri::from_static("/invoices?reference=RF71+2348+231");
+            let params = Query::try_from_uri(&uri).ok().map(|query| query.0);
+            assert_eq!(
+                query_reference(params).unwrap(),
+                RfQuery(RfCreditorReference::new("2348231"))
+            );
+        }
+
+        #[test]
+        fn response_test() {
+            let response = ReferenceRejection::Missing.into_response();
+            assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+            assert_eq!(
+                response.headers()[header::CONTENT_TYPE],
+                "application/problem+json"
+            );
+
+            let err = RfCreditorReference::parse_str("RF70 2348 231").unwrap_err();
+            let response = ReferenceRejection::from(err).into_response();
+            assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        }
+    }
+}
+
+#[cfg(feature = "rocket")]
+mod rocket {
+    use std::io::Cursor;
+
+    use ::rocket::{
+        form::{self, FromFormField, ValueField},
+        http::{ContentType, Status},
+        request::FromParam,
+        response::{self, Responder},
+        Request, Response,
+    };
+
+    use super::{bad_request, problem_body, PROBLEM_JSON};
+    use crate::{ParseError, RfCreditorReference};
+
+    impl<'a> FromParam<'a> for RfCreditorReference<'_> {
+        type Error = ParseError;
+
+        fn from_param(param: &'a str) -> Result<Self, Self::Error> {
+            RfCreditorReference::parse_str(param)
+        }
+    }
+
+    impl<'v> FromFormField<'v> for RfCreditorReference<'_> {
+        fn from_value(field: ValueField<'v>) -> form::Result<'v, Self> {
+            RfCreditorReference::parse_str(field.value)
+                .map_err(|err| form::Error::validation(err.to_string()).into())
+        }
+    }
+
+    /// Responds with `400 Bad Request` and the members of
+    /// [`ParseError::to_problem_details`] with this status.
+    impl<'r> Responder<'r, 'static> for ParseError {
+        fn respond_to(self, _request: &'r Request<'_>) -> response::Result<'static> {
+            let problem = bad_request(&self);
+            let body = problem_body(&problem);
+            Response::build()
+                .status(Status::new(problem.status))
+                .header(ContentType::parse_flexible(PROBLEM_JSON).unwrap_or(ContentType::JSON))
+                .sized_body(body.len(), Cursor::new(body))
+                .ok()
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use ::rocket::{
+            form::Form,
+            get,
+            http::{ContentType, Status},
+            local::blocking::Client,
+            post, routes, FromForm,
+        };
+
+        use super::*;
+
+        // Rocket passes request guards by value
+        #[allow(clippy::needless_pass_by_value)]
+        #[get("/invoices/<reference>")]
+        fn invoice(reference: RfCreditorReference<'_>) -> String {
+            reference.to_electronic_string()
+        }
+
+        #[get("/checks/<reference>")]
+        fn check(reference: Result<RfCreditorReference<'_>, ParseError>) -> String {
+            match reference {
+                Ok(rf) => rf.to_string(),
+                Err(err) => err.code().to_string(),
+            }
+        }
+
+        #[get("/payments/<reference>")]
+        fn payment(
+            reference: Result<RfCreditorReference<'_>, ParseError>,
+        ) -> Result<String, ParseError> {
+            Ok(reference?.to_electronic_string())
+        }
+
+        #[derive(FromForm)]
+        struct Invoice<'r> {
+            reference: RfCreditorReference<'r>,
+        }
+
+        #[allow(clippy::needless_pass_by_value)]
+        #[post("/invoices", data = "<invoice>")]
+        fn create(invoice: Form<Invoice<'_>>) -> String {
+            invoice.reference.to_electronic_string()
+        }
+
+        fn client() -> Client {
+            Client::tracked(::rocket::build().mount("/", routes![invoice, check, payment, create]))
+                .unwrap()
+        }
+
+        #[test]
+        fn from_param_test() {
+            assert_eq!(
+                RfCreditorReference::from_param("RF712348231").unwrap(),
+                RfCreditorReference::new("2348231")
+            );
+
+            let client = client();
+            let response = client.get("/invoices/RF71%202348%20231").dispatch();
+            assert_eq!(response.status(), Status::Ok);
+            assert_eq!(response.into_string().unwrap(), "RF712348231");
+
+            let response = client.get("/invoices/RF702348231").dispatch();
+            assert_eq!(response.status(), Status::UnprocessableEntity);
+
+            let response = client.get("/checks/RF702348231").dispatch();
+            assert_eq!(
+                response.into_string().unwrap(),
+                "ISO11649-E003-INVALID-CHECKSUM"
+            );
+        }
+
+        #[test]
+        fn responder_test() {
+            let client = client();
+            let response = client.get("/payments/RF712348231").dispatch();
+            assert_eq!(response.into_string().unwrap(), "RF712348231");
+
+            let response = client.get("/payments/RF702348231").dispatch();
+            assert_eq!(response.status(), Status::BadRequest);
+            assert_eq!(
+                response.headers().get_one("Content-Type"),
+                Some("application/problem+json")
+            );
+            let problem =
+                ::serde_json::from_str::<::serde_json::Value>(&response.into_string().unwrap())
+                    .unwrap();
+            assert_eq!(problem["type"], "urn:iso11649:problem:invalid-checksum");
+            assert_eq!(problem["status"], 400);
+            assert_eq!(problem["code"], "ISO11649-E003-INVALID-CHECKSUM");
+        }
+
+        #[test]
+        fn from_form_field_test() {
+            let field = ValueField::parse("reference=RF71 2348 231");
+            assert_eq!(
+                RfCreditorReference::from_value(field).unwrap(),
+                RfCreditorReference::new("2348231")
+            );
+
+            let client = client();
+            let response = client
+                .post("/invoices")
+                .header(ContentType::Form)
+                .body("reference=RF71+2348+231")
+                .dispatch();
+            assert_eq!(response.into_string().unwrap(), "RF712348231");
+
+            let response = client
+                .post("/invoices")
+                .header(ContentType::Form)
+                .body("reference=RF70+2348+231")
+                .dispatch();
+            assert_eq!(response.status(), Status::UnprocessableEntity);
+        }
+    }
+}
+
+#[cfg(all(test, any(feature = "actix", feature = "axum")))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn path_reference_test() {
+        let rf = RfPath(RfCreditorReference::new("2348231"));
+        assert_eq!(path_reference([("reference", "RF712348231")]).unwrap(), rf);
+        assert_eq!(
+            path_reference([("id", "4711"), ("reference", "RF712348231")]).unwrap(),
+            rf
+        );
+        assert_eq!(path_reference([("id", "RF712348231")]).unwrap(), rf);
+        assert_eq!(
+            path_reference([("id", "RF712348231"), ("page", "1")]),
+            Err(ReferenceRejection::Missing)
+        );
+        assert_eq!(
+            path_reference(std::iter::empty()),
+            Err(ReferenceRejection::Missing)
+        );
+    }
+
+    #[test]
+    fn query_reference_test() {
+        assert_eq!(query_reference(None), Err(ReferenceRejection::Missing));
+        assert_eq!(
+            query_reference(Some(Params { reference: None })),
+            Err(ReferenceRejection::Missing)
+        );
+        assert!(matches!(
+            query_reference(Some(Params {
+                reference: Some("RF702348231".to_string())
+            })),
+            Err(ReferenceRejection::Invalid(
+                ParseError::InvalidChecksum { .. }
+            ))
+        ));
+    }
+
+    #[test]
+    fn problem_details_test() {
+        let problem = ReferenceRejection::Missing.problem_details();
+        assert_eq!(problem["type"], "urn:iso11649:problem:missing-reference");
+        assert_eq!(problem["title"], "Missing creditor reference");
+        assert_eq!(problem["status"], 400);
+        assert_eq!(problem["detail"], "missing parameter `reference`");
+        assert!(problem.get("code").is_none());
+
+        let err = RfCreditorReference::parse_str("RF70 2348 231").unwrap_err();
+        let problem = ReferenceRejection::from(err.clone()).problem_details();
+        let details = err.to_problem_details();
+        assert_eq!(problem["type"], details.type_uri);
+        assert_eq!(problem["title"], details.title);
+        assert_eq!(problem["status"], 400);
+        assert_eq!(problem["detail"], details.detail);
+        assert_eq!(problem["code"], details.code);
+        assert_eq!(problem["input"], "RF70 2348 231");
+    }
+}
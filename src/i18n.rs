@@ -0,0 +1,228 @@
+//! Localized messages of [`ParseError`]
+//!
+//! Available with feature `i18n`.
+
+use crate::ParseError;
+
+/// Languages with translated messages besides English
+pub const SUPPORTED_LANGUAGES: &[&str] = &["de", "es", "fi", "fr", "it", "nl"];
+
+/// Message templates of a language
+///
+/// Placeholders in braces are replaced by the fields of the [`ParseError`].
+struct Messages {
+    body_too_long: &'static str,
+    invalid_character: &'static str,
+    invalid_check_digits: &'static str,
+    invalid_checksum: &'static str,
+    invalid_identifier: &'static str,
+    too_long: &'static str,
+    too_short: &'static str,
+}
+
+/// Message templates by language in order of [`SUPPORTED_LANGUAGES`]
+const CATALOG: &[Messages] = &[
+    Messages {
+        body_too_long: "Referenzinhalt ist zu lang mit Länge {len} (max. {max}) [{input}]",
+        invalid_character: "ungültiges Zeichen '{character}' an Position {position} [{input}]",
+        invalid_check_digits: "Prüfziffern sind nicht numerisch [{input}]",
+        invalid_checksum:
+            "Prüfsumme {found} stimmt nicht mit erwarteter Prüfsumme {expected} überein [{input}]",
+        invalid_identifier: "Kennung ist nicht RF [{input}]",
+        too_long: "Referenz ist zu lang mit Länge {len} (max. {max}) [{input}]",
+        too_short: "Referenz ist zu kurz mit Länge {len} (min. {min}) [{input}]",
+    },
+    Messages {
+        body_too_long:
+            "el cuerpo de la referencia es demasiado largo con longitud {len} (máx. {max}) [{input}]",
+        invalid_character: "carácter no válido '{character}' en la posición {position} [{input}]",
+        invalid_check_digits: "los dígitos de control no son numéricos [{input}]",
+        invalid_checksum:
+            "la suma de control {found} no coincide con el valor esperado {expected} [{input}]",
+        invalid_identifier: "el identificador no es RF [{input}]",
+        too_long: "la referencia es demasiado larga con longitud {len} (máx. {max}) [{input}]",
+        too_short: "la referencia es demasiado corta con longitud {len} (mín. {min}) [{input}]",
+    },
+    Messages {
+        body_too_long: "viitteen runko on liian pitkä, pituus {len} (enintään {max}) [{input}]",
+        invalid_character: "virheellinen merkki '{character}' kohdassa {position} [{input}]",
+        invalid_check_digits: "tarkistenumerot eivät ole numeerisia [{input}]",
+        invalid_checksum: "tarkiste {found} ei vastaa odotettua tarkistetta {expected} [{input}]",
+        invalid_identifier: "tunniste ei ole RF [{input}]",
+        too_long: "viite on liian pitkä, pituus {len} (enintään {max}) [{input}]",
+        too_short: "viite on liian lyhyt, pituus {len} (vähintään {min}) [{input}]",
+    },
+    Messages {
+        body_too_long:
+            "le corps de la référence est trop long avec longueur {len} (max. {max}) [{input}]",
+        invalid_character: "caractère invalide '{character}' à la position {position} [{input}]",
+        invalid_check_digits: "les chiffres de contrôle ne sont pas numériques [{input}]",
+        invalid_checksum:
+            "la somme de contrôle {found} ne correspond pas à la valeur attendue {expected} [{input}]",
+        invalid_identifier: "l'identifiant n'est pas RF [{input}]",
+        too_long: "la référence est trop longue avec longueur {len} (max. {max}) [{input}]",
+        too_short: "la référence est trop courte avec longueur {len} (min. {min}) [{input}]",
+    },
+    Messages {
+        body_too_long:
+            "il corpo del riferimento è troppo lungo con lunghezza {len} (max {max}) [{input}]",
+        invalid_character: "carattere non valido '{character}' alla posizione {position} [{input}]",
+        invalid_check_digits: "le cifre di controllo non sono numeriche [{input}]",
+        invalid_checksum:
+            "la somma di controllo {found} non corrisponde al valore atteso {expected} [{input}]",
+        invalid_identifier: "l'identificativo non è RF [{input}]",
+        too_long: "il riferimento è troppo lungo con lunghezza {len} (max {max}) [{input}]",
+        too_short: "il riferimento è troppo corto con lunghezza {len} (min {min}) [{input}]",
+    },
+    Messages {
+        body_too_long: "referentie-inhoud is te lang met lengte {len} (max. {max}) [{input}]",
+        invalid_character: "ongeldig teken '{character}' op positie {position} [{input}]",
+        invalid_check_digits: "controlecijfers zijn niet numeriek [{input}]",
+        invalid_checksum:
+            "controlegetal {found} komt niet overeen met verwacht controlegetal {expected} [{input}]",
+        invalid_identifier: "identificatie is niet RF [{input}]",
+        too_long: "referentie is te lang met lengte {len} (max. {max}) [{input}]",
+        too_short: "referentie is te kort met lengte {len} (min. {min}) [{input}]",
+    },
+];
+
+impl ParseError {
+    /// Returns the error message translated for `locale`
+    ///
+    /// `locale` is a language tag like `de`, `fr-BE` or `fi_FI`. Only the
+    /// language is considered. See [`SUPPORTED_LANGUAGES`] for the available
+    /// translations. Other languages fall back to the English message of
+    /// [`std::fmt::Display`].
+    ///
+    /// Available with feature `i18n`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use iso_11649::RfCreditorReference;
+    ///
+    /// let err = RfCreditorReference::parse_str("XX18 5390 0754 7034").unwrap_err();
+    ///
+    /// assert_eq!(err.localized_message("de-DE"), "Kennung ist nicht RF [XX18 5390 0754 7034]");
+    /// assert_eq!(err.localized_message("en"), err.to_string());
+    /// ```
+    ///
+    #[must_use]
+    pub fn localized_message(&self, locale: &str) -> String {
+        let language = locale
+            .split(|c| c == '-' || c == '_')
+            .next()
+            .unwrap_or_default()
+            .to_ascii_lowercase();
+
+        let messages = match SUPPORTED_LANGUAGES
+            .iter()
+            .position(|&supported| supported == language)
+        {
+            Some(i) => &CATALOG[i],
+            None => return self.to_string(),
+        };
+
+        match self {
+            ParseError::BodyTooLong { input, len, max } => messages
+                .body_too_long
+                .replace("{len}", &len.to_string())
+                .replace("{max}", &max.to_string())
+                .replace("{input}", input),
+            ParseError::InvalidCharacter {
+                input,
+                character,
+                position,
+            } => messages
+                .invalid_character
+                .replace("{character}", &character.to_string())
+                .replace("{position}", &position.to_string())
+                .replace("{input}", input),
+            ParseError::InvalidCheckDigits { input, .. } => {
+                messages.invalid_check_digits.replace("{input}", input)
+            }
+            ParseError::InvalidChecksum {
+                input,
+                expected,
+                found,
+            } => messages
+                .invalid_checksum
+                .replace("{found}", &format!("{:02}", found))
+                .replace("{expected}", &format!("{:02}", expected))
+                .replace("{input}", input),
+            ParseError::InvalidIdentifier { input } => {
+                messages.invalid_identifier.replace("{input}", input)
+            }
+            ParseError::TooLong { input, len, max } => messages
+                .too_long
+                .replace("{len}", &len.to_string())
+                .replace("{max}", &max.to_string())
+                .replace("{input}", input),
+            ParseError::TooShort { input, len, min } => messages
+                .too_short
+                .replace("{len}", &len.to_string())
+                .replace("{min}", &min.to_string())
+                .replace("{input}", input),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn localized_message_test() {
+        let err = ParseError::InvalidChecksum {
+            input: "RF19 5390 0754 7034".to_string(),
+            expected: 18,
+            found: 19,
+        };
+        assert_eq!(
+            err.localized_message("de"),
+            "Prüfsumme 19 stimmt nicht mit erwarteter Prüfsumme 18 überein [RF19 5390 0754 7034]"
+        );
+        assert_eq!(
+            err.localized_message("FR_ch"),
+            "la somme de contrôle 19 ne correspond pas à la valeur attendue 18 [RF19 5390 0754 7034]"
+        );
+        assert_eq!(err.localized_message("en-GB"), err.to_string());
+        assert_eq!(err.localized_message("sv"), err.to_string());
+        assert_eq!(err.localized_message(""), err.to_string());
+
+        let err = ParseError::InvalidCharacter {
+            input: "RF18 5390_0754 7034".to_string(),
+            character: '_',
+            position: 9,
+        };
+        assert_eq!(
+            err.localized_message("fi-FI"),
+            "virheellinen merkki '_' kohdassa 9 [RF18 5390_0754 7034]"
+        );
+    }
+
+    #[test]
+    fn catalog_test() {
+        assert_eq!(SUPPORTED_LANGUAGES.len(), CATALOG.len());
+        for messages in CATALOG {
+            assert!(messages.invalid_character.contains("{character}"));
+            assert!(messages.invalid_character.contains("{position}"));
+            assert!(messages.invalid_checksum.contains("{expected}"));
+            assert!(messages.invalid_checksum.contains("{found}"));
+            assert!(messages.body_too_long.contains("{max}"));
+            assert!(messages.too_long.contains("{max}"));
+            assert!(messages.too_short.contains("{min}"));
+            for template in [
+                messages.body_too_long,
+                messages.invalid_character,
+                messages.invalid_check_digits,
+                messages.invalid_checksum,
+                messages.invalid_identifier,
+                messages.too_long,
+                messages.too_short,
+            ] {
+                assert!(template.contains("{input}"));
+            }
+        }
+    }
+}
@@ -0,0 +1,308 @@
+//! Check character systems of ISO 7064
+//!
+//! The check digits of creditor references are calculated with
+//! [`Mod97_10`], which is also used by e.g. IBAN (ISO 13616).
+//! [`Mod11_2`] and [`Mod37_2`] serve identifiers like ISNI or ORCID.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use iso_11649::iso7064::{CheckCharacterSystem, Mod11_2, Mod97_10};
+//!
+//! // creditor reference RF18 5390 0754 7034 with identifier moved to end
+//! assert_eq!(Mod97_10::compute("539007547034RF").unwrap(), "18");
+//! assert!(Mod97_10::verify("539007547034RF18"));
+//!
+//! // ORCID 0000-0002-1694-233X
+//! assert_eq!(Mod11_2::compute("000000021694233").unwrap(), "X");
+//! assert!(Mod11_2::verify("000000021694233X"));
+//! ```
+
+use crate::ParseError;
+
+/// Valid characters of [`Mod37_2`] in order of their values
+const ALPHABET_37: &str = "0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZ*";
+
+/// Marker of invalid bytes in [`VALUES_36`]
+const INVALID: u8 = u8::MAX;
+
+/// Values of the bytes `0-9` and letters `A-Z` ignoring case in base 36,
+/// [`INVALID`] for all other bytes
+pub(crate) static VALUES_36: [u8; 256] = values_36();
+
+/// Creates the lookup table [`VALUES_36`]
+const fn values_36() -> [u8; 256] {
+    let mut values = [INVALID; 256];
+    let mut i = 0;
+    while i < 26 {
+        if i < 10 {
+            values[(b'0' + i) as usize] = i;
+        }
+        values[(b'A' + i) as usize] = 10 + i;
+        values[(b'a' + i) as usize] = 10 + i;
+        i += 1;
+    }
+    values
+}
+
+/// `CheckCharacterSystem` is a check character system of ISO 7064
+///
+/// Implementations only provide the character values and the check
+/// character encoding. Calculation and verification are provided.
+pub trait CheckCharacterSystem {
+    /// The modulus of the system
+    const MODULUS: u32;
+    /// The number of check characters
+    const CHECK_CHARS: usize;
+
+    /// Appends the value of data character `c` to `remainder` modulo
+    /// [`Self::MODULUS`]
+    ///
+    /// Returns `None` if `c` is no valid data character.
+    fn push(remainder: u32, c: char) -> Option<u32>;
+
+    /// Appends the value of check character `c` to `remainder` modulo
+    /// [`Self::MODULUS`]
+    ///
+    /// Returns `None` if `c` is no valid check character.
+    #[must_use]
+    fn push_check(remainder: u32, c: char) -> Option<u32> {
+        Self::push(remainder, c)
+    }
+
+    /// Encodes the check `value` as check characters
+    fn encode(value: u32) -> String;
+
+    /// Computes the check characters for `data`
+    ///
+    /// # Errors
+    ///
+    /// Results in [`ParseError`]s when `data` is empty or contains
+    /// invalid characters.
+    ///
+    fn compute(data: &str) -> Result<String, ParseError> {
+        if data.is_empty() {
+            return Err(ParseError::TooShort {
+                input: data.to_string(),
+                len: 0,
+                min: 1,
+            });
+        }
+
+        let mut remainder = 0;
+        for (position, character) in data.chars().enumerate() {
+            remainder =
+                Self::push(remainder, character).ok_or_else(|| ParseError::InvalidCharacter {
+                    input: data.to_string(),
+                    character,
+                    position,
+                })?;
+        }
+        for _ in 0..Self::CHECK_CHARS {
+            remainder = Self::push(remainder, '0').unwrap_or(remainder);
+        }
+
+        let value = Self::MODULUS + 1 - remainder;
+        Ok(Self::encode(if Self::CHECK_CHARS == 1 {
+            value % Self::MODULUS
+        } else {
+            value
+        }))
+    }
+
+    /// Verifies `data` ending with its check characters
+    #[must_use]
+    fn verify(data: &str) -> bool {
+        let len = data.chars().count();
+        if len <= Self::CHECK_CHARS {
+            return false;
+        }
+
+        data.chars().enumerate().try_fold(0, |remainder, (i, c)| {
+            if i < len - Self::CHECK_CHARS {
+                Self::push(remainder, c)
+            } else {
+                Self::push_check(remainder, c)
+            }
+        }) == Some(1)
+    }
+}
+
+/// ISO 7064 MOD 97-10 with two check digits
+///
+/// Letters are converted to two digits `A = 10` to `Z = 35` like in
+/// ISO 11649 and ISO 13616, ignoring case.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct Mod97_10;
+
+impl Mod97_10 {
+    /// Appends the value of data character byte `b` to `remainder` modulo
+    /// 97
+    ///
+    /// Returns `None` if `b` is no valid data character.
+    #[inline]
+    pub(crate) fn push_byte(remainder: u32, b: u8) -> Option<u32> {
+        match u32::from(VALUES_36[usize::from(b)]) {
+            n if n < 10 => Some((remainder * 10 + n) % Self::MODULUS),
+            n if n < 36 => Some((remainder * 100 + n) % Self::MODULUS),
+            _ => None,
+        }
+    }
+}
+
+impl CheckCharacterSystem for Mod97_10 {
+    const MODULUS: u32 = 97;
+    const CHECK_CHARS: usize = 2;
+
+    #[inline]
+    fn push(remainder: u32, c: char) -> Option<u32> {
+        Self::push_byte(remainder, u8::try_from(u32::from(c)).ok()?)
+    }
+
+    fn push_check(remainder: u32, c: char) -> Option<u32> {
+        let n = c.to_digit(10)?;
+        Some((remainder * 10 + n) % Self::MODULUS)
+    }
+
+    fn encode(value: u32) -> String {
+        format!("{:02}", value)
+    }
+}
+
+/// ISO 7064 MOD 11-2 for digits with check character `0-9` or `X`
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct Mod11_2;
+
+impl CheckCharacterSystem for Mod11_2 {
+    const MODULUS: u32 = 11;
+    const CHECK_CHARS: usize = 1;
+
+    #[inline]
+    fn push(remainder: u32, c: char) -> Option<u32> {
+        let n = c.to_digit(10)?;
+        Some((remainder * 2 + n) % Self::MODULUS)
+    }
+
+    fn push_check(remainder: u32, c: char) -> Option<u32> {
+        match c {
+            'X' => Some((remainder * 2 + 10) % Self::MODULUS),
+            _ => Self::push(remainder, c),
+        }
+    }
+
+    fn encode(value: u32) -> String {
+        match value {
+            10 => "X".to_string(),
+            _ => value.to_string(),
+        }
+    }
+}
+
+/// ISO 7064 MOD 37-2 for `0-9` and `A-Z` with check character `0-9`,
+/// `A-Z` or `*`
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct Mod37_2;
+
+impl CheckCharacterSystem for Mod37_2 {
+    const MODULUS: u32 = 37;
+    const CHECK_CHARS: usize = 1;
+
+    #[inline]
+    fn push(remainder: u32, c: char) -> Option<u32> {
+        let n = ALPHABET_37[..36].find(c)?;
+        #[allow(clippy::cast_possible_truncation)]
+        Some((remainder * 2 + n as u32) % Self::MODULUS)
+    }
+
+    fn push_check(remainder: u32, c: char) -> Option<u32> {
+        let n = ALPHABET_37.find(c)?;
+        #[allow(clippy::cast_possible_truncation)]
+        Some((remainder * 2 + n as u32) % Self::MODULUS)
+    }
+
+    fn encode(value: u32) -> String {
+        ALPHABET_37
+            .chars()
+            .nth(value as usize)
+            .unwrap_or_default()
+            .to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mod97_10_test() {
+        assert_eq!(Mod97_10::compute("2348231RF").unwrap(), "71");
+        assert_eq!(Mod97_10::compute("ABCD0754efghRF").unwrap(), "63");
+        assert!(Mod97_10::verify("ABCD0754efghRF63"));
+        assert!(!Mod97_10::verify("ABCD0754efghRF64"));
+
+        // IBAN GB82 WEST 1234 5698 7654 32
+        assert_eq!(Mod97_10::compute("WEST12345698765432GB").unwrap(), "82");
+        assert!(Mod97_10::verify("WEST12345698765432GB82"));
+        assert!(!Mod97_10::verify("WEST12345698765423GB82"));
+        assert!(!Mod97_10::verify("WEST12345698765432GB8A"));
+
+        // long alphanumeric data does not overflow
+        let data = "ZZZZZZZZZZZZZZZZZZZZZZZZZZZZZZZZZZZZZZZZ";
+        let check = Mod97_10::compute(data).unwrap();
+        assert!(Mod97_10::verify(&format!("{}{}", data, check)));
+    }
+
+    #[test]
+    fn values_36_test() {
+        for b in 0..=u8::MAX {
+            let expected = char::from(b).to_digit(36).filter(|_| b.is_ascii());
+            let value = Some(u32::from(VALUES_36[usize::from(b)])).filter(|&n| n < 36);
+            assert_eq!(value, expected, "{}", b);
+        }
+        assert_eq!(Mod97_10::push_byte(0, b'A'), Some(10));
+        assert_eq!(Mod97_10::push_byte(0, b'_'), None);
+        assert_eq!(Mod97_10::push(0, 'ä'), None);
+    }
+
+    #[test]
+    fn mod11_2_test() {
+        assert_eq!(Mod11_2::compute("000000021694233").unwrap(), "X");
+        assert_eq!(Mod11_2::compute("000000021825009").unwrap(), "7");
+        assert!(Mod11_2::verify("0000000218250097"));
+        assert!(!Mod11_2::verify("0000000218250098"));
+        assert!(!Mod11_2::verify("00000002182500X7"));
+    }
+
+    #[test]
+    fn mod37_2_test() {
+        for data in ["A", "ISO7064", "G123498654321", "ZZZZZZZZZ"] {
+            let check = Mod37_2::compute(data).unwrap();
+            assert_eq!(check.len(), 1);
+            assert!(Mod37_2::verify(&format!("{}{}", data, check)), "{}", data);
+            let wrong = ALPHABET_37
+                .chars()
+                .find(|&c| c.to_string() != check)
+                .unwrap();
+            assert!(!Mod37_2::verify(&format!("{}{}", data, wrong)), "{}", data);
+        }
+        assert!(!Mod37_2::verify("A*B"));
+    }
+
+    #[test]
+    fn error_test() {
+        assert!(matches!(
+            Mod97_10::compute(""),
+            Err(ParseError::TooShort { .. })
+        ));
+        assert!(matches!(
+            Mod11_2::compute("12A4"),
+            Err(ParseError::InvalidCharacter {
+                character: 'A',
+                position: 2,
+                ..
+            })
+        ));
+        assert!(!Mod11_2::verify("X"));
+        assert!(!Mod97_10::verify(""));
+    }
+}
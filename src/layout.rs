@@ -0,0 +1,397 @@
+//! Structured segments inside the reference body
+//!
+//! A [`SegmentLayout`] defines named segments with fixed width and
+//! charset, e.g. customer number, invoice number and document type.
+//! References are generated from field values and incoming references
+//! are decoded back into their named segments for reconciliation.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use iso_11649::layout::{Charset, SegmentLayout};
+//!
+//! let layout = SegmentLayout::new()
+//!     .segment("customer", 6, Charset::Numeric)
+//!     .segment("invoice", 8, Charset::Numeric)
+//!     .segment("type", 2, Charset::Alphabetic);
+//!
+//! let rf = layout.encode(&["4711", "20240815", "IN"]).unwrap();
+//! assert_eq!(&rf.as_electronic_str()[4..], "00471120240815IN");
+//!
+//! let fields = layout.decode(&rf).unwrap();
+//! assert_eq!(fields, [("customer", "004711"), ("invoice", "20240815"), ("type", "IN")]);
+//! ```
+
+use crate::{ParseError, RfCreditorReference, RfReferenceBuilder, GEN_PREFIX};
+
+/// The `LayoutError` enum is a collection of all the possible reasons
+/// encoding and decoding segments fail.
+///
+/// New variants may be added in future versions.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum LayoutError {
+    /// reference body does not have the length of the layout
+    BodyLength {
+        /// the rejected reference in electronic format
+        input: String,
+        /// the length of the body
+        len: usize,
+        /// the length of the body defined by the layout
+        expected: usize,
+    },
+    /// reference could not be generated or value has invalid characters
+    Reference(ParseError),
+    /// more values than segments
+    TooManyValues {
+        /// the number of values
+        count: usize,
+        /// the number of segments
+        segments: usize,
+    },
+    /// value does not have the width of its segment
+    Width {
+        /// the index of the segment
+        segment: usize,
+        /// the rejected value
+        input: String,
+        /// the width of `input`
+        width: usize,
+        /// the width of the segment
+        expected: usize,
+    },
+}
+
+impl std::fmt::Display for LayoutError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> Result<(), std::fmt::Error> {
+        match self {
+            LayoutError::BodyLength {
+                input,
+                len,
+                expected,
+            } => write!(
+                f,
+                "reference body has length {} instead of {} [{}]",
+                len, expected, input
+            ),
+            LayoutError::Reference(err) => write!(f, "{}", err),
+            LayoutError::TooManyValues { count, segments } => {
+                write!(f, "{} values for {} segments", count, segments)
+            }
+            LayoutError::Width {
+                segment,
+                input,
+                width,
+                expected,
+            } => write!(
+                f,
+                "value of segment {} has width {} instead of {} [{}]",
+                segment, width, expected, input
+            ),
+        }
+    }
+}
+
+impl std::error::Error for LayoutError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            LayoutError::Reference(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<ParseError> for LayoutError {
+    fn from(err: ParseError) -> Self {
+        LayoutError::Reference(err)
+    }
+}
+
+/// `Charset` defines the valid characters of a segment
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Charset {
+    /// Digits 0-9, values are padded with leading zeros
+    Numeric,
+    /// Letters a-z and A-Z
+    Alphabetic,
+    /// Digits 0-9 and letters a-z and A-Z
+    Alphanumeric,
+}
+
+impl Charset {
+    /// Checks if `c` is valid in the charset
+    fn contains(self, c: char) -> bool {
+        match self {
+            Charset::Numeric => c.is_ascii_digit(),
+            Charset::Alphabetic => c.is_ascii_alphabetic(),
+            Charset::Alphanumeric => c.is_ascii_alphanumeric(),
+        }
+    }
+
+    /// Number of distinct characters ignoring case
+    pub(crate) fn size(self) -> u32 {
+        match self {
+            Charset::Numeric => 10,
+            Charset::Alphabetic => 26,
+            Charset::Alphanumeric => 36,
+        }
+    }
+}
+
+/// Named segment with fixed width
+#[derive(Clone, Debug, Eq, PartialEq)]
+struct Segment {
+    name: String,
+    width: usize,
+    charset: Charset,
+}
+
+/// `SegmentLayout` defines the segments of the reference body in order
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct SegmentLayout {
+    segments: Vec<Segment>,
+}
+
+impl SegmentLayout {
+    /// Creates an empty [`SegmentLayout`]
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Appends a segment with `name`, fixed `width` and `charset`
+    #[must_use]
+    pub fn segment(mut self, name: &str, width: usize, charset: Charset) -> Self {
+        self.segments.push(Segment {
+            name: name.to_string(),
+            width,
+            charset,
+        });
+        self
+    }
+
+    /// Length of the reference body defined by the layout
+    #[must_use]
+    pub fn body_len(&self) -> usize {
+        self.segments.iter().map(|segment| segment.width).sum()
+    }
+
+    /// Generates a [`RfCreditorReference`] from `values` in order of the
+    /// segments
+    ///
+    /// Numeric values are padded with leading zeros to the segment width.
+    /// Other values need to have the exact width.
+    ///
+    /// # Errors
+    ///
+    /// Results in [`LayoutError::Reference`] with
+    /// [`ParseError::InvalidCharacter`] with the position in the value when
+    /// a value does not match the charset of its segment, and with
+    /// [`ParseError::BodyTooLong`] when the layout exceeds the body length.
+    /// Results in [`LayoutError::Width`] when a value does not fit the
+    /// segment width and in [`LayoutError::TooManyValues`] when there are
+    /// more values than segments.
+    ///
+    pub fn encode(&self, values: &[&str]) -> Result<RfCreditorReference<'static>, LayoutError> {
+        if values.len() > self.segments.len() {
+            return Err(LayoutError::TooManyValues {
+                count: values.len(),
+                segments: self.segments.len(),
+            });
+        }
+
+        let mut builder = RfReferenceBuilder::new();
+        for (i, segment) in self.segments.iter().enumerate() {
+            let value = values.get(i).copied().unwrap_or_default();
+            let len = value.chars().count();
+
+            if let Some((position, character)) = value
+                .chars()
+                .enumerate()
+                .find(|&(_, c)| !segment.charset.contains(c))
+            {
+                return Err(LayoutError::Reference(ParseError::InvalidCharacter {
+                    input: value.to_string(),
+                    character,
+                    position,
+                }));
+            } else if len > segment.width
+                || (len < segment.width && segment.charset != Charset::Numeric)
+            {
+                return Err(LayoutError::Width {
+                    segment: i,
+                    input: value.to_string(),
+                    width: len,
+                    expected: segment.width,
+                });
+            }
+
+            builder = builder.segment(format!("{:0>width$}", value, width = segment.width));
+        }
+
+        Ok(builder.build()?)
+    }
+
+    /// Decodes the body of `rf` into its named segments
+    ///
+    /// # Errors
+    ///
+    /// Results in [`LayoutError::BodyLength`] when the body length does not
+    /// match the layout, and in [`LayoutError::Reference`] with
+    /// [`ParseError::InvalidCharacter`] with the position in the electronic
+    /// format when a segment does not match its charset.
+    ///
+    pub fn decode<'r>(
+        &self,
+        rf: &'r RfCreditorReference<'_>,
+    ) -> Result<Vec<(&str, &'r str)>, LayoutError> {
+        let electronic_reference = rf.as_electronic_str();
+        let body = &electronic_reference[GEN_PREFIX.len()..];
+        let len = body.len();
+        let body_len = self.body_len();
+
+        if len != body_len {
+            return Err(LayoutError::BodyLength {
+                input: electronic_reference.to_string(),
+                len,
+                expected: body_len,
+            });
+        }
+
+        let mut offset = 0;
+        let mut fields = Vec::with_capacity(self.segments.len());
+        for segment in &self.segments {
+            let value = &body[offset..offset + segment.width];
+            if let Some((position, character)) = value
+                .char_indices()
+                .find(|&(_, c)| !segment.charset.contains(c))
+            {
+                return Err(LayoutError::Reference(ParseError::InvalidCharacter {
+                    input: electronic_reference.to_string(),
+                    character,
+                    position: GEN_PREFIX.len() + offset + position,
+                }));
+            }
+            fields.push((segment.name.as_str(), value));
+            offset += segment.width;
+        }
+
+        Ok(fields)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn layout() -> SegmentLayout {
+        SegmentLayout::new()
+            .segment("customer", 4, Charset::Numeric)
+            .segment("type", 2, Charset::Alphabetic)
+            .segment("invoice", 6, Charset::Alphanumeric)
+    }
+
+    #[test]
+    fn encode_test() {
+        let layout = layout();
+        assert_eq!(layout.body_len(), 12);
+
+        let rf = layout.encode(&["42", "IN", "A12345"]).unwrap();
+        assert_eq!(&rf.as_electronic_str()[4..], "0042INA12345");
+
+        let rf = layout.encode(&["", "CR", "000001"]).unwrap();
+        assert_eq!(&rf.as_electronic_str()[4..], "0000CR000001");
+
+        assert!(matches!(
+            layout.encode(&["4A", "IN", "A12345"]),
+            Err(LayoutError::Reference(ParseError::InvalidCharacter {
+                character: 'A',
+                position: 1,
+                ..
+            }))
+        ));
+        assert!(matches!(
+            layout.encode(&["47110", "IN", "A12345"]),
+            Err(LayoutError::Width {
+                segment: 0,
+                width: 5,
+                expected: 4,
+                ..
+            })
+        ));
+        assert!(matches!(
+            layout.encode(&["4711", "IN", "A1234"]),
+            Err(LayoutError::Width {
+                segment: 2,
+                width: 5,
+                expected: 6,
+                ..
+            })
+        ));
+        assert!(matches!(
+            layout.encode(&["4711", "IN"]),
+            Err(LayoutError::Width {
+                segment: 2,
+                width: 0,
+                expected: 6,
+                ..
+            })
+        ));
+        let err = layout.encode(&["4711", "IN", "A12345", "1"]).unwrap_err();
+        assert_eq!(
+            err,
+            LayoutError::TooManyValues {
+                count: 4,
+                segments: 3
+            }
+        );
+        assert_eq!(err.to_string(), "4 values for 3 segments");
+
+        let layout = SegmentLayout::new().segment("customer", 22, Charset::Numeric);
+        assert!(matches!(
+            layout.encode(&["1"]),
+            Err(LayoutError::Reference(ParseError::BodyTooLong { .. }))
+        ));
+    }
+
+    #[test]
+    fn decode_test() {
+        let layout = layout();
+        let rf = layout.encode(&["4711", "IN", "b12345"]).unwrap();
+        assert_eq!(
+            layout.decode(&rf).unwrap(),
+            [("customer", "4711"), ("type", "IN"), ("invoice", "b12345")]
+        );
+
+        let rf = RfReferenceBuilder::new()
+            .segment("4711IN1234")
+            .build()
+            .unwrap();
+        let err = layout.decode(&rf).unwrap_err();
+        assert!(matches!(
+            err,
+            LayoutError::BodyLength {
+                len: 10,
+                expected: 12,
+                ..
+            }
+        ));
+        assert_eq!(
+            err.to_string(),
+            "reference body has length 10 instead of 12 [RF594711IN1234]"
+        );
+
+        let rf = RfReferenceBuilder::new()
+            .segment("47110N123456")
+            .build()
+            .unwrap();
+        assert!(matches!(
+            layout.decode(&rf),
+            Err(LayoutError::Reference(ParseError::InvalidCharacter {
+                character: '0',
+                position: 8,
+                ..
+            }))
+        ));
+    }
+}
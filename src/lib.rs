@@ -27,19 +27,116 @@
     trivial_numeric_casts,
     unreachable_pub,
     unsafe_code,
-    unused_crate_dependencies,
     unused_extern_crates,
     unused_import_braces,
     unused_qualifications,
     unused_results
 )]
+// the dev-dependencies are for the integration tests of optional features
+#![cfg_attr(not(test), warn(unused_crate_dependencies))]
 #![forbid(unsafe_code)]
 
 use std::{borrow::Cow, str::FromStr};
 
-pub use parse_error::ParseError;
+use iso7064::{CheckCharacterSystem, Mod97_10};
+use print::PrintReference;
 
+// only used by the benches
+#[cfg(feature = "bench")]
+use criterion as _;
+
+#[cfg(feature = "rkyv")]
+pub use crate::rkyv::ArchivedRfCreditorReference;
+pub use builder::RfReferenceBuilder;
+pub use electronic::{parse_bytes, ElectronicReference};
+pub use extract::find_references;
+#[cfg(feature = "rayon")]
+pub use parallel::par_validate;
+pub use parse_error::{ParseError, ProblemDetails};
+pub use parse_options::{CasePolicy, ParseOptions};
+pub use reader::validate_reader;
+pub use report::{validate_iter, ValidationReport};
+pub use unvalidated::UnvalidatedReference;
+
+#[cfg(feature = "arrow")]
+pub mod arrow;
+#[cfg(feature = "avro")]
+pub mod avro;
+pub mod batch;
+#[cfg(feature = "borsh")]
+mod borsh;
+#[cfg(feature = "bson")]
+pub mod bson;
+pub mod builder;
+pub mod cache;
+#[cfg(feature = "clap")]
+pub mod clap;
+pub mod correction;
+#[cfg(feature = "csv")]
+pub mod csv;
+mod electronic;
+pub mod entropy;
+pub mod extract;
+#[cfg(feature = "garde")]
+pub mod garde;
+#[cfg(any(feature = "async-graphql", feature = "juniper"))]
+pub mod graphql;
+#[cfg(any(feature = "actix", feature = "axum", feature = "rocket"))]
+pub mod http;
+#[cfg(feature = "i18n")]
+pub mod i18n;
+pub mod iso7064;
+pub mod layout;
+#[cfg(feature = "miette")]
+mod miette_report;
+#[cfg(feature = "mock")]
+pub mod mock;
+#[cfg(any(feature = "okapi", feature = "poem-openapi", feature = "utoipa"))]
+mod openapi;
+#[cfg(feature = "rayon")]
+mod parallel;
 pub mod parse_error;
+pub mod parse_options;
+#[cfg(feature = "polars")]
+pub mod polars;
+pub mod prefix;
+mod print;
+pub mod profile;
+#[cfg(feature = "proto")]
+pub mod proto;
+#[cfg(feature = "pseudonymize")]
+pub mod pseudonymize;
+#[cfg(feature = "rand")]
+mod random;
+pub mod reader;
+#[cfg(feature = "redis")]
+mod redis;
+pub mod registry;
+pub mod report;
+#[cfg(feature = "rkyv")]
+pub mod rkyv;
+pub mod score;
+pub mod sequential;
+#[cfg(feature = "serde")]
+pub mod serde;
+#[cfg(feature = "simd")]
+mod simd;
+pub mod sql;
+#[cfg(feature = "futures")]
+pub mod stream;
+pub mod template;
+#[cfg(feature = "test-util")]
+pub mod test_vectors;
+pub mod timestamp;
+#[cfg(feature = "unicode")]
+pub mod unicode;
+pub mod unvalidated;
+#[cfg(feature = "uuid")]
+mod uuid;
+#[cfg(feature = "validator")]
+pub mod validator;
+#[cfg(feature = "web")]
+pub mod web;
 
 /// The official identifier for `RfCreditorReference`
 pub const IDENTIFIER: &str = "RF";
@@ -47,9 +144,252 @@ pub const IDENTIFIER: &str = "RF";
 /// Prefix with identifier to use for checksum calculation
 pub const GEN_PREFIX: &str = "RF00";
 
-const DIGIT_CONVERT_LOWCASE: i8 = -('a' as i8) + 10;
-const DIGIT_CONVERT_NUMBER: i8 = -('0' as i8);
-const DIGIT_CONVERT_UPCASE: i8 = -('A' as i8) + 10;
+/// Maximum length of a creditor reference in electronic format
+pub const MAX_LEN: usize = 25;
+
+/// Maximum length of the body of a creditor reference without
+/// identifier and check digits
+pub const MAX_BODY_LEN: usize = MAX_LEN - GEN_PREFIX.len();
+
+/// Minimum length of a creditor reference in electronic format
+pub const MIN_LEN: usize = 5;
+
+/// Computes the checksum for a reference `body`
+///
+/// The `body` is the creditor reference without identifier and check
+/// digits. Spaces are ignored.
+///
+/// See also [`compute_check_digits`].
+///
+/// # Examples
+///
+/// ```rust
+/// assert_eq!(iso_11649::compute_checksum("5390 0754 7034"), Ok(18));
+/// assert_eq!(iso_11649::compute_checksum("2348231"), Ok(71));
+/// ```
+///
+/// # Errors
+///
+/// Results in [`ParseError`]s when `body` is empty, too long or
+/// contains invalid characters.
+/// Valid characters are 0-9, a-z and A-Z.
+///
+pub fn compute_checksum(body: &str) -> Result<u8, ParseError> {
+    let max = MAX_BODY_LEN;
+    let mut len = 0;
+    let mut remainder = 0;
+
+    for (position, character) in body.chars().enumerate().filter(|&(_, c)| c != ' ') {
+        remainder =
+            Mod97_10::push(remainder, character).ok_or_else(|| ParseError::InvalidCharacter {
+                input: body.to_string(),
+                character,
+                position,
+            })?;
+        len += 1;
+    }
+
+    if len == 0 {
+        Err(ParseError::TooShort {
+            input: body.to_string(),
+            len,
+            min: 1,
+        })
+    } else if len > max {
+        Err(ParseError::BodyTooLong {
+            input: body.to_string(),
+            len,
+            max,
+        })
+    } else {
+        let remainder = GEN_PREFIX
+            .chars()
+            .try_fold(remainder, Mod97_10::push)
+            .unwrap_or_default();
+        #[allow(clippy::cast_possible_truncation)]
+        Ok((98 - remainder) as u8)
+    }
+}
+
+/// Computes the two check digits for a reference `body`
+///
+/// See [`compute_checksum`].
+///
+/// # Examples
+///
+/// ```rust
+/// assert_eq!(iso_11649::compute_check_digits("5390 0754 7034"), Ok(['1', '8']));
+/// assert_eq!(iso_11649::compute_check_digits("ABCD0754efgh"), Ok(['6', '3']));
+/// ```
+///
+/// # Errors
+///
+/// See [`compute_checksum`].
+///
+pub fn compute_check_digits(body: &str) -> Result<[char; 2], ParseError> {
+    let checksum = compute_checksum(body)?;
+    Ok([
+        (checksum / 10 + b'0') as char,
+        (checksum % 10 + b'0') as char,
+    ])
+}
+
+/// Checks if `reference` is a valid creditor reference without allocation
+///
+/// Spaces are ignored like in [`RfCreditorReference::parse_str`].
+///
+/// # Examples
+///
+/// ```rust
+/// assert!(iso_11649::is_valid("RF18 5390 0754 7034"));
+/// assert!(iso_11649::is_valid("RF18539007547034"));
+/// assert!(!iso_11649::is_valid("RF19 5390 0754 7034"));
+/// assert!(!iso_11649::is_valid("RF18 5390_0754 7034"));
+/// ```
+///
+#[must_use]
+pub fn is_valid(reference: &str) -> bool {
+    let mut len = 0;
+    let mut check_digits = ['0'; 2];
+    let mut remainder = 0;
+
+    for (i, c) in reference.chars().filter(|&c| c != ' ').enumerate() {
+        len = i + 1;
+        match i {
+            0 | 1 if Some(c) != IDENTIFIER.chars().nth(i) => return false,
+            2 | 3 if !c.is_ascii_digit() => return false,
+            2 | 3 => check_digits[i - 2] = c,
+            0 | 1 => {}
+            _ if len > MAX_LEN => return false,
+            _ => match Mod97_10::push(remainder, c) {
+                Some(r) => remainder = r,
+                None => return false,
+            },
+        }
+    }
+
+    if len < MIN_LEN {
+        return false;
+    }
+
+    IDENTIFIER
+        .chars()
+        .chain(check_digits)
+        .try_fold(remainder, Mod97_10::push)
+        == Some(1)
+}
+
+/// Checks for each of `references` if it is a valid creditor reference
+///
+/// The results are the same like with [`is_valid`]. With feature `simd`
+/// references without spaces are validated in parallel lanes, which is
+/// faster for large batches like clearing files.
+///
+/// # Examples
+///
+/// ```rust
+/// let references = ["RF18539007547034", "RF19539007547034", "RF71 2348 231"];
+///
+/// assert_eq!(iso_11649::is_valid_batch(&references), [true, false, true]);
+/// ```
+///
+#[must_use]
+pub fn is_valid_batch(references: &[&str]) -> Vec<bool> {
+    #[cfg(feature = "simd")]
+    {
+        simd::is_valid_batch(references)
+    }
+    #[cfg(not(feature = "simd"))]
+    {
+        references
+            .iter()
+            .map(|reference| is_valid(reference))
+            .collect()
+    }
+}
+
+/// Verifies a creditor reference in electronic format given as ASCII bytes
+///
+/// Unlike [`RfCreditorReference::parse_str`] spaces are not allowed.
+/// There is no allocation unless an error is returned.
+/// Positions in errors are byte indices.
+///
+/// # Examples
+///
+/// ```rust
+/// assert!(iso_11649::verify_bytes(b"RF18539007547034").is_ok());
+/// assert!(iso_11649::verify_bytes(b"RF19539007547034").is_err());
+/// assert!(iso_11649::verify_bytes(b"RF18 5390 0754 7034").is_err());
+/// ```
+///
+/// # Errors
+///
+/// Results in [`ParseError`]s when there is some problem with
+/// the `reference`.
+///
+pub fn verify_bytes(reference: &[u8]) -> Result<(), ParseError> {
+    let input = || String::from_utf8_lossy(reference).into_owned();
+    let len = reference.len();
+
+    if len < MIN_LEN {
+        return Err(ParseError::TooShort {
+            input: input(),
+            len,
+            min: MIN_LEN,
+        });
+    } else if len > MAX_LEN {
+        return Err(ParseError::TooLong {
+            input: input(),
+            len,
+            max: MAX_LEN,
+        });
+    } else if &reference[..2] != IDENTIFIER.as_bytes() {
+        return Err(ParseError::InvalidIdentifier { input: input() });
+    }
+
+    let mut remainder = 0;
+    for (position, &b) in reference[4..].iter().chain(&reference[..4]).enumerate() {
+        let position = (position + 4) % len;
+        remainder = match Mod97_10::push_byte(remainder, b) {
+            Some(r) if !(2..4).contains(&position) || b.is_ascii_digit() => r,
+            Some(_) => {
+                return Err(ParseError::InvalidCheckDigits {
+                    input: input(),
+                    source: String::from_utf8_lossy(&reference[2..4])
+                        .parse::<u8>()
+                        .unwrap_err(),
+                })
+            }
+            None => {
+                return Err(ParseError::InvalidCharacter {
+                    input: input(),
+                    character: String::from_utf8_lossy(&reference[position..])
+                        .chars()
+                        .next()
+                        .unwrap_or(char::REPLACEMENT_CHARACTER),
+                    position,
+                })
+            }
+        };
+    }
+
+    if remainder == 1 {
+        Ok(())
+    } else {
+        let found = (reference[2] - b'0') * 10 + (reference[3] - b'0');
+        let remainder = reference[4..]
+            .iter()
+            .chain(GEN_PREFIX.as_bytes())
+            .try_fold(0, |r, &b| Mod97_10::push_byte(r, b))
+            .unwrap_or_default();
+        #[allow(clippy::cast_possible_truncation)]
+        Err(ParseError::InvalidChecksum {
+            input: input(),
+            expected: (98 - remainder) as u8,
+            found,
+        })
+    }
+}
 
 /// `RfCreditorReference` provides generator and validator for
 /// creditor references.
@@ -85,6 +425,7 @@ const DIGIT_CONVERT_UPCASE: i8 = -('A' as i8) + 10;
 /// let rf = RfCreditorReference::from_str("RF18539007547034").unwrap();
 ///
 /// assert_eq!(rf.to_string(), "RF18 5390 0754 7034");
+/// assert_eq!(format!("{:#}", rf), "RF18539007547034");
 ///
 /// let r: &str = (&rf).into();
 /// assert_eq!(r, "RF18 5390 0754 7034");
@@ -107,10 +448,14 @@ const DIGIT_CONVERT_UPCASE: i8 = -('A' as i8) + 10;
 ///         // multiple kinds of errors, with cause of
 ///         // why validation failed...
 ///         Err(err) => match err {
-///             ParseError::InvalidCharacter(_) => {}
-///             ParseError::InvalidChecksum(_) => {}
-///             ParseError::InvalidFormat(_) => {}
-///             ParseError::InvalidIdentifier(_) => {}
+///             ParseError::BodyTooLong { .. } => {}
+///             ParseError::InvalidCharacter { .. } => {}
+///             ParseError::InvalidCheckDigits { .. } => {}
+///             ParseError::InvalidChecksum { .. } => {}
+///             ParseError::InvalidIdentifier { .. } => {}
+///             ParseError::TooLong { .. } => {}
+///             ParseError::TooShort { .. } => {}
+///             _ => {}
 ///         },
 ///     }
 /// }
@@ -119,12 +464,19 @@ const DIGIT_CONVERT_UPCASE: i8 = -('A' as i8) + 10;
 ///
 /// ```
 ///
-#[derive(Clone, Debug, Eq, PartialEq)]
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(
+    feature = "diesel",
+    derive(::diesel::expression::AsExpression, ::diesel::deserialize::FromSqlRow),
+    diesel(sql_type = ::diesel::sql_types::Text)
+)]
 pub struct RfCreditorReference<'a> {
     /// The checksum digits of reference
     checksum: u8,
     /// For print formatted creditor reference string
-    creditor_reference: Cow<'a, str>,
+    creditor_reference: PrintReference,
+    /// For electronic formatted creditor reference string
+    electronic_reference: Cow<'a, str>,
 }
 
 impl RfCreditorReference<'_> {
@@ -138,7 +490,8 @@ impl RfCreditorReference<'_> {
     ///
     /// # Panics
     ///
-    /// If `reference` contains invalid characters.
+    /// If `reference` contains invalid characters or the body is longer
+    /// than [`MAX_BODY_LEN`].
     /// Valid characters are 0-9, a-z and A-Z.
     ///
     #[must_use]
@@ -156,167 +509,606 @@ impl RfCreditorReference<'_> {
     /// parsing the `reference`.
     ///
     pub fn parse_str(reference: &str) -> Result<Self, ParseError> {
-        Self::check_reference(reference)?;
-
-        let reference = RfCreditorReference::convert_electronic(reference);
-        let checksum = str::parse::<u8>(&reference[2..4]);
-
-        if let Err(e) = checksum {
-            Err(ParseError::InvalidChecksum(e.to_string()))
-        } else {
-            let checksum = checksum.unwrap_or_default();
-
-            let check_digits = Self::gen_check_digits(&reference)?;
-
-            if Self::is_valid(&check_digits) {
-                let four_elemented_ref = reference[4..]
-                    .chars()
-                    .enumerate()
-                    .flat_map(|(i, c)| {
-                        if i != 0 && i % 4 == 0 {
-                            vec![' ', c]
-                        } else {
-                            vec![c]
-                        }
-                    })
-                    .collect::<String>();
-                let creditor_reference = Cow::from(format!(
-                    "{}{:02} {}",
-                    IDENTIFIER, checksum, four_elemented_ref
-                ));
-                Ok(Self {
-                    checksum,
-                    creditor_reference,
-                })
-            } else {
-                Err(ParseError::InvalidChecksum(reference))
-            }
-        }
+        parse_bytes(reference.as_bytes()).map(Self::from)
     }
 
     /// Retrieves `creditor_reference` in electronic format without spaces
+    ///
+    /// See also [`Self::as_electronic_str`] to avoid the allocation.
     #[must_use]
     #[inline]
     pub fn to_electronic_string(&self) -> String {
-        Self::convert_electronic(&self.creditor_reference)
+        self.electronic_reference.to_string()
+    }
+
+    /// Borrows `creditor_reference` in electronic format without spaces
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use iso_11649::RfCreditorReference;
+    ///
+    /// let rf = RfCreditorReference::new("539007547034");
+    ///
+    /// assert_eq!(rf.as_electronic_str(), "RF18539007547034");
+    /// ```
+    ///
+    #[must_use]
+    #[inline]
+    pub fn as_electronic_str(&self) -> &str {
+        &self.electronic_reference
+    }
+
+    /// Retrieves `creditor_reference` in groups of `group_size` characters
+    /// separated by spaces
+    ///
+    /// The print format uses groups of 4. A `group_size` of 0 results in
+    /// the electronic format.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use iso_11649::RfCreditorReference;
+    ///
+    /// let rf = RfCreditorReference::new("539007547034");
+    ///
+    /// assert_eq!(rf.to_grouped_string(4), "RF18 5390 0754 7034");
+    /// assert_eq!(rf.to_grouped_string(5), "RF185 39007 54703 4");
+    /// assert_eq!(rf.to_grouped_string(0), "RF18539007547034");
+    /// ```
+    ///
+    #[must_use]
+    pub fn to_grouped_string(&self, group_size: usize) -> String {
+        Self::group(&self.electronic_reference, group_size, " ")
+    }
+
+    /// Retrieves `creditor_reference` in groups of 4 characters separated
+    /// by `separator` instead of spaces
+    ///
+    /// To parse such a reference see
+    /// [`ParseOptions::strip_separators`](crate::ParseOptions::strip_separators).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use iso_11649::RfCreditorReference;
+    ///
+    /// let rf = RfCreditorReference::new("539007547034");
+    ///
+    /// assert_eq!(rf.to_separated_string("-"), "RF18-5390-0754-7034");
+    /// assert_eq!(rf.to_separated_string(" / "), "RF18 / 5390 / 0754 / 7034");
+    /// ```
+    ///
+    #[must_use]
+    pub fn to_separated_string(&self, separator: &str) -> String {
+        Self::group(&self.electronic_reference, 4, separator)
+    }
+
+    /// Iterates over the groups of 4 characters of the print format
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use iso_11649::RfCreditorReference;
+    ///
+    /// let rf = RfCreditorReference::new("539007547034");
+    ///
+    /// assert_eq!(rf.groups().collect::<Vec<_>>(), ["RF18", "5390", "0754", "7034"]);
+    /// ```
+    ///
+    pub fn groups(&self) -> impl Iterator<Item = &str> {
+        self.creditor_reference.split(' ')
+    }
+
+    /// Iterates over the characters of the electronic format without
+    /// intermediate allocation
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use iso_11649::RfCreditorReference;
+    ///
+    /// let rf = RfCreditorReference::new("539007547034");
+    ///
+    /// assert!(rf.electronic_chars().eq("RF18539007547034".chars()));
+    /// ```
+    ///
+    pub fn electronic_chars(&self) -> impl Iterator<Item = char> + '_ {
+        self.electronic_reference.chars()
+    }
+
+    /// Retrieves `creditor_reference` in paper format of ISO 11649
+    ///
+    /// The paper format has uppercase letters in groups of 4 characters
+    /// separated by single spaces, the last group possibly shorter.
+    /// It is independent of how the reference was entered.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use iso_11649::RfCreditorReference;
+    ///
+    /// let rf = RfCreditorReference::parse_str("RF63abcd0754efgh").unwrap();
+    ///
+    /// assert_eq!(rf.to_paper_string(), "RF63 ABCD 0754 EFGH");
+    /// ```
+    ///
+    #[must_use]
+    pub fn to_paper_string(&self) -> String {
+        Self::group(&self.electronic_reference.to_ascii_uppercase(), 4, " ")
+    }
+
+    /// Retrieves `creditor_reference` in electronic format padded with
+    /// `pad_char` to `width` characters
+    ///
+    /// The reference is not truncated if it is longer than `width`.
+    /// The [`Display`](std::fmt::Display) implementation honors width,
+    /// fill and alignment of the formatter as well.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use std::fmt::Alignment;
+    ///
+    /// use iso_11649::RfCreditorReference;
+    ///
+    /// let rf = RfCreditorReference::new("539007547034");
+    ///
+    /// assert_eq!(rf.to_padded_string(20, ' ', Alignment::Left), "RF18539007547034    ");
+    /// assert_eq!(rf.to_padded_string(20, '0', Alignment::Right), "0000RF18539007547034");
+    /// assert_eq!(format!("{:*<20}", rf), "RF18 5390 0754 7034*");
+    /// ```
+    ///
+    #[must_use]
+    pub fn to_padded_string(
+        &self,
+        width: usize,
+        pad_char: char,
+        alignment: std::fmt::Alignment,
+    ) -> String {
+        let electronic_reference = &self.electronic_reference;
+        let padding = width.saturating_sub(electronic_reference.len());
+        let (before, after) = match alignment {
+            std::fmt::Alignment::Left => (0, padding),
+            std::fmt::Alignment::Right => (padding, 0),
+            std::fmt::Alignment::Center => (padding / 2, padding - padding / 2),
+        };
+
+        let mut padded = String::with_capacity(electronic_reference.len() + padding.min(MAX_LEN));
+        padded.extend(std::iter::repeat(pad_char).take(before));
+        padded.push_str(electronic_reference);
+        padded.extend(std::iter::repeat(pad_char).take(after));
+        padded
+    }
+
+    /// Writes `creditor_reference` in print format with spaces to `w`
+    /// without intermediate allocation
+    ///
+    /// # Errors
+    ///
+    /// Results in [`std::fmt::Error`] when writing to `w` fails.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use iso_11649::RfCreditorReference;
+    ///
+    /// let rf = RfCreditorReference::new("539007547034");
+    /// let mut buf = String::new();
+    /// rf.write_print(&mut buf).unwrap();
+    ///
+    /// assert_eq!(buf, "RF18 5390 0754 7034");
+    /// ```
+    ///
+    #[inline]
+    pub fn write_print(&self, w: &mut impl std::fmt::Write) -> std::fmt::Result {
+        w.write_str(&self.creditor_reference)
+    }
+
+    /// Writes `creditor_reference` in electronic format without spaces to `w`
+    /// without intermediate allocation
+    ///
+    /// # Errors
+    ///
+    /// Results in [`std::fmt::Error`] when writing to `w` fails.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use iso_11649::RfCreditorReference;
+    ///
+    /// let rf = RfCreditorReference::new("539007547034");
+    /// let mut buf = String::new();
+    /// rf.write_electronic(&mut buf).unwrap();
+    ///
+    /// assert_eq!(buf, "RF18539007547034");
+    /// ```
+    ///
+    pub fn write_electronic(&self, w: &mut impl std::fmt::Write) -> std::fmt::Result {
+        w.write_str(&self.electronic_reference)
+    }
+
+    /// Converts the letters of `creditor_reference` to uppercase
+    ///
+    /// The checksum is not affected, because letters are validated
+    /// regardless of their case.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use iso_11649::RfCreditorReference;
+    ///
+    /// let rf = RfCreditorReference::parse_str("RF63 abcd 0754 efgh").unwrap();
+    ///
+    /// assert_eq!(rf.to_uppercase_reference().to_string(), "RF63 ABCD 0754 EFGH");
+    /// ```
+    ///
+    #[must_use]
+    pub fn to_uppercase_reference(&self) -> RfCreditorReference<'static> {
+        RfCreditorReference {
+            checksum: self.checksum,
+            creditor_reference: self.creditor_reference.to_ascii_uppercase(),
+            electronic_reference: Cow::from(self.electronic_reference.to_ascii_uppercase()),
+        }
+    }
+
+    /// Generates new [`RfCreditorReference`] with decimal number `n` as body
+    ///
+    /// See also [`Self::from_u128`] and [`Self::to_u128`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use iso_11649::RfCreditorReference;
+    ///
+    /// assert_eq!(RfCreditorReference::from_u64(539007547034).to_string(), "RF18 5390 0754 7034");
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Never, because [`u64::MAX`] has 20 digits and fits into the body.
+    ///
+    #[must_use]
+    pub fn from_u64(n: u64) -> RfCreditorReference<'static> {
+        RfReferenceBuilder::new()
+            .segment(n.to_string())
+            .build()
+            .expect("the 20 digits of u64::MAX fit into the body")
+    }
+
+    /// Generates new [`RfCreditorReference`] with decimal number `n` as body
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use iso_11649::RfCreditorReference;
+    ///
+    /// let rf = RfCreditorReference::from_u128(539007547034).unwrap();
+    /// assert_eq!(rf.to_u128(), Some(539007547034));
+    ///
+    /// assert!(RfCreditorReference::from_u128(u128::MAX).is_err());
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Results in [`ParseError::BodyTooLong`] when `n` has more than 21 digits.
+    ///
+    pub fn from_u128(n: u128) -> Result<RfCreditorReference<'static>, ParseError> {
+        RfReferenceBuilder::new().segment(n.to_string()).build()
+    }
+
+    /// Retrieves the body as decimal number
+    ///
+    /// Returns `None` if the body contains letters. Leading zeros of the
+    /// body are not preserved.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use iso_11649::RfCreditorReference;
+    ///
+    /// assert_eq!(RfCreditorReference::new("539007547034").to_u128(), Some(539007547034));
+    /// assert_eq!(RfCreditorReference::new("ABCD0754").to_u128(), None);
+    /// ```
+    ///
+    #[must_use]
+    pub fn to_u128(&self) -> Option<u128> {
+        let body = &self.electronic_reference[GEN_PREFIX.len()..];
+        if body.bytes().all(|b| b.is_ascii_digit()) {
+            body.parse().ok()
+        } else {
+            None
+        }
     }
 
     /// Try to generate new [`RfCreditorReference`] from specified reference
     ///
     /// See also [`Self::new`] and [`Self::parse_str`].
     ///
-    /// # Examples
+    /// # Examples
+    ///
+    /// See [`RfCreditorReference`].
+    ///
+    /// The body of `reference` has at most [`MAX_BODY_LEN`] characters
+    /// without spaces. A leading `RF` or `RF00` is not part of the body.
+    ///
+    /// # Errors
+    ///
+    /// If `reference` contains invalid characters.
+    /// Valid characters are 0-9, a-z and A-Z.
+    ///
+    /// If the body is longer than [`MAX_BODY_LEN`], resulting in
+    /// [`ParseError::BodyTooLong`].
+    ///
+    pub fn try_new(reference: &str) -> Result<Self, ParseError> {
+        Self::generate(reference)
+    }
+
+    /// Generates a [`RfCreditorReference`] for each of `bodies` in order
+    ///
+    /// The result of each body is the same as with [`Self::try_new`], which
+    /// allocates only the resulting reference. Useful for batch runs
+    /// minting a large number of references.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use iso_11649::RfCreditorReference;
+    ///
+    /// let refs = RfCreditorReference::generate_batch(["539007547034", "2348231", "ABC_"]);
+    ///
+    /// assert_eq!(refs[0].as_ref().unwrap().to_string(), "RF18 5390 0754 7034");
+    /// assert_eq!(refs[1].as_ref().unwrap().to_string(), "RF71 2348 231");
+    /// assert!(refs[2].is_err());
+    /// ```
+    ///
+    pub fn generate_batch<'b, I>(bodies: I) -> Vec<Result<RfCreditorReference<'static>, ParseError>>
+    where
+        I: IntoIterator<Item = &'b str>,
+    {
+        bodies.into_iter().map(Self::generate).collect()
+    }
+
+    /// Generates the [`RfCreditorReference`] of `reference` like
+    /// [`Self::try_new`]
+    ///
+    /// The electronic format is built in place with check digits `00`,
+    /// which are replaced after the single pass of the checksum computation.
+    fn generate(reference: &str) -> Result<RfCreditorReference<'static>, ParseError> {
+        let start = if reference.len() > GEN_PREFIX.len() && reference.starts_with(GEN_PREFIX) {
+            GEN_PREFIX.len()
+        } else if reference.len() > IDENTIFIER.len() && reference.starts_with(IDENTIFIER) {
+            IDENTIFIER.len()
+        } else {
+            0
+        };
+
+        let mut electronic_reference =
+            String::with_capacity(GEN_PREFIX.len() + reference.len() - start);
+        electronic_reference.push_str(GEN_PREFIX);
+        electronic_reference.extend(reference[start..].chars().filter(|&c| c != ' '));
+
+        // errors are rare, so they are reported by the slower validation,
+        // which also counts chars instead of bytes
+        let len = electronic_reference.len() - GEN_PREFIX.len();
+        let remainder = match Self::gen_check_digits(&electronic_reference) {
+            Ok(remainder) if (1..=MAX_BODY_LEN).contains(&len) => remainder,
+            result => {
+                Self::check_new(reference, &electronic_reference)?;
+                result?
+            }
+        };
+        let (checksum, check_digits) = Self::gen_checksum(remainder);
+
+        let mut utf8 = [0; 4];
+        for (i, c) in check_digits.iter().enumerate() {
+            let position = IDENTIFIER.len() + i;
+            electronic_reference.replace_range(position..=position, c.encode_utf8(&mut utf8));
+        }
+
+        Ok(RfCreditorReference {
+            checksum,
+            creditor_reference: PrintReference::new(&electronic_reference),
+            electronic_reference: Cow::from(electronic_reference),
+        })
+    }
+
+    /// Creates the [`RfCreditorReference`] of the valid `body` with its
+    /// `checksum` without validation
+    fn from_checksum(checksum: u8, body: &str) -> RfCreditorReference<'static> {
+        let mut electronic_reference = String::with_capacity(GEN_PREFIX.len() + body.len());
+        electronic_reference.push_str(IDENTIFIER);
+        electronic_reference.push(char::from(checksum / 10 + b'0'));
+        electronic_reference.push(char::from(checksum % 10 + b'0'));
+        electronic_reference.push_str(body);
+
+        RfCreditorReference {
+            checksum,
+            creditor_reference: PrintReference::new(&electronic_reference),
+            electronic_reference: Cow::from(electronic_reference),
+        }
+    }
+
+    /// Validates the `electronic_reference` with check digits `00` generated
+    /// from `reference`
+    fn check_new(reference: &str, electronic_reference: &str) -> Result<(), ParseError> {
+        let len = electronic_reference.chars().count() - GEN_PREFIX.len();
+        if len > MAX_BODY_LEN {
+            return Err(ParseError::BodyTooLong {
+                input: reference.to_string(),
+                len,
+                max: MAX_BODY_LEN,
+            });
+        }
+
+        Self::check_reference(electronic_reference)
+    }
+
+    /// Validates a `reference` and collects all detectable problems
+    ///
+    /// Unlike [`Self::parse_str`], which fails on the first problem,
+    /// every violation of length, identifier and characters is reported.
+    /// The checksum is only verified when there is no other problem.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use iso_11649::RfCreditorReference;
+    ///
+    /// assert!(RfCreditorReference::validate_all("RF18 5390 0754 7034").is_ok());
     ///
-    /// See [`RfCreditorReference`].
+    /// let errors = RfCreditorReference::validate_all("XX18 5390_0754@7034").unwrap_err();
+    /// assert_eq!(errors.len(), 3);
+    /// ```
     ///
     /// # Errors
     ///
-    /// If `reference` contains invalid characters.
-    /// Valid characters are 0-9, a-z and A-Z.
+    /// Results in all found [`ParseError`]s when there is some problem with
+    /// parsing the `reference`.
     ///
-    pub fn try_new(reference: &str) -> Result<Self, ParseError> {
-        let mut electronic_reference =
-            if reference.len() > GEN_PREFIX.len() && reference.starts_with(GEN_PREFIX) {
-                Self::convert_electronic(reference)
-            } else if reference.len() > IDENTIFIER.len() && reference.starts_with(IDENTIFIER) {
-                GEN_PREFIX.to_string() + &Self::convert_electronic(reference)[2..]
-            } else {
-                GEN_PREFIX.to_string() + &Self::convert_electronic(reference)
-            };
+    pub fn validate_all(reference: &str) -> Result<(), Vec<ParseError>> {
+        let mut errors = Vec::new();
 
-        Self::check_reference(&electronic_reference)?;
-
-        let checksum = Self::gen_checksum(&Self::gen_check_digits(&electronic_reference)?);
+        let electronic_reference = Self::convert_electronic(reference);
+        if let Err(err) = Self::check_len(reference, &electronic_reference) {
+            errors.push(err);
+        }
+        if electronic_reference.get(..2) != Some(IDENTIFIER) {
+            errors.push(ParseError::InvalidIdentifier {
+                input: reference.to_string(),
+            });
+        }
+        errors.extend(
+            reference
+                .chars()
+                .enumerate()
+                .filter(|&(_, c)| c != ' ')
+                .skip(4)
+                .filter(|&(_, c)| !c.is_ascii_alphanumeric())
+                .map(|(position, character)| ParseError::InvalidCharacter {
+                    input: reference.to_string(),
+                    character,
+                    position,
+                }),
+        );
 
-        electronic_reference.replace_range(2..4, &String::from_iter(checksum.1));
+        if errors.is_empty() {
+            if let Err(err) = Self::parse_str(reference) {
+                errors.push(err);
+            }
+        }
 
-        Self::parse_str(&electronic_reference)
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
     }
 
     /// First basic validation of reference
     fn check_reference(reference: &str) -> Result<(), ParseError> {
-        let reference = RfCreditorReference::convert_electronic(reference);
-        if !(reference.len() > 4 && reference.len() <= 25) {
-            Err(ParseError::InvalidFormat(reference))
-        } else if &reference[..2] != IDENTIFIER {
-            Err(ParseError::InvalidIdentifier(reference))
-        } else if reference[4..]
-            .find(|c| {
-                !(('0'..='9').contains(&c) || ('A'..='Z').contains(&c) || ('a'..='z').contains(&c))
+        let electronic_reference = RfCreditorReference::convert_electronic(reference);
+        Self::check_len(reference, &electronic_reference)?;
+
+        if electronic_reference.get(..2) != Some(IDENTIFIER) {
+            Err(ParseError::InvalidIdentifier {
+                input: reference.to_string(),
             })
-            .is_some()
+        } else if let Some((position, character)) = reference
+            .chars()
+            .enumerate()
+            .filter(|&(_, c)| c != ' ')
+            .skip(4)
+            .find(|&(_, c)| !c.is_ascii_alphanumeric())
         {
-            Err(ParseError::InvalidCharacter(reference))
+            Err(ParseError::InvalidCharacter {
+                input: reference.to_string(),
+                character,
+                position,
+            })
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Validates the length of `electronic_reference` against
+    /// [`MIN_LEN`] and [`MAX_LEN`]
+    fn check_len(reference: &str, electronic_reference: &str) -> Result<(), ParseError> {
+        let len = electronic_reference.chars().count();
+        if len < MIN_LEN {
+            Err(ParseError::TooShort {
+                input: reference.to_string(),
+                len,
+                min: MIN_LEN,
+            })
+        } else if len > MAX_LEN {
+            Err(ParseError::TooLong {
+                input: reference.to_string(),
+                len,
+                max: MAX_LEN,
+            })
         } else {
             Ok(())
         }
     }
 
+    /// Groups `electronic_reference` in groups of `group_size` characters
+    /// separated by `separator`
+    fn group(electronic_reference: &str, group_size: usize, separator: &str) -> String {
+        let mut grouped =
+            String::with_capacity(electronic_reference.len() * (separator.len() + 4) / 4);
+        for (i, c) in electronic_reference.chars().enumerate() {
+            if group_size != 0 && i != 0 && i % group_size == 0 {
+                grouped.push_str(separator);
+            }
+            grouped.push(c);
+        }
+        grouped
+    }
+
     #[inline]
     fn convert_electronic(reference: &str) -> String {
         reference.replace(' ', "")
     }
 
-    /// Try to generate a `Vec` of `electronic_reference` with digits
+    /// Try to compute the MOD 97-10 remainder of `electronic_reference`
+    /// with identifier and check digits moved to the end
     ///
     /// See also [`Self::to_electronic_string`]
     /// and [`convert_electronic`].
     #[inline]
-    fn gen_check_digits(electronic_reference: &str) -> Result<Vec<i8>, ParseError> {
-        let map = electronic_reference[4..]
-            .chars()
-            .chain(electronic_reference[0..4].chars())
-            .map(|c| match c {
-                '0'..='9' => {
-                    let n = (c as i8) + DIGIT_CONVERT_NUMBER;
-                    Some(vec![n])
-                }
-                'A'..='Z' => {
-                    let n = (c as i8) + DIGIT_CONVERT_UPCASE;
-                    let t = n / 10;
-                    Some(vec![t, n - t * 10])
-                }
-                'a'..='z' => {
-                    let n = (c as i8) + DIGIT_CONVERT_LOWCASE;
-                    let t = n / 10;
-                    Some(vec![t, n - t * 10])
-                }
-                _ => None,
-            });
-
-        if map.clone().any(|o| o.is_none()) {
-            return Err(ParseError::InvalidCharacter(
-                electronic_reference.to_string(),
-            ));
-        }
+    fn gen_check_digits(electronic_reference: &str) -> Result<u32, ParseError> {
+        let bytes = electronic_reference.as_bytes();
+        let (prefix, body) = bytes.split_at(GEN_PREFIX.len().min(bytes.len()));
 
-        // unwrap() ok, because return ParseError above
-        let digits = map.flat_map(Option::unwrap).collect();
-
-        Ok(digits)
+        body.iter()
+            .chain(prefix)
+            .try_fold(0, |remainder, &b| Mod97_10::push_byte(remainder, b))
+            .ok_or_else(|| {
+                let chars = electronic_reference.chars().enumerate();
+                let (position, character) = chars
+                    .clone()
+                    .skip(GEN_PREFIX.len())
+                    .chain(chars.take(GEN_PREFIX.len()))
+                    .find(|&(_, c)| !c.is_ascii_alphanumeric())
+                    .unwrap_or_default();
+                ParseError::InvalidCharacter {
+                    input: electronic_reference.to_string(),
+                    character,
+                    position,
+                }
+            })
     }
 
-    /// Generates the checksum
+    /// Generates the checksum of the `remainder` of a reference with check
+    /// digits `00`
     ///
     /// Returns a tuple with checksum as `u8` and the two checksum digits as `[char; 2]`.<br>
     /// Later can be used with e.g. `String::from_iter()`.
     #[inline]
-    fn gen_checksum(check_digits: &[i8]) -> (u8, [char; 2]) {
-        #[allow(clippy::cast_sign_loss, clippy::cast_possible_truncation)]
-        let checksum = u8::try_from(
-            98u128
-                .checked_sub(
-                    check_digits
-                        .iter()
-                        .rev()
-                        .enumerate()
-                        .map(|(i, &n)| (n as u128) * 10u128.pow(i as u32))
-                        .sum::<u128>()
-                        % 97,
-                )
-                .unwrap(),
-        )
-        .unwrap();
+    fn gen_checksum(remainder: u32) -> (u8, [char; 2]) {
+        #[allow(clippy::cast_possible_truncation)]
+        let checksum = (98 - remainder % 97) as u8;
 
         let checksum_chars = &mut ['0'; 2];
         checksum_chars[0] = (checksum / 10 + 48) as char;
@@ -324,23 +1116,28 @@ impl RfCreditorReference<'_> {
 
         (checksum, *checksum_chars)
     }
+}
 
-    /// Returns true if `check_digits` contains valid data and checksum.
+impl<'a> RfCreditorReference<'a> {
+    /// Borrows `creditor_reference` in electronic format without spaces as
+    /// stored
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use std::borrow::Cow;
+    ///
+    /// use iso_11649::RfCreditorReference;
+    ///
+    /// let rf = RfCreditorReference::new("539007547034");
+    ///
+    /// assert_eq!(rf.as_electronic_cow(), &Cow::from("RF18539007547034"));
+    /// ```
+    ///
+    #[must_use]
     #[inline]
-    fn is_valid(check_digits: &[i8]) -> bool {
-        #[allow(clippy::cast_sign_loss, clippy::cast_possible_truncation)]
-        let check = u8::try_from(
-            check_digits
-                .iter()
-                .rev()
-                .enumerate()
-                .map(|(i, &n)| (n as u128) * 10u128.pow(i as u32))
-                .sum::<u128>()
-                % 97,
-        )
-        .unwrap();
-
-        check == 1
+    pub fn as_electronic_cow(&self) -> &Cow<'a, str> {
+        &self.electronic_reference
     }
 }
 
@@ -358,25 +1155,44 @@ impl From<RfCreditorReference<'_>> for String {
 
 impl<'a> From<&'a RfCreditorReference<'a>> for &'a str {
     fn from(id: &'a RfCreditorReference) -> Self {
-        id.creditor_reference.as_ref()
+        id.creditor_reference.as_str()
     }
 }
 
-impl<'a> From<&'a RfCreditorReference<'a>> for &Cow<'a, str> {
+/// Borrows the print format.
+///
+/// Replaces the conversion to `&Cow<str>`, because the print format is no
+/// `Cow` anymore. See [`RfCreditorReference::as_electronic_cow`] for the
+/// electronic format.
+impl<'a> From<&'a RfCreditorReference<'a>> for Cow<'a, str> {
     fn from(id: &'a RfCreditorReference<'a>) -> Self {
-        &id.creditor_reference
+        Cow::Borrowed(id.creditor_reference.as_str())
     }
 }
 
 impl<'a> From<RfCreditorReference<'a>> for Cow<'a, str> {
     fn from(id: RfCreditorReference<'a>) -> Self {
-        id.creditor_reference
+        Cow::from(id.creditor_reference.to_string())
     }
 }
 
-impl<'a> std::fmt::Display for RfCreditorReference<'a> {
+/// Formats the print format with spaces.
+///
+/// The alternate flag `{:#}` formats the electronic format without spaces.
+/// Width, fill and alignment of the formatter are honored.
+impl std::fmt::Display for RfCreditorReference<'_> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        f.write_str(&*self.creditor_reference)
+        if f.width().is_some() {
+            if f.alternate() {
+                f.pad(&self.electronic_reference)
+            } else {
+                f.pad(&self.creditor_reference)
+            }
+        } else if f.alternate() {
+            self.write_electronic(f)
+        } else {
+            self.write_print(f)
+        }
     }
 }
 
@@ -431,22 +1247,142 @@ mod tests {
         );
     }
 
+    #[test]
+    fn is_valid_test() {
+        for vr in VALID_REFS {
+            assert!(is_valid(vr), "not valid: {}", vr);
+        }
+        for ir in INVALID_REFS {
+            assert!(!is_valid(ir), "should not be valid: {}", ir);
+        }
+        for gr in GEN_REFS {
+            assert!(!is_valid(gr), "should not be valid: {}", gr);
+        }
+        assert!(!is_valid(""));
+        assert!(!is_valid("RF18"));
+        assert!(!is_valid("RF1A539007547034"));
+        assert!(!is_valid("rf18539007547034"));
+    }
+
+    #[test]
+    fn generate_batch_test() {
+        let bodies = GEN_REFS.iter().copied().chain([
+            "SOS",
+            "",
+            "RF",
+            "ABC_",
+            "5390075470349283012345",
+            "RF18539007547034",
+        ]);
+
+        let results = RfCreditorReference::generate_batch(bodies.clone());
+        assert_eq!(results.len(), GEN_REFS.len() + 6);
+        for (result, body) in results.into_iter().zip(bodies) {
+            assert_eq!(result, RfCreditorReference::try_new(body), "{}", body);
+        }
+
+        assert!(RfCreditorReference::generate_batch(Vec::new()).is_empty());
+    }
+
+    #[test]
+    fn compute_checksum_test() {
+        for (gr, vr) in GEN_REFS.iter().zip(VALID_REFS) {
+            let body = gr.trim().trim_start_matches(GEN_PREFIX);
+            let checksum = compute_checksum(body).unwrap();
+            assert_eq!(
+                format!("{}{:02}{}", IDENTIFIER, checksum, body),
+                vr.trim(),
+                "wrong checksum for body {}",
+                body
+            );
+        }
+
+        assert_eq!(compute_check_digits("2348231"), Ok(['7', '1']));
+        assert_eq!(compute_check_digits("SOS"), Ok(['0', '6']));
+        assert!(matches!(
+            compute_checksum(""),
+            Err(ParseError::TooShort { len: 0, min: 1, .. })
+        ));
+        assert!(matches!(
+            compute_checksum("5390075470349283012345"),
+            Err(ParseError::BodyTooLong {
+                len: 22,
+                max: 21,
+                ..
+            })
+        ));
+        assert!(matches!(
+            compute_checksum("5390 07_4"),
+            Err(ParseError::InvalidCharacter {
+                character: '_',
+                position: 7,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn verify_bytes_test() {
+        for vr in VALID_REFS {
+            let electronic = RfCreditorReference::convert_electronic(vr);
+            assert_eq!(verify_bytes(electronic.as_bytes()), Ok(()), "{}", vr);
+        }
+        for ir in INVALID_REFS {
+            let electronic = RfCreditorReference::convert_electronic(ir);
+            assert_eq!(
+                verify_bytes(electronic.as_bytes()),
+                RfCreditorReference::parse_str(&electronic).map(|_| ()),
+                "{}",
+                ir
+            );
+        }
+        for r in [
+            "RF17539007547034",
+            "RF1A539007547034",
+            "RF+1539007547034",
+            "RF18",
+        ] {
+            assert_eq!(
+                verify_bytes(r.as_bytes()),
+                RfCreditorReference::parse_str(r).map(|_| ()),
+                "{}",
+                r
+            );
+        }
+        assert!(verify_bytes(b"RF18 5390 0754 7034").is_err());
+    }
+
     #[test]
     fn gen_check_digits_test() {
         assert_eq!(
             RfCreditorReference::gen_check_digits(VALID_REFS[0]).unwrap(),
-            vec![5, 3, 9, 0, 0, 7, 5, 4, 7, 0, 3, 4, 2, 7, 1, 5, 1, 8]
+            1
         );
         assert_eq!(
             RfCreditorReference::gen_check_digits(VALID_REFS[1]).unwrap(),
-            vec![2, 3, 4, 8, 2, 3, 1, 2, 7, 1, 5, 7, 1]
+            1
         );
 
+        // 1011271518 % 97
         let r = "RF18AB";
-        assert_eq!(
-            RfCreditorReference::gen_check_digits(r).unwrap(),
-            vec![1, 0, 1, 1, 2, 7, 1, 5, 1, 8]
-        );
+        assert_eq!(RfCreditorReference::gen_check_digits(r).unwrap(), 55);
+
+        assert!(matches!(
+            RfCreditorReference::gen_check_digits("RF18AB_C"),
+            Err(ParseError::InvalidCharacter {
+                character: '_',
+                position: 6,
+                ..
+            })
+        ));
+        assert!(matches!(
+            RfCreditorReference::gen_check_digits("RF1_ABC"),
+            Err(ParseError::InvalidCharacter {
+                character: '_',
+                position: 3,
+                ..
+            })
+        ));
     }
 
     #[test]
@@ -474,47 +1410,21 @@ mod tests {
         let nr = u8::try_from(nr).unwrap();
         assert_eq!(nr, 71);
 
-        assert_eq!(
-            RfCreditorReference::gen_check_digits(GEN_REFS[0]).unwrap(),
-            vec![5, 3, 9, 0, 0, 7, 5, 4, 7, 0, 3, 4, 2, 7, 1, 5, 0, 0]
-        );
-
-        assert_eq!(
-            RfCreditorReference::gen_checksum(&[2i8, 3, 4, 8, 2, 3, 1, 2, 7, 1, 5, 0, 0]),
-            (71, ['7', '1'])
-        );
-
         assert_eq!(
             RfCreditorReference::gen_check_digits(GEN_REFS[1]).unwrap(),
-            vec![2, 3, 4, 8, 2, 3, 1, 2, 7, 1, 5, 0, 0]
-        );
-
-        assert_eq!(
-            RfCreditorReference::gen_checksum(&[
-                5, 3, 9, 0, 0, 7, 5, 4, 7, 0, 3, 4, 2, 7, 1, 5, 0, 0
-            ]),
-            (18, ['1', '8'])
+            27
         );
+        assert_eq!(RfCreditorReference::gen_checksum(27), (71, ['7', '1']));
 
         assert_eq!(
-            RfCreditorReference::gen_checksum(
-                &RfCreditorReference::gen_check_digits(GEN_REFS[0]).unwrap()
-            )
-            .0,
-            18
-        );
-
-        assert_eq!(
-            RfCreditorReference::gen_checksum(
-                &RfCreditorReference::gen_check_digits(GEN_REFS[1]).unwrap()
-            )
-            .0,
-            71
+            RfCreditorReference::gen_check_digits(GEN_REFS[0]).unwrap(),
+            80
         );
+        assert_eq!(RfCreditorReference::gen_checksum(80), (18, ['1', '8']));
 
         assert_eq!(
             RfCreditorReference::gen_checksum(
-                &RfCreditorReference::gen_check_digits(&RfCreditorReference::convert_electronic(
+                RfCreditorReference::gen_check_digits(&RfCreditorReference::convert_electronic(
                     GEN_REFS[4]
                 ))
                 .unwrap()
@@ -525,11 +1435,25 @@ mod tests {
 
         assert_eq!(
             RfCreditorReference::gen_checksum(
-                &RfCreditorReference::gen_check_digits(GEN_REFS[5]).unwrap()
+                RfCreditorReference::gen_check_digits(GEN_REFS[5]).unwrap()
             )
             .0,
             93
         );
+        assert_eq!(RfCreditorReference::gen_checksum(96), (2, ['0', '2']));
+    }
+
+    #[test]
+    fn mod97_test() {
+        // all-letter body of maximum length expands to 48 digits
+        let body = "Z".repeat(MAX_BODY_LEN);
+        let rf = RfCreditorReference::new(&body);
+        assert_eq!(rf.checksum, compute_checksum(&body).unwrap());
+        assert!(is_valid(rf.as_electronic_str()));
+        assert!(RfCreditorReference::parse_str(rf.as_electronic_str()).is_ok());
+
+        let remainder = RfCreditorReference::gen_check_digits(&format!("RF00{}", body)).unwrap();
+        assert_eq!(RfCreditorReference::gen_checksum(remainder).0, rf.checksum);
     }
 
     #[test]
@@ -547,6 +1471,351 @@ mod tests {
         }
     }
 
+    #[test]
+    fn error_position_test() {
+        assert_eq!(
+            RfCreditorReference::parse_str(" RF18 5390_0754 7034"),
+            Err(ParseError::InvalidCharacter {
+                input: " RF18 5390_0754 7034".to_string(),
+                character: '_',
+                position: 10,
+            })
+        );
+        assert_eq!(
+            RfCreditorReference::parse_str("RF18539007547034älsö"),
+            Err(ParseError::InvalidCharacter {
+                input: "RF18539007547034älsö".to_string(),
+                character: 'ä',
+                position: 16,
+            })
+        );
+        assert_eq!(
+            RfCreditorReference::parse_str("RF18539007547034928TOOLONG"),
+            Err(ParseError::TooLong {
+                input: "RF18539007547034928TOOLONG".to_string(),
+                len: 26,
+                max: 25,
+            })
+        );
+    }
+
+    #[test]
+    fn length_error_test() {
+        assert_eq!(
+            RfCreditorReference::parse_str("RF18"),
+            Err(ParseError::TooShort {
+                input: "RF18".to_string(),
+                len: 4,
+                min: 5,
+            })
+        );
+        assert_eq!(
+            RfCreditorReference::try_new(""),
+            Err(ParseError::TooShort {
+                input: "RF00".to_string(),
+                len: 4,
+                min: 5,
+            })
+        );
+        assert_eq!(
+            RfCreditorReference::try_new("5390075470349283012345"),
+            Err(ParseError::BodyTooLong {
+                input: "5390075470349283012345".to_string(),
+                len: 22,
+                max: 21,
+            })
+        );
+
+        // boundary with and without prefix
+        let body = "539007547034928301234";
+        assert!(RfCreditorReference::try_new(body).is_ok());
+        assert!(RfCreditorReference::try_new(&format!("RF{}", body)).is_ok());
+        assert!(RfCreditorReference::try_new(&format!("RF00{}", body)).is_ok());
+        for reference in [
+            format!("{}5", body),
+            format!("RF{}5", body),
+            format!("RF00{}5", body),
+        ] {
+            assert!(matches!(
+                RfCreditorReference::try_new(&reference),
+                Err(ParseError::BodyTooLong {
+                    len: 22,
+                    max: 21,
+                    ..
+                })
+            ));
+        }
+    }
+
+    #[test]
+    fn checksum_error_test() {
+        assert_eq!(
+            RfCreditorReference::parse_str("RF17539007547034"),
+            Err(ParseError::InvalidChecksum {
+                input: "RF17539007547034".to_string(),
+                expected: 18,
+                found: 17,
+            })
+        );
+        assert_eq!(
+            RfCreditorReference::parse_str("RF1A539007547034"),
+            Err(ParseError::InvalidCheckDigits {
+                input: "RF1A539007547034".to_string(),
+                source: "1A".parse::<u8>().unwrap_err(),
+            })
+        );
+
+        let err = RfCreditorReference::parse_str("RF1A539007547034").unwrap_err();
+        assert_eq!(
+            std::error::Error::source(&err).map(ToString::to_string),
+            Some("1A".parse::<u8>().unwrap_err().to_string())
+        );
+        assert!(std::error::Error::source(
+            &RfCreditorReference::parse_str("RF17539007547034").unwrap_err()
+        )
+        .is_none());
+    }
+
+    #[test]
+    fn validate_all_test() {
+        for vr in VALID_REFS {
+            assert_eq!(RfCreditorReference::validate_all(vr), Ok(()));
+        }
+        for ir in INVALID_REFS {
+            let errors = RfCreditorReference::validate_all(ir).unwrap_err();
+            assert_eq!(
+                errors[0],
+                RfCreditorReference::parse_str(ir).unwrap_err(),
+                "first error should be same as parse_str(): {}",
+                ir
+            );
+        }
+
+        let errors =
+            RfCreditorReference::validate_all("XF18 5390_0754@7034 TOOLONGER").unwrap_err();
+        assert_eq!(errors.len(), 4);
+        assert!(matches!(errors[0], ParseError::TooLong { len: 27, .. }));
+        assert!(matches!(errors[1], ParseError::InvalidIdentifier { .. }));
+        assert!(matches!(
+            errors[2],
+            ParseError::InvalidCharacter {
+                character: '_',
+                position: 9,
+                ..
+            }
+        ));
+        assert!(matches!(
+            errors[3],
+            ParseError::InvalidCharacter {
+                character: '@',
+                position: 14,
+                ..
+            }
+        ));
+
+        assert_eq!(
+            RfCreditorReference::validate_all("RF19 5390 0754 7034"),
+            Err(vec![ParseError::InvalidChecksum {
+                input: "RF19 5390 0754 7034".to_string(),
+                expected: 18,
+                found: 19,
+            }])
+        );
+    }
+
+    #[test]
+    fn to_grouped_string_test() {
+        let rf = RfCreditorReference::parse_str("RF93539007547034928301234").unwrap();
+        assert_eq!(rf.to_grouped_string(4), rf.to_string());
+        assert_eq!(rf.to_grouped_string(0), rf.to_electronic_string());
+        assert_eq!(rf.to_grouped_string(25), rf.to_electronic_string());
+        assert_eq!(rf.to_grouped_string(5), "RF935 39007 54703 49283 01234");
+        assert_eq!(rf.to_grouped_string(1).len(), 49);
+    }
+
+    #[test]
+    fn write_test() {
+        let rf = RfCreditorReference::parse_str("RF93539007547034928301234").unwrap();
+        let mut buf = String::from("ref: ");
+        rf.write_print(&mut buf).unwrap();
+        buf.push_str(", ");
+        rf.write_electronic(&mut buf).unwrap();
+        assert_eq!(
+            buf,
+            "ref: RF93 5390 0754 7034 9283 0123 4, RF93539007547034928301234"
+        );
+    }
+
+    #[test]
+    fn to_uppercase_reference_test() {
+        let rf = RfCreditorReference::parse_str("RF63abcd0754efgh").unwrap();
+        let upper = rf.to_uppercase_reference();
+        assert_eq!(upper.to_string(), "RF63 ABCD 0754 EFGH");
+        assert_eq!(
+            upper,
+            RfCreditorReference::parse_str("RF63 ABCD 0754 EFGH").unwrap()
+        );
+        assert_eq!(upper.to_uppercase_reference(), upper);
+    }
+
+    #[test]
+    fn to_paper_string_test() {
+        for reference in [
+            "RF63abcd0754efgh",
+            "RF63 ABCD 0754 efgh",
+            "RF63AB CD07 54EF GH",
+        ] {
+            let rf = RfCreditorReference::parse_str(reference).unwrap();
+            assert_eq!(rf.to_paper_string(), "RF63 ABCD 0754 EFGH");
+        }
+        let rf = RfCreditorReference::parse_str("RF93539007547034928301234").unwrap();
+        assert_eq!(rf.to_paper_string(), "RF93 5390 0754 7034 9283 0123 4");
+    }
+
+    #[test]
+    fn to_padded_string_test() {
+        use std::fmt::Alignment;
+
+        let rf = RfCreditorReference::new("539007547034");
+        assert_eq!(
+            rf.to_padded_string(25, ' ', Alignment::Left),
+            "RF18539007547034         "
+        );
+        assert_eq!(
+            rf.to_padded_string(25, '0', Alignment::Right),
+            "000000000RF18539007547034"
+        );
+        assert_eq!(
+            rf.to_padded_string(21, '_', Alignment::Center),
+            "__RF18539007547034___"
+        );
+        assert_eq!(
+            rf.to_padded_string(10, ' ', Alignment::Left),
+            "RF18539007547034"
+        );
+
+        assert_eq!(format!("{:>21}", rf), "  RF18 5390 0754 7034");
+        assert_eq!(format!("{:#<20}", rf), "RF18 5390 0754 7034#");
+        assert_eq!(format!("{:#25}", rf), "RF18539007547034         ");
+        assert_eq!(format!("{:5}", rf), "RF18 5390 0754 7034");
+    }
+
+    #[test]
+    fn groups_test() {
+        let rf = RfCreditorReference::parse_str("RF93539007547034928301234").unwrap();
+        assert_eq!(
+            rf.groups().collect::<Vec<_>>(),
+            ["RF93", "5390", "0754", "7034", "9283", "0123", "4"]
+        );
+        let rf = RfCreditorReference::parse_str("RF71 2348 231").unwrap();
+        assert_eq!(rf.groups().collect::<Vec<_>>(), ["RF71", "2348", "231"]);
+    }
+
+    #[test]
+    fn electronic_chars_test() {
+        let rf = RfCreditorReference::parse_str("RF63 ABCD 0754 efgh").unwrap();
+        assert_eq!(
+            rf.electronic_chars().collect::<String>(),
+            rf.to_electronic_string()
+        );
+        assert_eq!(rf.electronic_chars().count(), 16);
+
+        // re-verification of the checksum without allocation
+        let mut chars = rf.electronic_chars();
+        let head = [chars.next().unwrap(), chars.next().unwrap()];
+        let check = [chars.next().unwrap(), chars.next().unwrap()];
+        let remainder = chars
+            .chain(head)
+            .try_fold(0, Mod97_10::push)
+            .and_then(|remainder| {
+                check
+                    .iter()
+                    .try_fold(remainder, |r, &c| Mod97_10::push_check(r, c))
+            });
+        assert_eq!(remainder, Some(1));
+    }
+
+    #[test]
+    fn to_separated_string_test() {
+        let rf = RfCreditorReference::parse_str("RF93539007547034928301234").unwrap();
+        assert_eq!(rf.to_separated_string(" "), rf.to_string());
+        assert_eq!(rf.to_separated_string(""), rf.to_electronic_string());
+        assert_eq!(
+            rf.to_separated_string("-"),
+            "RF93-5390-0754-7034-9283-0123-4"
+        );
+        assert_eq!(
+            ParseOptions::new()
+                .strip_separators(&['-'])
+                .parse(&rf.to_separated_string("-")),
+            Ok(rf)
+        );
+    }
+
+    #[test]
+    fn as_electronic_str_test() {
+        let rf = RfCreditorReference::parse_str("RF63 abcd 0754 EFGH").unwrap();
+        assert_eq!(rf.as_electronic_str(), "RF63abcd0754EFGH");
+        assert_eq!(rf.as_electronic_str(), rf.to_electronic_string());
+        assert_eq!(
+            rf.to_uppercase_reference().as_electronic_str(),
+            "RF63ABCD0754EFGH"
+        );
+        assert_eq!(
+            RfCreditorReference::new("539007547034").as_electronic_str(),
+            "RF18539007547034"
+        );
+
+        assert_eq!(rf.as_electronic_cow(), "RF63abcd0754EFGH");
+        let cow: Cow<str> = (&rf).into();
+        assert!(matches!(cow, Cow::Borrowed("RF63 abcd 0754 EFGH")));
+        assert_eq!(cow, String::from(&rf));
+        let cow: Cow<str> = rf.into();
+        assert_eq!(cow, "RF63 abcd 0754 EFGH");
+    }
+
+    #[test]
+    fn integer_test() {
+        for n in [0, 1, 42, 539_007_547_034, u64::MAX] {
+            let rf = RfCreditorReference::from_u64(n);
+            assert!(is_valid(rf.as_electronic_str()));
+            assert_eq!(rf.to_u128(), Some(u128::from(n)));
+            assert_eq!(RfCreditorReference::from_u128(u128::from(n)), Ok(rf));
+        }
+
+        let n = 999_999_999_999_999_999_999;
+        assert_eq!(
+            RfCreditorReference::from_u128(n).unwrap().to_u128(),
+            Some(n)
+        );
+        assert!(matches!(
+            RfCreditorReference::from_u128(n + 1),
+            Err(ParseError::BodyTooLong { len: 22, .. })
+        ));
+
+        assert_eq!(RfCreditorReference::new("000042").to_u128(), Some(42));
+        assert_eq!(RfCreditorReference::new("42A").to_u128(), None);
+    }
+
+    #[test]
+    fn display_test() {
+        for vr in VALID_REFS {
+            let rf = RfCreditorReference::parse_str(vr).unwrap();
+            assert_eq!(format!("{}", rf), String::from(&rf));
+            assert_eq!(format!("{:#}", rf), rf.to_electronic_string());
+        }
+    }
+
+    #[test]
+    fn hash_test() {
+        let references = VALID_REFS
+            .iter()
+            .map(|vr| RfCreditorReference::parse_str(vr).unwrap())
+            .collect::<std::collections::HashSet<_>>();
+        assert_eq!(references.len(), 4);
+        assert!(references.contains(&RfCreditorReference::new("539007547034")));
+    }
+
     #[test]
     fn from_str_test() {
         for vr in VALID_REFS {
@@ -558,10 +1827,13 @@ mod tests {
             match RfCreditorReference::from_str(ir) {
                 Ok(_) => panic!("should not be valid: {}", ir),
                 Err(err) => match err {
-                    ParseError::InvalidCharacter(_) => {}
-                    ParseError::InvalidChecksum(_) => {}
-                    ParseError::InvalidFormat(_) => {}
-                    ParseError::InvalidIdentifier(_) => {}
+                    ParseError::BodyTooLong { .. } => {}
+                    ParseError::InvalidCharacter { .. } => {}
+                    ParseError::InvalidCheckDigits { .. } => {}
+                    ParseError::InvalidChecksum { .. } => {}
+                    ParseError::InvalidIdentifier { .. } => {}
+                    ParseError::TooLong { .. } => {}
+                    ParseError::TooShort { .. } => {}
                 },
             }
         }
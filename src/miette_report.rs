@@ -0,0 +1,176 @@
+//! [`miette::Diagnostic`] implementation for [`ParseError`]
+//!
+//! Available with feature `miette`.
+
+use std::fmt::Display;
+
+use miette::{Diagnostic, LabeledSpan, SourceCode};
+
+use crate::{ParseError, IDENTIFIER};
+
+impl Diagnostic for ParseError {
+    fn code<'a>(&'a self) -> Option<Box<dyn Display + 'a>> {
+        Some(Box::new(ParseError::code(self)))
+    }
+
+    fn help<'a>(&'a self) -> Option<Box<dyn Display + 'a>> {
+        let help = match self {
+            ParseError::BodyTooLong { .. } => "the reference body has at most 21 characters",
+            ParseError::InvalidCharacter { .. } => "valid characters are 0-9, A-Z and a-z",
+            ParseError::InvalidCheckDigits { .. } => "check digits must be 2 digits 0-9",
+            ParseError::InvalidChecksum { .. } => {
+                "check the reference for typos or transposed characters"
+            }
+            ParseError::InvalidIdentifier { .. } => "creditor references start with RF",
+            ParseError::TooLong { .. } | ParseError::TooShort { .. } => {
+                "creditor references have 5 to 25 characters without spaces"
+            }
+        };
+        Some(Box::new(help))
+    }
+
+    fn source_code(&self) -> Option<&dyn SourceCode> {
+        match self {
+            ParseError::BodyTooLong { input, .. }
+            | ParseError::InvalidCharacter { input, .. }
+            | ParseError::InvalidCheckDigits { input, .. }
+            | ParseError::InvalidChecksum { input, .. }
+            | ParseError::InvalidIdentifier { input }
+            | ParseError::TooLong { input, .. }
+            | ParseError::TooShort { input, .. } => Some(input),
+        }
+    }
+
+    fn labels(&self) -> Option<Box<dyn Iterator<Item = LabeledSpan> + '_>> {
+        let input = self.input();
+        let label = match self {
+            ParseError::BodyTooLong { len, max, .. } => {
+                let end = input.chars().filter(|c| c.is_alphanumeric()).count();
+                electronic_span(
+                    input,
+                    end.saturating_sub(len.saturating_sub(*max)),
+                    end,
+                    format!("exceeds {} characters", max),
+                )
+            }
+            ParseError::InvalidCharacter {
+                character,
+                position,
+                ..
+            } => {
+                let offset = input
+                    .char_indices()
+                    .nth(*position)
+                    .map_or(input.len(), |(offset, _)| offset);
+                LabeledSpan::new(
+                    Some(format!("invalid character '{}'", character)),
+                    offset,
+                    character.len_utf8(),
+                )
+            }
+            ParseError::InvalidCheckDigits { .. } => {
+                electronic_span(input, 2, 4, "not numeric".to_string())
+            }
+            ParseError::InvalidChecksum { expected, .. } => {
+                electronic_span(input, 2, 4, format!("expected {:02}", expected))
+            }
+            ParseError::InvalidIdentifier { .. } => {
+                electronic_span(input, 0, 2, format!("expected {}", IDENTIFIER))
+            }
+            ParseError::TooLong { len, max, .. } => {
+                electronic_span(input, *max, *len, format!("exceeds {} characters", max))
+            }
+            ParseError::TooShort { len, min, .. } => electronic_span(
+                input,
+                0,
+                *len,
+                format!("{} of at least {} characters", len, min),
+            ),
+        };
+        Some(Box::new(std::iter::once(label)))
+    }
+}
+
+/// Creates a [`LabeledSpan`] over the chars `start..end` of `input`
+/// in electronic format
+///
+/// Spaces and the separators of [`crate::ParseOptions`] are skipped like
+/// any other char, which is not alphanumeric.
+fn electronic_span(input: &str, start: usize, end: usize, label: String) -> LabeledSpan {
+    let mut offsets = input
+        .char_indices()
+        .filter(|&(_, c)| c.is_alphanumeric())
+        .map(|(offset, c)| (offset, offset + c.len_utf8()))
+        .skip(start)
+        .take(end.saturating_sub(start));
+
+    let (offset, mut span_end) = offsets.next().unwrap_or((input.len(), input.len()));
+    if let Some((_, last_end)) = offsets.last() {
+        span_end = last_end;
+    }
+
+    LabeledSpan::new(Some(label), offset, span_end - offset)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ParseOptions, RfCreditorReference};
+
+    fn label_span(err: &ParseError) -> (usize, usize) {
+        let label = err.labels().unwrap().next().unwrap();
+        (label.offset(), label.len())
+    }
+
+    #[test]
+    fn diagnostic_test() {
+        let err = RfCreditorReference::parse_str("RF18 5390_0754 7034").unwrap_err();
+        assert_eq!(
+            Diagnostic::code(&err).unwrap().to_string(),
+            "ISO11649-E001-INVALID-CHAR"
+        );
+        assert!(err.help().is_some());
+        assert!(err.source_code().is_some());
+        assert_eq!(label_span(&err), (9, 1));
+
+        let err = RfCreditorReference::parse_str("RF18539007547034älsö").unwrap_err();
+        assert_eq!(label_span(&err), (16, 2));
+
+        let err = RfCreditorReference::parse_str(" RF19 5390 0754 7034").unwrap_err();
+        assert_eq!(label_span(&err), (3, 2));
+
+        let err = RfCreditorReference::parse_str("XF18 5390 0754 7034").unwrap_err();
+        assert_eq!(label_span(&err), (0, 2));
+
+        let err = RfCreditorReference::parse_str("RF18539007547034928TOOLONG").unwrap_err();
+        assert_eq!(label_span(&err), (25, 1));
+
+        let err = RfCreditorReference::try_new("5390 0754 7034 9283 0123 45").unwrap_err();
+        assert_eq!(label_span(&err), (26, 1));
+    }
+
+    #[test]
+    fn separators_test() {
+        let err = ParseOptions::new()
+            .strip_separators(&['-'])
+            .parse("RF19-5390-0754-7034")
+            .unwrap_err();
+        assert_eq!(label_span(&err), (2, 2));
+
+        let err = ParseOptions::new()
+            .strip_separators(&['-'])
+            .parse("RF18-5390-0754-7034-9283-0123-4567")
+            .unwrap_err();
+        assert_eq!(label_span(&err), (31, 3));
+    }
+
+    #[test]
+    fn body_too_long_test() {
+        let err = ParseError::BodyTooLong {
+            input: "RF18".to_string(),
+            len: usize::MAX,
+            max: 21,
+        };
+        assert_eq!(label_span(&err), (0, 4));
+    }
+}
@@ -0,0 +1,215 @@
+//! Deterministic fake data of creditor references
+//!
+//! Available with feature `mock`.
+//!
+//! [`MockReferences`] produces a reproducible mix of valid references,
+//! near-misses and garbage strings for fixture files of integration tests.
+//! The same seed always results in the same sequence.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use iso_11649::mock::{MockKind, MockReferences};
+//!
+//! let fixtures = MockReferences::seeded(42).take(100).collect::<Vec<_>>();
+//! assert_eq!(fixtures, MockReferences::seeded(42).take(100).collect::<Vec<_>>());
+//!
+//! for (kind, reference) in fixtures {
+//!     assert_eq!(iso_11649::is_valid(&reference), kind == MockKind::Valid);
+//! }
+//! ```
+
+use crate::{is_valid, RfCreditorReference, RfReferenceBuilder, MAX_BODY_LEN};
+
+/// Percentage of valid references in the mix
+const PERCENT_VALID: usize = 60;
+/// Percentage of near-misses in the mix
+const PERCENT_NEAR_MISS: usize = 25;
+/// Characters of bodies, digits followed by uppercase letters
+const CHARS: &[u8] = b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZ";
+/// Characters of garbage strings
+const GARBAGE_CHARS: &[u8] =
+    b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz -_./#@";
+/// Maximum length of garbage strings
+const GARBAGE_MAX_LEN: usize = 30;
+
+/// `MockKind` is the kind of a generated string
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum MockKind {
+    /// Valid reference in electronic or print format
+    Valid,
+    /// Reference with check digits off by one
+    NearMiss,
+    /// String which is no valid reference
+    Garbage,
+}
+
+/// `MockReferences` generates deterministic fake data of creditor
+/// references
+///
+/// As [`Iterator`] it endlessly yields strings of a mix of 60 % valid
+/// references, 25 % near-misses and 15 % garbage together with their
+/// [`MockKind`].
+///
+/// The sequence is stable for a seed within a major version, but not
+/// cryptographically secure.
+#[derive(Clone, Debug)]
+pub struct MockReferences {
+    /// State of the pseudo-random number generator
+    state: u64,
+}
+
+impl MockReferences {
+    /// Creates [`MockReferences`] generating the sequence of `seed`
+    #[must_use]
+    pub fn seeded(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    /// Generates a valid [`RfCreditorReference`]
+    ///
+    /// Bodies are digits with an optional prefix of up to 3 uppercase
+    /// letters, like invoice or customer numbers.
+    ///
+    /// # Panics
+    ///
+    /// Bodies of valid characters and length always build.
+    ///
+    #[must_use]
+    pub fn valid(&mut self) -> RfCreditorReference<'static> {
+        let len = 1 + self.below(MAX_BODY_LEN);
+        let letters = self.below(4).min(len.saturating_sub(1));
+
+        let body = (0..len)
+            .map(|i| {
+                if i < letters {
+                    char::from(CHARS[10 + self.below(26)])
+                } else {
+                    char::from(CHARS[self.below(10)])
+                }
+            })
+            .collect::<String>();
+
+        RfReferenceBuilder::new()
+            .segment(body)
+            .build()
+            .expect("generated body is valid")
+    }
+
+    /// Generates a reference in electronic format with check digits off
+    /// by one
+    #[must_use]
+    pub fn near_miss(&mut self) -> String {
+        let rf = self.valid();
+        let electronic = rf.as_electronic_str();
+        let checksum = electronic[2..4].parse::<u8>().unwrap_or_default();
+        let checksum = if checksum < 98 {
+            checksum + 1
+        } else {
+            checksum - 1
+        };
+
+        format!("{}{:02}{}", &electronic[..2], checksum, &electronic[4..])
+    }
+
+    /// Generates a string which is no valid reference
+    ///
+    /// Half of the strings start with `RF` to resemble references.
+    #[must_use]
+    pub fn garbage(&mut self) -> String {
+        loop {
+            let mut garbage = if self.below(2) == 0 {
+                String::from("RF")
+            } else {
+                String::new()
+            };
+            for _ in 0..self.below(GARBAGE_MAX_LEN + 1) {
+                garbage.push(char::from(GARBAGE_CHARS[self.below(GARBAGE_CHARS.len())]));
+            }
+
+            if !is_valid(&garbage) {
+                return garbage;
+            }
+        }
+    }
+
+    /// Next pseudo-random number of `SplitMix64`
+    fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// Next pseudo-random number below `n`
+    #[allow(clippy::cast_possible_truncation)]
+    fn below(&mut self, n: usize) -> usize {
+        (self.next_u64() % n as u64) as usize
+    }
+}
+
+impl Iterator for MockReferences {
+    type Item = (MockKind, String);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let percent = self.below(100);
+        let item = if percent < PERCENT_VALID {
+            let rf = self.valid();
+            let reference = if self.below(2) == 0 {
+                rf.to_electronic_string()
+            } else {
+                rf.to_string()
+            };
+            (MockKind::Valid, reference)
+        } else if percent < PERCENT_VALID + PERCENT_NEAR_MISS {
+            (MockKind::NearMiss, self.near_miss())
+        } else {
+            (MockKind::Garbage, self.garbage())
+        };
+        Some(item)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn seeded_test() {
+        let first = MockReferences::seeded(1).take(50).collect::<Vec<_>>();
+        assert_eq!(
+            first,
+            MockReferences::seeded(1).take(50).collect::<Vec<_>>()
+        );
+        assert_ne!(
+            first,
+            MockReferences::seeded(2).take(50).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn mix_test() {
+        let mut counts = [0; 3];
+        for (kind, reference) in MockReferences::seeded(0).take(1000) {
+            match kind {
+                MockKind::Valid => {
+                    counts[0] += 1;
+                    assert!(RfCreditorReference::parse_str(&reference).is_ok());
+                }
+                MockKind::NearMiss => {
+                    counts[1] += 1;
+                    assert!(matches!(
+                        RfCreditorReference::parse_str(&reference),
+                        Err(crate::ParseError::InvalidChecksum { .. })
+                    ));
+                }
+                MockKind::Garbage => {
+                    counts[2] += 1;
+                    assert!(!is_valid(&reference));
+                }
+            }
+        }
+        assert!(counts.iter().all(|&count| count > 100), "{:?}", counts);
+    }
+}
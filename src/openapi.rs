@@ -0,0 +1,305 @@
+//! Documentation of [`RfCreditorReference`](crate::RfCreditorReference)s
+//! in generated `OpenAPI` definitions
+//!
+//! References are documented as strings in canonical electronic format
+//! with format `iso11649`, the [`PATTERN`] of [`crate::sql`], a length of
+//! [`crate::MIN_LEN`] to [`crate::MAX_LEN`] characters and an example.
+//!
+//! - feature `utoipa`: `ToSchema` for body fields and `IntoParams` for
+//!   path and query parameters named `reference` with
+//!   [utoipa](https://docs.rs/utoipa)
+//! - feature `poem-openapi`: `Type`, `ParseFromJSON`, `ToJSON` and
+//!   `ParseFromParameter` for request objects and parameters of
+//!   [Poem OpenAPI](https://docs.rs/poem-openapi), invalid references
+//!   result in responses `400 Bad Request` with messages ending in the
+//!   [`ParseError`] message
+//! - feature `okapi`: `JsonSchema` of the schemars version of
+//!   [okapi](https://docs.rs/okapi), so `rocket_okapi` documents body
+//!   fields and, together with feature `rocket`, path parameters
+//!
+//! [`ParseError`]: crate::ParseError
+
+use crate::sql::PATTERN;
+
+/// Format of references in schemas
+const FORMAT: &str = "iso11649";
+
+/// Description of references in schemas
+const DESCRIPTION: &str = "ISO 11649 creditor reference in electronic format";
+
+/// Example of references in schemas
+const EXAMPLE: &str = "RF18539007547034";
+
+#[cfg(feature = "okapi")]
+mod okapi {
+    use ::okapi::schemars::{
+        gen::SchemaGenerator,
+        schema::{InstanceType, Metadata, Schema, SchemaObject, StringValidation},
+        JsonSchema,
+    };
+
+    use super::{DESCRIPTION, EXAMPLE, FORMAT, PATTERN};
+    use crate::{RfCreditorReference, MAX_LEN, MIN_LEN};
+
+    impl JsonSchema for RfCreditorReference<'_> {
+        fn schema_name() -> String {
+            "RfCreditorReference".to_string()
+        }
+
+        fn json_schema(_gen: &mut SchemaGenerator) -> Schema {
+            Schema::Object(SchemaObject {
+                instance_type: Some(InstanceType::String.into()),
+                format: Some(FORMAT.to_string()),
+                metadata: Some(Box::new(Metadata {
+                    description: Some(DESCRIPTION.to_string()),
+                    examples: vec![EXAMPLE.into()],
+                    ..Metadata::default()
+                })),
+                string: Some(Box::new(StringValidation {
+                    max_length: u32::try_from(MAX_LEN).ok(),
+                    min_length: u32::try_from(MIN_LEN).ok(),
+                    pattern: Some(PATTERN.to_string()),
+                })),
+                ..SchemaObject::default()
+            })
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use ::okapi::schemars::schema_for;
+
+        use super::*;
+
+        #[test]
+        fn json_schema_test() {
+            assert_eq!(RfCreditorReference::schema_name(), "RfCreditorReference");
+
+            let schema = schema_for!(RfCreditorReference).schema;
+            assert_eq!(schema.format.as_deref(), Some("iso11649"));
+            let string = schema.string.unwrap();
+            assert_eq!(string.pattern.as_deref(), Some(PATTERN));
+            assert_eq!(string.min_length, Some(5));
+            assert_eq!(string.max_length, Some(25));
+            assert!(schema
+                .metadata
+                .unwrap()
+                .examples
+                .iter()
+                .all(|example| example.as_str().map_or(false, crate::is_valid)));
+        }
+    }
+}
+
+#[cfg(feature = "poem-openapi")]
+mod poem_openapi {
+    use std::borrow::Cow;
+
+    use ::poem_openapi::{
+        registry::{MetaSchema, MetaSchemaRef},
+        types::{ParseFromJSON, ParseFromParameter, ParseResult, ToJSON, Type},
+    };
+    use ::serde_json::Value;
+
+    use super::{DESCRIPTION, EXAMPLE, FORMAT, PATTERN};
+    use crate::{RfCreditorReference, MAX_LEN, MIN_LEN};
+
+    impl Type for RfCreditorReference<'static> {
+        const IS_REQUIRED: bool = true;
+
+        type RawValueType = Self;
+
+        type RawElementValueType = Self;
+
+        fn name() -> Cow<'static, str> {
+            Cow::Borrowed("string(iso11649)")
+        }
+
+        fn schema_ref() -> MetaSchemaRef {
+            MetaSchemaRef::Inline(Box::new(MetaSchema {
+                description: Some(DESCRIPTION),
+                example: Some(Value::from(EXAMPLE)),
+                pattern: Some(PATTERN.to_string()),
+                min_length: Some(MIN_LEN),
+                max_length: Some(MAX_LEN),
+                ..MetaSchema::new_with_format("string", FORMAT)
+            }))
+        }
+
+        fn as_raw_value(&self) -> Option<&Self::RawValueType> {
+            Some(self)
+        }
+
+        fn raw_element_iter<'a>(
+            &'a self,
+        ) -> Box<dyn Iterator<Item = &'a Self::RawElementValueType> + 'a> {
+            Box::new(self.as_raw_value().into_iter())
+        }
+    }
+
+    /// Validates strings like [`RfCreditorReference::parse_str`]
+    impl ParseFromJSON for RfCreditorReference<'static> {
+        fn parse_from_json(value: Option<Value>) -> ParseResult<Self> {
+            match value.unwrap_or_default() {
+                Value::String(reference) => RfCreditorReference::parse_str(&reference)
+                    .map_err(::poem_openapi::types::ParseError::custom),
+                value => Err(::poem_openapi::types::ParseError::expected_type(value)),
+            }
+        }
+    }
+
+    /// Validates like [`RfCreditorReference::parse_str`]
+    impl ParseFromParameter for RfCreditorReference<'static> {
+        fn parse_from_parameter(value: &str) -> ParseResult<Self> {
+            RfCreditorReference::parse_str(value).map_err(::poem_openapi::types::ParseError::custom)
+        }
+    }
+
+    /// Keeps the reference in electronic format.
+    impl ToJSON for RfCreditorReference<'static> {
+        fn to_json(&self) -> Option<Value> {
+            Some(Value::String(self.to_electronic_string()))
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[test]
+        fn type_test() {
+            assert_eq!(RfCreditorReference::name(), "string(iso11649)");
+            let schema = match RfCreditorReference::schema_ref() {
+                MetaSchemaRef::Inline(schema) => schema,
+                MetaSchemaRef::Reference(_) => panic!("expected inline schema"),
+            };
+            assert_eq!(schema.ty, "string");
+            assert_eq!(schema.format, Some("iso11649"));
+            assert_eq!(schema.pattern.as_deref(), Some(PATTERN));
+            assert_eq!(schema.max_length, Some(25));
+        }
+
+        #[test]
+        fn json_test() {
+            let rf =
+                RfCreditorReference::parse_from_json(Some(Value::from("RF71 2348 231"))).unwrap();
+            assert_eq!(rf, RfCreditorReference::new("2348231"));
+            assert_eq!(rf.to_json(), Some(Value::from("RF712348231")));
+
+            let err = RfCreditorReference::parse_from_json(Some(Value::from("RF70 2348 231")))
+                .unwrap_err();
+            assert!(err
+                .message()
+                .starts_with("failed to parse \"string(iso11649)\": "));
+            assert!(err.message().ends_with(
+                &RfCreditorReference::parse_str("RF70 2348 231")
+                    .unwrap_err()
+                    .to_string()
+            ));
+            assert!(RfCreditorReference::parse_from_json(Some(Value::from(4711))).is_err());
+            assert!(RfCreditorReference::parse_from_json(None).is_err());
+        }
+
+        #[test]
+        fn parameter_test() {
+            assert_eq!(
+                RfCreditorReference::parse_from_parameter("RF712348231").unwrap(),
+                RfCreditorReference::new("2348231")
+            );
+            assert!(RfCreditorReference::parse_from_parameter("RF712348232").is_err());
+        }
+    }
+}
+
+#[cfg(feature = "utoipa")]
+mod utoipa {
+    use std::borrow::Cow;
+
+    use ::utoipa::{
+        openapi::{
+            path::{Parameter, ParameterBuilder, ParameterIn},
+            schema::{ObjectBuilder, Schema, SchemaFormat, Type},
+            RefOr, Required,
+        },
+        IntoParams, PartialSchema, ToSchema,
+    };
+
+    use super::{DESCRIPTION, EXAMPLE, FORMAT, PATTERN};
+    use crate::{RfCreditorReference, MAX_LEN, MIN_LEN};
+
+    /// Name of references as parameter
+    const PARAMETER_NAME: &str = "reference";
+
+    impl PartialSchema for RfCreditorReference<'_> {
+        fn schema() -> RefOr<Schema> {
+            RefOr::T(Schema::Object(
+                ObjectBuilder::new()
+                    .schema_type(Type::String)
+                    .format(Some(SchemaFormat::Custom(FORMAT.to_string())))
+                    .pattern(Some(PATTERN))
+                    .min_length(Some(MIN_LEN))
+                    .max_length(Some(MAX_LEN))
+                    .description(Some(DESCRIPTION))
+                    .examples([EXAMPLE])
+                    .build(),
+            ))
+        }
+    }
+
+    impl ToSchema for RfCreditorReference<'_> {
+        fn name() -> Cow<'static, str> {
+            Cow::Borrowed("RfCreditorReference")
+        }
+    }
+
+    /// Single required parameter `reference`, in path by default
+    impl IntoParams for RfCreditorReference<'_> {
+        fn into_params(parameter_in_provider: impl Fn() -> Option<ParameterIn>) -> Vec<Parameter> {
+            vec![ParameterBuilder::new()
+                .name(PARAMETER_NAME)
+                .parameter_in(parameter_in_provider().unwrap_or_default())
+                .required(Required::True)
+                .description(Some(DESCRIPTION))
+                .schema(Some(Self::schema()))
+                .build()]
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use ::utoipa::openapi::schema::SchemaType;
+
+        use super::*;
+
+        #[test]
+        fn schema_test() {
+            assert_eq!(RfCreditorReference::name(), "RfCreditorReference");
+
+            let object = match RfCreditorReference::schema() {
+                RefOr::T(Schema::Object(object)) => object,
+                _ => panic!("expected object schema"),
+            };
+            assert!(object.schema_type == SchemaType::new(Type::String));
+            assert!(matches!(
+                object.format,
+                Some(SchemaFormat::Custom(ref format)) if format == "iso11649"
+            ));
+            assert_eq!(object.pattern.as_deref(), Some(PATTERN));
+            assert_eq!(object.min_length, Some(5));
+            assert_eq!(object.max_length, Some(25));
+            assert!(crate::is_valid(EXAMPLE));
+        }
+
+        #[test]
+        fn into_params_test() {
+            let params = RfCreditorReference::into_params(|| Some(ParameterIn::Query));
+            assert_eq!(params.len(), 1);
+            assert_eq!(params[0].name, "reference");
+            assert!(matches!(params[0].parameter_in, ParameterIn::Query));
+            assert!(matches!(params[0].required, Required::True));
+
+            let params = RfCreditorReference::into_params(|| None);
+            assert!(matches!(params[0].parameter_in, ParameterIn::Path));
+        }
+    }
+}
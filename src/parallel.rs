@@ -0,0 +1,60 @@
+//! Parallel validation of large reference lists
+//!
+//! Available with feature `rayon`.
+
+use rayon::prelude::*;
+
+use crate::{ParseError, RfCreditorReference};
+
+/// Validates all `references` like [`RfCreditorReference::parse_str`]
+/// distributed across the threads of the current rayon thread pool
+///
+/// The results are in order of `references`. Collect them into a
+/// [`ValidationReport`](crate::ValidationReport) for the aggregation.
+///
+/// Available with feature `rayon`.
+///
+/// # Examples
+///
+/// ```rust
+/// use iso_11649::ValidationReport;
+///
+/// let references = ["RF18 5390 0754 7034", "RF19 5390 0754 7034", "RF71 2348 231"];
+/// let results = iso_11649::par_validate(&references);
+/// assert!(results[0].is_ok());
+/// assert!(results[1].is_err());
+///
+/// let report = results.into_iter().collect::<ValidationReport>();
+/// assert_eq!(report.failures(), [1]);
+/// ```
+///
+pub fn par_validate<S>(references: &[S]) -> Vec<Result<RfCreditorReference<'static>, ParseError>>
+where
+    S: AsRef<str> + Sync,
+{
+    references
+        .par_iter()
+        .map(|reference| RfCreditorReference::parse_str(reference.as_ref()))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn par_validate_test() {
+        let mut references = (0..10_000u64)
+            .map(|n| RfCreditorReference::from_u64(n * 7919).to_string())
+            .collect::<Vec<_>>();
+        references[4711] = "RF00 4711".to_string();
+
+        let results = par_validate(&references);
+        assert_eq!(results.len(), references.len());
+        for (reference, result) in references.iter().zip(&results) {
+            assert_eq!(result, &RfCreditorReference::parse_str(reference));
+        }
+        assert!(results[4711].is_err());
+        assert!(par_validate::<&str>(&[]).is_empty());
+    }
+}
@@ -1,28 +1,308 @@
-//!
+//! Errors of parsing creditor references
+
+use std::num::ParseIntError;
 
 /// The `ParseError` enum is a collection of all the possible
 /// reasons parsing fail.
+///
+/// New variants may be added in future versions.
+///
+/// With feature `serde` the error is serializable with the variant name
+/// in field `kind`.
 #[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[cfg_attr(feature = "serde", serde(tag = "kind"))]
+#[non_exhaustive]
 pub enum ParseError {
+    /// reference body is longer than allowed on generation
+    BodyTooLong {
+        /// the rejected input
+        input: String,
+        /// the length of the body of `input` in electronic format
+        len: usize,
+        /// the maximum allowed length of the body
+        max: usize,
+    },
     /// invalid character not parseable
-    InvalidCharacter(String),
-    /// checksum has invalid format
-    InvalidChecksum(String),
-    /// invalid format not parseable
-    InvalidFormat(String),
+    InvalidCharacter {
+        /// the rejected input
+        input: String,
+        /// the offending character
+        character: char,
+        /// the char index of `character` in `input`
+        position: usize,
+    },
+    /// check digits are not numeric
+    InvalidCheckDigits {
+        /// the rejected input
+        input: String,
+        /// the error parsing the check digits
+        #[cfg_attr(feature = "serde", serde(skip))]
+        source: ParseIntError,
+    },
+    /// checksum does not match the reference
+    InvalidChecksum {
+        /// the rejected input
+        input: String,
+        /// the checksum calculated for the reference
+        expected: u8,
+        /// the checksum found in `input`
+        found: u8,
+    },
     /// identifier is not RF
-    InvalidIdentifier(String),
+    InvalidIdentifier {
+        /// the rejected input
+        input: String,
+    },
+    /// reference is longer than allowed
+    TooLong {
+        /// the rejected input
+        input: String,
+        /// the length of `input` in electronic format
+        len: usize,
+        /// the maximum allowed length
+        max: usize,
+    },
+    /// reference is shorter than allowed
+    TooShort {
+        /// the rejected input
+        input: String,
+        /// the length of `input` in electronic format
+        len: usize,
+        /// the minimum required length
+        min: usize,
+    },
+}
+
+impl ParseError {
+    /// Returns the rejected input
+    #[must_use]
+    pub fn input(&self) -> &str {
+        match self {
+            ParseError::BodyTooLong { input, .. }
+            | ParseError::InvalidCharacter { input, .. }
+            | ParseError::InvalidCheckDigits { input, .. }
+            | ParseError::InvalidChecksum { input, .. }
+            | ParseError::InvalidIdentifier { input }
+            | ParseError::TooLong { input, .. }
+            | ParseError::TooShort { input, .. } => input,
+        }
+    }
+
+    /// Replaces the rejected input by `input`, e.g. the input of the user
+    /// instead of a preprocessed one
+    pub(crate) fn with_input(mut self, input: &str) -> Self {
+        match &mut self {
+            ParseError::BodyTooLong {
+                input: rejected, ..
+            }
+            | ParseError::InvalidCharacter {
+                input: rejected, ..
+            }
+            | ParseError::InvalidCheckDigits {
+                input: rejected, ..
+            }
+            | ParseError::InvalidChecksum {
+                input: rejected, ..
+            }
+            | ParseError::InvalidIdentifier { input: rejected }
+            | ParseError::TooLong {
+                input: rejected, ..
+            }
+            | ParseError::TooShort {
+                input: rejected, ..
+            } => *rejected = input.to_string(),
+        }
+        self
+    }
+
+    /// Returns a stable, machine-readable code of the error
+    ///
+    /// The codes are guaranteed not to change across versions of this crate.
+    ///
+    /// | Code                                 | Variant                            |
+    /// |--------------------------------------|------------------------------------|
+    /// | `ISO11649-E001-INVALID-CHAR`         | [`ParseError::InvalidCharacter`]   |
+    /// | `ISO11649-E002-INVALID-CHECK-DIGITS` | [`ParseError::InvalidCheckDigits`] |
+    /// | `ISO11649-E003-INVALID-CHECKSUM`     | [`ParseError::InvalidChecksum`]    |
+    /// | `ISO11649-E004-INVALID-IDENTIFIER`   | [`ParseError::InvalidIdentifier`]  |
+    /// | `ISO11649-E005-TOO-LONG`             | [`ParseError::TooLong`]            |
+    /// | `ISO11649-E006-TOO-SHORT`            | [`ParseError::TooShort`]           |
+    /// | `ISO11649-E007-BODY-TOO-LONG`        | [`ParseError::BodyTooLong`]        |
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use iso_11649::RfCreditorReference;
+    ///
+    /// let err = RfCreditorReference::parse_str("RF18 5390_0754 7034").unwrap_err();
+    ///
+    /// assert_eq!(err.code(), "ISO11649-E001-INVALID-CHAR");
+    /// ```
+    ///
+    #[must_use]
+    pub fn code(&self) -> &'static str {
+        match self {
+            ParseError::InvalidCharacter { .. } => "ISO11649-E001-INVALID-CHAR",
+            ParseError::InvalidCheckDigits { .. } => "ISO11649-E002-INVALID-CHECK-DIGITS",
+            ParseError::InvalidChecksum { .. } => "ISO11649-E003-INVALID-CHECKSUM",
+            ParseError::InvalidIdentifier { .. } => "ISO11649-E004-INVALID-IDENTIFIER",
+            ParseError::TooLong { .. } => "ISO11649-E005-TOO-LONG",
+            ParseError::TooShort { .. } => "ISO11649-E006-TOO-SHORT",
+            ParseError::BodyTooLong { .. } => "ISO11649-E007-BODY-TOO-LONG",
+        }
+    }
+
+    /// Creates [`ProblemDetails`] describing the error according to
+    /// [RFC 7807](https://www.rfc-editor.org/rfc/rfc7807)
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use iso_11649::RfCreditorReference;
+    ///
+    /// let err = RfCreditorReference::parse_str("RF19 5390 0754 7034").unwrap_err();
+    /// let problem = err.to_problem_details();
+    ///
+    /// assert_eq!(problem.type_uri, "urn:iso11649:problem:invalid-checksum");
+    /// assert_eq!(problem.title, "Invalid checksum");
+    /// assert_eq!(problem.status, 422);
+    /// assert_eq!(problem.code, err.code());
+    /// assert_eq!(problem.detail, err.to_string());
+    /// ```
+    ///
+    #[must_use]
+    pub fn to_problem_details(&self) -> ProblemDetails {
+        let (type_uri, title) = match self {
+            ParseError::BodyTooLong { .. } => (
+                "urn:iso11649:problem:body-too-long",
+                "Reference body too long",
+            ),
+            ParseError::InvalidCharacter { .. } => (
+                "urn:iso11649:problem:invalid-character",
+                "Invalid character",
+            ),
+            ParseError::InvalidCheckDigits { .. } => (
+                "urn:iso11649:problem:invalid-check-digits",
+                "Invalid check digits",
+            ),
+            ParseError::InvalidChecksum { .. } => {
+                ("urn:iso11649:problem:invalid-checksum", "Invalid checksum")
+            }
+            ParseError::InvalidIdentifier { .. } => (
+                "urn:iso11649:problem:invalid-identifier",
+                "Invalid identifier",
+            ),
+            ParseError::TooLong { .. } => ("urn:iso11649:problem:too-long", "Reference too long"),
+            ParseError::TooShort { .. } => {
+                ("urn:iso11649:problem:too-short", "Reference too short")
+            }
+        };
+
+        ProblemDetails {
+            type_uri,
+            title,
+            status: 422,
+            detail: self.to_string(),
+            code: self.code(),
+        }
+    }
 }
 
 impl std::fmt::Display for ParseError {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> Result<(), std::fmt::Error> {
         match self {
-            ParseError::InvalidCharacter(m) => write!(f, "invalid character not parseable [{}]", m),
-            ParseError::InvalidChecksum(m) => write!(f, "checksum has invalid format [{}]", m),
-            ParseError::InvalidFormat(m) => write!(f, "invalid format not parseable [{}]", m),
-            ParseError::InvalidIdentifier(m) => write!(f, "identifier is not RF [{}]", m),
+            ParseError::BodyTooLong { input, len, max } => write!(
+                f,
+                "reference body is too long with length {} (max {}) [{}]",
+                len, max, input
+            ),
+            ParseError::InvalidCharacter {
+                input,
+                character,
+                position,
+            } => write!(
+                f,
+                "invalid character '{}' at position {} not parseable [{}]",
+                character, position, input
+            ),
+            ParseError::InvalidCheckDigits { input, .. } => {
+                write!(f, "check digits are not numeric [{}]", input)
+            }
+            ParseError::InvalidChecksum {
+                input,
+                expected,
+                found,
+            } => write!(
+                f,
+                "checksum {:02} does not match expected {:02} [{}]",
+                found, expected, input
+            ),
+            ParseError::InvalidIdentifier { input } => {
+                write!(f, "identifier is not RF [{}]", input)
+            }
+            ParseError::TooLong { input, len, max } => write!(
+                f,
+                "reference is too long with length {} (max {}) [{}]",
+                len, max, input
+            ),
+            ParseError::TooShort { input, len, min } => write!(
+                f,
+                "reference is too short with length {} (min {}) [{}]",
+                len, min, input
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            ParseError::InvalidCheckDigits { source, .. } => Some(source),
+            _ => None,
         }
     }
 }
 
-impl std::error::Error for ParseError {}
+/// `ProblemDetails` describes a [`ParseError`] in the format of
+/// [RFC 7807](https://www.rfc-editor.org/rfc/rfc7807) problem details.
+///
+/// Created by [`ParseError::to_problem_details`]. With feature `serde`
+/// it serializes to a `application/problem+json` body.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct ProblemDetails {
+    /// Stable URI reference identifying the problem type
+    #[cfg_attr(feature = "serde", serde(rename = "type"))]
+    pub type_uri: &'static str,
+    /// Short summary of the problem type
+    pub title: &'static str,
+    /// Suggested HTTP status code
+    pub status: u16,
+    /// Explanation specific to this occurrence of the problem
+    pub detail: String,
+    /// Stable error code, see [`ParseError::code`]
+    pub code: &'static str,
+}
+
+#[cfg(all(test, feature = "serde"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn serialize_test() {
+        let err = ParseError::TooShort {
+            input: "RF18".to_string(),
+            len: 4,
+            min: 5,
+        };
+        assert_eq!(
+            serde_json::to_string(&err).unwrap(),
+            r#"{"kind":"TooShort","input":"RF18","len":4,"min":5}"#
+        );
+        assert_eq!(
+            serde_json::to_string(&err.to_problem_details()).unwrap(),
+            r#"{"type":"urn:iso11649:problem:too-short","title":"Reference too short","status":422,"detail":"reference is too short with length 4 (min 5) [RF18]","code":"ISO11649-E006-TOO-SHORT"}"#
+        );
+    }
+}
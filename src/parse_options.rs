@@ -0,0 +1,330 @@
+//! Configurable parsing of creditor references
+
+use crate::{ParseError, RfCreditorReference, MAX_LEN};
+
+/// `CasePolicy` defines the handling of lowercase letters in references
+///
+/// The identifier `RF` is always required in uppercase.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum CasePolicy {
+    /// Lowercase letters are accepted and kept as entered
+    Preserve,
+    /// Lowercase letters are accepted and converted to uppercase
+    Uppercase,
+    /// Lowercase letters result in [`ParseError::InvalidCharacter`]
+    RejectLowercase,
+}
+
+impl Default for CasePolicy {
+    fn default() -> Self {
+        Self::Preserve
+    }
+}
+
+/// `ParseOptions` is a builder for parsing creditor references with
+/// additional, environment-specific validation rules.
+///
+/// The default options accept the same input as [`RfCreditorReference::parse_str`].
+///
+/// # Examples
+///
+/// ```rust
+/// use iso_11649::parse_options::ParseOptions;
+///
+/// let options = ParseOptions::new()
+///     .allow_lowercase(false)
+///     .strip_separators(&['-', '.'])
+///     .max_len(20)
+///     .require_numeric_body(true);
+///
+/// let rf = options.parse("RF18-5390-0754-7034").unwrap();
+/// assert_eq!(rf.to_string(), "RF18 5390 0754 7034");
+///
+/// assert!(options.parse("RF63 ABCD 0754 efgh").is_err());
+/// assert!(options.parse("RF93539007547034928301234").is_err());
+/// ```
+///
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ParseOptions {
+    /// Handling of lowercase letters in the reference
+    case_policy: CasePolicy,
+    /// Maximum length of the reference in electronic format
+    max_len: usize,
+    /// Unicode digits and full-width characters are mapped to ASCII
+    #[cfg(feature = "unicode")]
+    normalize_unicode: bool,
+    /// Only digits are accepted after the check digits
+    require_numeric_body: bool,
+    /// Characters removed in addition to spaces before validation
+    separators: Vec<char>,
+}
+
+impl ParseOptions {
+    /// Creates [`ParseOptions`] with default settings
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets if lowercase letters are accepted in the reference
+    ///
+    /// Shorthand for [`Self::case_policy`] with [`CasePolicy::Preserve`]
+    /// or [`CasePolicy::RejectLowercase`]. Default is `true`.
+    #[must_use]
+    pub fn allow_lowercase(self, allow: bool) -> Self {
+        self.case_policy(if allow {
+            CasePolicy::Preserve
+        } else {
+            CasePolicy::RejectLowercase
+        })
+    }
+
+    /// Sets the handling of lowercase letters in the reference
+    ///
+    /// Default is [`CasePolicy::Preserve`].
+    #[must_use]
+    pub fn case_policy(mut self, case_policy: CasePolicy) -> Self {
+        self.case_policy = case_policy;
+        self
+    }
+
+    /// Sets the maximum length of the reference in electronic format
+    ///
+    /// Default is [`MAX_LEN`]. Values above [`MAX_LEN`] have no effect.
+    #[must_use]
+    pub fn max_len(mut self, max_len: usize) -> Self {
+        self.max_len = max_len;
+        self
+    }
+
+    /// Sets if Unicode digits and full-width characters are mapped to
+    /// ASCII before validation
+    ///
+    /// See [`crate::unicode::normalize`]. Default is `false`.
+    ///
+    /// Available with feature `unicode`.
+    #[cfg(feature = "unicode")]
+    #[must_use]
+    pub fn normalize_unicode(mut self, normalize: bool) -> Self {
+        self.normalize_unicode = normalize;
+        self
+    }
+
+    /// Sets if the reference may only contain digits after the check digits
+    ///
+    /// Default is `false`.
+    #[must_use]
+    pub fn require_numeric_body(mut self, require: bool) -> Self {
+        self.require_numeric_body = require;
+        self
+    }
+
+    /// Sets characters which are removed from the input before validation
+    ///
+    /// Spaces are always removed. Default is no additional separators.
+    #[must_use]
+    pub fn strip_separators(mut self, separators: &[char]) -> Self {
+        self.separators = separators.to_vec();
+        self
+    }
+
+    /// Parses a `reference` with the configured options
+    ///
+    /// # Errors
+    ///
+    /// Results in [`ParseError`]s with the unchanged `reference` as input
+    /// when there is some problem with parsing the `reference` or it
+    /// violates the configured options.
+    ///
+    pub fn parse(&self, reference: &str) -> Result<RfCreditorReference<'static>, ParseError> {
+        let input = reference;
+
+        #[cfg(feature = "unicode")]
+        let normalized;
+        #[cfg(feature = "unicode")]
+        let reference = if self.normalize_unicode {
+            normalized = crate::unicode::normalize(reference);
+            &normalized
+        } else {
+            reference
+        };
+
+        // separators are replaced by spaces to keep error positions
+        // in line with the original `reference`
+        let reference = reference
+            .chars()
+            .map(|c| if self.separators.contains(&c) { ' ' } else { c })
+            .collect::<String>();
+
+        let rf = RfCreditorReference::parse_str(&reference).map_err(|err| err.with_input(input))?;
+        let len = rf.as_electronic_str().len();
+
+        if len > self.max_len {
+            Err(ParseError::TooLong {
+                input: input.to_string(),
+                len,
+                max: self.max_len,
+            })
+        } else if let Some((position, character)) = reference
+            .chars()
+            .enumerate()
+            .filter(|&(_, c)| c != ' ')
+            .skip(4)
+            .find(|&(_, c)| {
+                (self.case_policy == CasePolicy::RejectLowercase && c.is_ascii_lowercase())
+                    || (self.require_numeric_body && !c.is_ascii_digit())
+            })
+        {
+            Err(ParseError::InvalidCharacter {
+                input: input.to_string(),
+                character,
+                position,
+            })
+        } else if self.case_policy == CasePolicy::Uppercase {
+            Ok(rf.to_uppercase_reference())
+        } else {
+            Ok(rf)
+        }
+    }
+}
+
+impl Default for ParseOptions {
+    fn default() -> Self {
+        Self {
+            case_policy: CasePolicy::default(),
+            max_len: MAX_LEN,
+            #[cfg(feature = "unicode")]
+            normalize_unicode: false,
+            require_numeric_body: false,
+            separators: Vec::new(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default_options_test() {
+        let options = ParseOptions::new();
+        assert_eq!(
+            options.parse("RF18 5390 0754 7034"),
+            RfCreditorReference::parse_str("RF18 5390 0754 7034")
+        );
+        assert!(options.parse("RF63 ABCD 0754 efgh").is_ok());
+        assert!(options.parse("RF93539007547034928301234").is_ok());
+        assert!(options.parse("RF18-5390-0754-7034").is_err());
+    }
+
+    #[test]
+    fn allow_lowercase_test() {
+        let options = ParseOptions::new().allow_lowercase(false);
+        assert!(matches!(
+            options.parse("RF63 ABCD 0754 efgh"),
+            Err(ParseError::InvalidCharacter {
+                character: 'e',
+                position: 15,
+                ..
+            })
+        ));
+        assert!(options.parse("RF18 5390 0754 7034").is_ok());
+    }
+
+    #[test]
+    fn case_policy_test() {
+        let reference = "RF63 ABCD 0754 efgh";
+        assert_eq!(
+            ParseOptions::new().parse(reference).unwrap().to_string(),
+            reference
+        );
+        assert_eq!(
+            ParseOptions::new()
+                .case_policy(CasePolicy::Uppercase)
+                .parse(reference)
+                .unwrap()
+                .to_string(),
+            "RF63 ABCD 0754 EFGH"
+        );
+        assert_eq!(
+            ParseOptions::new().case_policy(CasePolicy::RejectLowercase),
+            ParseOptions::new().allow_lowercase(false)
+        );
+        assert!(ParseOptions::new()
+            .case_policy(CasePolicy::Uppercase)
+            .parse("rf63 ABCD 0754 efgh")
+            .is_err());
+    }
+
+    #[test]
+    fn max_len_test() {
+        let options = ParseOptions::new().max_len(16);
+        assert!(options.parse("RF18539007547034").is_ok());
+        assert!(matches!(
+            options.parse("RF93539007547034928301234"),
+            Err(ParseError::TooLong {
+                len: 25,
+                max: 16,
+                ..
+            })
+        ));
+    }
+
+    #[cfg(feature = "unicode")]
+    #[test]
+    fn normalize_unicode_test() {
+        let reference = "ＲＦ１８　５３９０　０７５４　７０３４";
+        assert!(ParseOptions::new().parse(reference).is_err());
+        assert_eq!(
+            ParseOptions::new()
+                .normalize_unicode(true)
+                .parse(reference)
+                .unwrap()
+                .to_string(),
+            "RF18 5390 0754 7034"
+        );
+
+        let reference = "ＲＦ１９　５３９０　０７５４　７０３４";
+        let err = ParseOptions::new()
+            .normalize_unicode(true)
+            .parse(reference)
+            .unwrap_err();
+        assert_eq!(err.input(), reference);
+    }
+
+    #[test]
+    fn require_numeric_body_test() {
+        let options = ParseOptions::new().require_numeric_body(true);
+        assert!(options.parse("RF18 5390 0754 7034").is_ok());
+        assert!(matches!(
+            options.parse("RF63 ABCD 0754 efgh"),
+            Err(ParseError::InvalidCharacter {
+                character: 'A',
+                position: 5,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn strip_separators_test() {
+        let options = ParseOptions::new().strip_separators(&['-', '.']);
+        assert_eq!(
+            options.parse("RF18-5390.0754-7034").unwrap().to_string(),
+            "RF18 5390 0754 7034"
+        );
+        assert!(options.parse("RF18 5390-0754 7034").is_ok());
+        assert!(options.parse("RF18_5390_0754_7034").is_err());
+
+        let err = options.parse("RF19-5390-0754-7034").unwrap_err();
+        assert_eq!(err.input(), "RF19-5390-0754-7034");
+        let err = options
+            .clone()
+            .require_numeric_body(true)
+            .parse("RF63-ABCD-0754-EFGH")
+            .unwrap_err();
+        assert_eq!(err.input(), "RF63-ABCD-0754-EFGH");
+        let err = options.max_len(5).parse("RF71-2348-231").unwrap_err();
+        assert_eq!(err.input(), "RF71-2348-231");
+    }
+}
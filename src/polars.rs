@@ -0,0 +1,296 @@
+//! Vectorized handling of reference columns in
+//! [Polars](https://pola.rs) data frames
+//!
+//! Available with feature `polars`.
+//!
+//! The kernels [`is_valid`], [`normalize`] and [`checksum`] work on
+//! [`StringChunked`] columns. [`RfSeriesExt::rf`] exposes them as namespace
+//! of [`Series`] like `series.rf().is_valid()`, [`RfExprExt::rf`] as
+//! namespace of expressions of lazy frames like
+//! `col("reference").rf().is_valid()`.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use iso_11649::polars::RfSeriesExt;
+//! use polars_core::prelude::*;
+//!
+//! let references = Series::new(
+//!     "reference".into(),
+//!     &[Some("RF18 5390 0754 7034"), Some("RF19 5390 0754 7034"), None],
+//! );
+//!
+//! let valid = references.rf().is_valid().unwrap();
+//! assert_eq!(
+//!     valid.bool().unwrap().into_iter().collect::<Vec<_>>(),
+//!     [Some(true), Some(false), None]
+//! );
+//!
+//! let normalized = references.rf().normalize().unwrap();
+//! assert_eq!(normalized.str().unwrap().get(0), Some("RF18539007547034"));
+//! assert_eq!(normalized.str().unwrap().get(1), None);
+//! ```
+//!
+//! In lazy frames:
+//!
+//! ```rust
+//! use iso_11649::polars::RfExprExt;
+//! use polars_core::df;
+//! use polars_lazy::prelude::*;
+//!
+//! let payments = df!("reference" => &["RF18 5390 0754 7034", "RF19 5390 0754 7034"])
+//!     .unwrap()
+//!     .lazy()
+//!     .filter(col("reference").rf().is_valid())
+//!     .select([col("reference").rf().normalize()])
+//!     .collect()
+//!     .unwrap();
+//! assert_eq!(
+//!     payments.column("reference").unwrap().str().unwrap().get(0),
+//!     Some("RF18539007547034")
+//! );
+//! assert_eq!(payments.height(), 1);
+//! ```
+
+use ::polars_core::prelude::{
+    BooleanChunked, DataType, IntoColumn, IntoSeries, PolarsResult, Series, StringChunked,
+    UInt8Chunked,
+};
+use ::polars_lazy::dsl::{Expr, GetOutput};
+
+use crate::parse_bytes;
+
+/// Validates each value of `ca` like [`crate::is_valid`]
+///
+/// Null values stay null.
+#[must_use]
+pub fn is_valid(ca: &StringChunked) -> BooleanChunked {
+    let mut valid = ca
+        .into_iter()
+        .map(|reference| reference.map(crate::is_valid))
+        .collect::<BooleanChunked>();
+    valid.rename(ca.name().clone());
+    valid
+}
+
+/// Normalizes each value of `ca` to the electronic format
+///
+/// Invalid references become null.
+#[must_use]
+pub fn normalize(ca: &StringChunked) -> StringChunked {
+    let mut normalized = ca
+        .into_iter()
+        .map(|reference| {
+            reference
+                .and_then(|reference| parse_bytes(reference.as_bytes()).ok())
+                .map(|electronic_reference| electronic_reference.as_str().to_string())
+        })
+        .collect::<StringChunked>();
+    normalized.rename(ca.name().clone());
+    normalized
+}
+
+/// Checksums of the check digits of each value of `ca`
+///
+/// Invalid references become null.
+#[must_use]
+pub fn checksum(ca: &StringChunked) -> UInt8Chunked {
+    let mut checksums = ca
+        .into_iter()
+        .map(|reference| {
+            reference
+                .and_then(|reference| parse_bytes(reference.as_bytes()).ok())
+                .map(|electronic_reference| electronic_reference.checksum())
+        })
+        .collect::<UInt8Chunked>();
+    checksums.rename(ca.name().clone());
+    checksums
+}
+
+/// `RfSeriesExt` adds the namespace [`RfNameSpace`] to [`Series`]
+pub trait RfSeriesExt {
+    /// Namespace of the reference kernels
+    fn rf(&self) -> RfNameSpace<'_>;
+}
+
+impl RfSeriesExt for Series {
+    fn rf(&self) -> RfNameSpace<'_> {
+        RfNameSpace(self)
+    }
+}
+
+/// `RfNameSpace` applies the reference kernels to a string [`Series`]
+///
+/// Created by [`RfSeriesExt::rf`]. All kernels fail for series of other
+/// types than strings.
+#[derive(Clone, Copy, Debug)]
+pub struct RfNameSpace<'a>(&'a Series);
+
+impl RfNameSpace<'_> {
+    /// Applies [`is_valid`]
+    ///
+    /// # Errors
+    ///
+    /// Results in a `PolarsError` for series of other types than strings.
+    ///
+    pub fn is_valid(self) -> PolarsResult<Series> {
+        Ok(is_valid(self.0.str()?).into_series())
+    }
+
+    /// Applies [`normalize`]
+    ///
+    /// # Errors
+    ///
+    /// Results in a `PolarsError` for series of other types than strings.
+    ///
+    pub fn normalize(self) -> PolarsResult<Series> {
+        Ok(normalize(self.0.str()?).into_series())
+    }
+
+    /// Applies [`checksum`]
+    ///
+    /// # Errors
+    ///
+    /// Results in a `PolarsError` for series of other types than strings.
+    ///
+    pub fn checksum(self) -> PolarsResult<Series> {
+        Ok(checksum(self.0.str()?).into_series())
+    }
+}
+
+/// `RfExprExt` adds the namespace [`RfExprNameSpace`] to [`Expr`]
+pub trait RfExprExt {
+    /// Namespace of the reference kernels
+    fn rf(self) -> RfExprNameSpace;
+}
+
+impl RfExprExt for Expr {
+    fn rf(self) -> RfExprNameSpace {
+        RfExprNameSpace(self)
+    }
+}
+
+/// `RfExprNameSpace` maps the values of an expression with the reference
+/// kernels
+///
+/// Created by [`RfExprExt::rf`]. Evaluating the expressions fails for
+/// columns of other types than strings.
+#[derive(Clone, Debug)]
+pub struct RfExprNameSpace(Expr);
+
+impl RfExprNameSpace {
+    /// Maps with [`is_valid`]
+    pub fn is_valid(self) -> Expr {
+        self.map(|series| series.rf().is_valid(), DataType::Boolean)
+    }
+
+    /// Maps with [`normalize`]
+    pub fn normalize(self) -> Expr {
+        self.map(|series| series.rf().normalize(), DataType::String)
+    }
+
+    /// Maps with [`checksum`]
+    pub fn checksum(self) -> Expr {
+        self.map(|series| series.rf().checksum(), DataType::UInt8)
+    }
+
+    /// Maps with `kernel` resulting in `dtype`
+    fn map(self, kernel: fn(&Series) -> PolarsResult<Series>, dtype: DataType) -> Expr {
+        self.0.map(
+            move |column| {
+                let series = kernel(column.as_materialized_series())?;
+                Ok(Some(series.into_column()))
+            },
+            GetOutput::from_type(dtype),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ::polars_core::{df, prelude::NamedFrom};
+    use ::polars_lazy::{dsl::col, frame::IntoLazy};
+
+    use super::*;
+
+    fn references() -> Series {
+        Series::new(
+            "reference".into(),
+            &[
+                Some(" RF71 2348 231"),
+                None,
+                Some("RF18 5390 0754 703_"),
+                Some("RF18539007547034"),
+            ],
+        )
+    }
+
+    #[test]
+    fn kernels_test() {
+        let references = references();
+        let ca = references.str().unwrap();
+
+        let valid = is_valid(ca);
+        assert_eq!(valid.name().as_str(), "reference");
+        assert_eq!(
+            valid.into_iter().collect::<Vec<_>>(),
+            [Some(true), None, Some(false), Some(true)]
+        );
+        assert_eq!(
+            normalize(ca).into_iter().collect::<Vec<_>>(),
+            [Some("RF712348231"), None, None, Some("RF18539007547034")]
+        );
+        assert_eq!(
+            checksum(ca).into_iter().collect::<Vec<_>>(),
+            [Some(71), None, None, Some(18)]
+        );
+    }
+
+    #[test]
+    fn namespace_test() {
+        let references = references();
+        assert_eq!(references.rf().is_valid().unwrap().len(), 4);
+        assert_eq!(references.rf().checksum().unwrap().null_count(), 2);
+
+        let numbers = Series::new("number".into(), &[4711_u32]);
+        assert!(numbers.rf().is_valid().is_err());
+    }
+
+    #[test]
+    fn lazy_frame_test() {
+        let frame = references()
+            .into_frame()
+            .lazy()
+            .select([
+                col("reference").rf().is_valid().alias("valid"),
+                col("reference").rf().normalize().alias("normalized"),
+                col("reference").rf().checksum().alias("checksum"),
+            ])
+            .collect()
+            .unwrap();
+        assert_eq!(
+            frame
+                .column("valid")
+                .unwrap()
+                .bool()
+                .unwrap()
+                .into_iter()
+                .collect::<Vec<_>>(),
+            [Some(true), None, Some(false), Some(true)]
+        );
+        assert_eq!(
+            frame.column("normalized").unwrap().str().unwrap().get(0),
+            Some("RF712348231")
+        );
+        assert_eq!(
+            frame.column("checksum").unwrap().u8().unwrap().get(3),
+            Some(18)
+        );
+
+        let numbers = df!("number" => &[4711_u32]).unwrap().lazy();
+        assert!(numbers
+            .select([col("number").rf().is_valid()])
+            .collect()
+            .is_err());
+    }
+}
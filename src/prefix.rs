@@ -0,0 +1,323 @@
+//! Namespaces of creditor references for multiple tenants
+//!
+//! A [`PrefixAllocator`] reserves a distinct numeric leading segment of
+//! fixed width for each tenant, e.g. a 3-digit tenant code. References
+//! are minted within the namespace of a tenant, so references of
+//! different tenants never collide.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use iso_11649::prefix::{PrefixAllocator, PrefixError};
+//!
+//! let mut allocator = PrefixAllocator::new(3);
+//! assert_eq!(allocator.allocate("acme").unwrap(), "000");
+//! assert_eq!(allocator.allocate("globex").unwrap(), "001");
+//! assert!(matches!(
+//!     allocator.reserve("initech", "001"),
+//!     Err(PrefixError::Taken { .. })
+//! ));
+//!
+//! let rf = allocator.mint("acme", "4711").unwrap();
+//! assert_eq!(rf.to_string(), "RF71 0004 711");
+//! assert_eq!(allocator.owner(&rf), Some("acme"));
+//! ```
+
+use std::collections::HashMap;
+
+use crate::{ParseError, RfCreditorReference, RfReferenceBuilder, MAX_BODY_LEN};
+
+/// The `PrefixError` enum is a collection of all the possible reasons
+/// allocating prefixes and minting references fail.
+///
+/// New variants may be added in future versions.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum PrefixError {
+    /// tenant has already another prefix
+    AlreadyAllocated {
+        /// the tenant
+        tenant: String,
+        /// the prefix of `tenant`
+        prefix: String,
+    },
+    /// all prefixes are allocated
+    Exhausted,
+    /// prefix is not numeric or has the wrong width
+    InvalidPrefix {
+        /// the rejected prefix
+        prefix: String,
+    },
+    /// reference could not be minted
+    Reference(ParseError),
+    /// prefix is allocated to another tenant
+    Taken {
+        /// the rejected prefix
+        prefix: String,
+        /// the tenant owning `prefix`
+        tenant: String,
+    },
+    /// tenant has no prefix
+    UnknownTenant {
+        /// the tenant
+        tenant: String,
+    },
+}
+
+impl std::fmt::Display for PrefixError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> Result<(), std::fmt::Error> {
+        match self {
+            PrefixError::AlreadyAllocated { tenant, prefix } => {
+                write!(f, "tenant {} has already prefix {}", tenant, prefix)
+            }
+            PrefixError::Exhausted => write!(f, "all prefixes are allocated"),
+            PrefixError::InvalidPrefix { prefix } => write!(f, "invalid prefix [{}]", prefix),
+            PrefixError::Reference(err) => write!(f, "{}", err),
+            PrefixError::Taken { prefix, tenant } => {
+                write!(f, "prefix {} is allocated to tenant {}", prefix, tenant)
+            }
+            PrefixError::UnknownTenant { tenant } => write!(f, "unknown tenant {}", tenant),
+        }
+    }
+}
+
+impl std::error::Error for PrefixError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            PrefixError::Reference(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<ParseError> for PrefixError {
+    fn from(err: ParseError) -> Self {
+        PrefixError::Reference(err)
+    }
+}
+
+/// `PrefixAllocator` partitions creditor references into namespaces of
+/// tenants
+///
+/// Each tenant owns exactly one numeric prefix of the width of the
+/// allocator.
+#[derive(Clone, Debug)]
+pub struct PrefixAllocator {
+    /// Width of the prefixes
+    width: usize,
+    /// Next candidate of [`Self::allocate`]
+    next: u128,
+    /// Tenants by prefix
+    owners: HashMap<String, String>,
+    /// Prefixes by tenant
+    prefixes: HashMap<String, String>,
+}
+
+impl PrefixAllocator {
+    /// Creates an empty [`PrefixAllocator`] of prefixes with `width`
+    /// digits
+    ///
+    /// # Panics
+    ///
+    /// If `width` is 0 or leaves no room for a body within
+    /// [`MAX_BODY_LEN`].
+    ///
+    #[must_use]
+    pub fn new(width: usize) -> Self {
+        assert!(
+            (1..MAX_BODY_LEN).contains(&width),
+            "prefix width must be within 1 and {}",
+            MAX_BODY_LEN - 1
+        );
+
+        Self {
+            width,
+            next: 0,
+            owners: HashMap::new(),
+            prefixes: HashMap::new(),
+        }
+    }
+
+    /// Allocates the next free prefix for `tenant`
+    ///
+    /// Returns the existing prefix if `tenant` has already one.
+    ///
+    /// # Errors
+    ///
+    /// Results in [`PrefixError::Exhausted`] when all prefixes are
+    /// allocated.
+    ///
+    pub fn allocate(&mut self, tenant: &str) -> Result<&str, PrefixError> {
+        if !self.prefixes.contains_key(tenant) {
+            // width is at most MAX_BODY_LEN
+            #[allow(clippy::cast_possible_truncation)]
+            let capacity = 10u128.pow(self.width as u32);
+            let prefix = loop {
+                if self.next >= capacity {
+                    return Err(PrefixError::Exhausted);
+                }
+                let prefix = format!("{:0width$}", self.next, width = self.width);
+                self.next += 1;
+                if !self.owners.contains_key(&prefix) {
+                    break prefix;
+                }
+            };
+            self.insert(tenant, prefix);
+        }
+
+        Ok(self.prefix(tenant).unwrap_or_default())
+    }
+
+    /// Reserves the given `prefix` for `tenant`
+    ///
+    /// Reserving the prefix `tenant` has already is no error.
+    ///
+    /// # Errors
+    ///
+    /// Results in [`PrefixError::InvalidPrefix`] when `prefix` is not
+    /// numeric with the width of the allocator, [`PrefixError::Taken`]
+    /// when `prefix` is allocated to another tenant and
+    /// [`PrefixError::AlreadyAllocated`] when `tenant` has another prefix.
+    ///
+    pub fn reserve(&mut self, tenant: &str, prefix: &str) -> Result<(), PrefixError> {
+        if prefix.len() != self.width || !prefix.bytes().all(|b| b.is_ascii_digit()) {
+            return Err(PrefixError::InvalidPrefix {
+                prefix: prefix.to_string(),
+            });
+        }
+
+        match (self.owners.get(prefix), self.prefixes.get(tenant)) {
+            (Some(owner), _) if owner != tenant => Err(PrefixError::Taken {
+                prefix: prefix.to_string(),
+                tenant: owner.clone(),
+            }),
+            (_, Some(allocated)) if allocated != prefix => Err(PrefixError::AlreadyAllocated {
+                tenant: tenant.to_string(),
+                prefix: allocated.clone(),
+            }),
+            (Some(_), _) => Ok(()),
+            _ => {
+                self.insert(tenant, prefix.to_string());
+                Ok(())
+            }
+        }
+    }
+
+    /// Prefix of `tenant`
+    #[must_use]
+    pub fn prefix(&self, tenant: &str) -> Option<&str> {
+        self.prefixes.get(tenant).map(String::as_str)
+    }
+
+    /// Tenant owning the namespace of `rf`
+    #[must_use]
+    pub fn owner(&self, rf: &RfCreditorReference<'_>) -> Option<&str> {
+        rf.as_electronic_str()
+            .get(4..4 + self.width)
+            .and_then(|prefix| self.owners.get(prefix))
+            .map(String::as_str)
+    }
+
+    /// Mints the [`RfCreditorReference`] with `body` in the namespace of
+    /// `tenant`
+    ///
+    /// # Errors
+    ///
+    /// Results in [`PrefixError::UnknownTenant`] when `tenant` has no
+    /// prefix and [`PrefixError::Reference`] with the errors of
+    /// [`RfReferenceBuilder::build`].
+    ///
+    pub fn mint(
+        &self,
+        tenant: &str,
+        body: &str,
+    ) -> Result<RfCreditorReference<'static>, PrefixError> {
+        let prefix = self
+            .prefix(tenant)
+            .ok_or_else(|| PrefixError::UnknownTenant {
+                tenant: tenant.to_string(),
+            })?;
+
+        Ok(RfReferenceBuilder::new()
+            .segment(prefix)
+            .segment(body)
+            .build()?)
+    }
+
+    /// Inserts `prefix` of `tenant`
+    fn insert(&mut self, tenant: &str, prefix: String) {
+        let _ = self.owners.insert(prefix.clone(), tenant.to_string());
+        let _ = self.prefixes.insert(tenant.to_string(), prefix);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn allocate_test() {
+        let mut allocator = PrefixAllocator::new(1);
+        allocator.reserve("reserved", "1").unwrap();
+        assert_eq!(allocator.allocate("a").unwrap(), "0");
+        assert_eq!(allocator.allocate("b").unwrap(), "2");
+        assert_eq!(allocator.allocate("a").unwrap(), "0");
+
+        for tenant in 3..10 {
+            assert!(allocator.allocate(&tenant.to_string()).is_ok());
+        }
+        assert_eq!(allocator.allocate("z"), Err(PrefixError::Exhausted));
+    }
+
+    #[test]
+    fn reserve_test() {
+        let mut allocator = PrefixAllocator::new(3);
+        assert!(allocator.reserve("acme", "100").is_ok());
+        assert!(allocator.reserve("acme", "100").is_ok());
+        assert_eq!(
+            allocator.reserve("globex", "100"),
+            Err(PrefixError::Taken {
+                prefix: "100".to_string(),
+                tenant: "acme".to_string()
+            })
+        );
+        assert!(matches!(
+            allocator.reserve("acme", "200"),
+            Err(PrefixError::AlreadyAllocated { .. })
+        ));
+        assert!(matches!(
+            allocator.reserve("globex", "10"),
+            Err(PrefixError::InvalidPrefix { .. })
+        ));
+        assert!(matches!(
+            allocator.reserve("globex", "1a0"),
+            Err(PrefixError::InvalidPrefix { .. })
+        ));
+    }
+
+    #[test]
+    fn mint_test() {
+        let mut allocator = PrefixAllocator::new(3);
+        allocator.reserve("acme", "100").unwrap();
+
+        let rf = allocator.mint("acme", "4711").unwrap();
+        assert_eq!(rf.as_electronic_str(), "RF211004711");
+        assert_eq!(allocator.owner(&rf), Some("acme"));
+        assert_eq!(allocator.owner(&RfCreditorReference::new("2004711")), None);
+
+        assert!(matches!(
+            allocator.mint("globex", "4711"),
+            Err(PrefixError::UnknownTenant { .. })
+        ));
+        assert!(matches!(
+            allocator.mint("acme", "123456789012345678901"),
+            Err(PrefixError::Reference(ParseError::BodyTooLong { .. }))
+        ));
+    }
+
+    #[test]
+    #[should_panic(expected = "prefix width")]
+    fn new_panic_test() {
+        let _ = PrefixAllocator::new(0);
+    }
+}
@@ -0,0 +1,112 @@
+//! Inline storage of the print format
+
+use std::{
+    fmt::Debug,
+    hash::{Hash, Hasher},
+    ops::Deref,
+};
+
+use crate::MAX_LEN;
+
+/// Maximum length of the print format with a space after each group of 4
+pub(crate) const MAX_PRINT_LEN: usize = MAX_LEN + (MAX_LEN - 1) / 4;
+
+/// `PrintReference` is the print format of a creditor reference stored
+/// inline without heap allocation
+#[derive(Clone, Copy)]
+pub(crate) struct PrintReference {
+    buf: [u8; MAX_PRINT_LEN],
+    len: usize,
+}
+
+impl PrintReference {
+    /// Groups the ASCII `electronic_reference` into groups of 4 characters
+    /// separated by spaces
+    ///
+    /// Characters beyond [`MAX_LEN`] are dropped, which never happens
+    /// after validation.
+    pub(crate) fn new(electronic_reference: &str) -> Self {
+        let mut print = Self {
+            buf: [0; MAX_PRINT_LEN],
+            len: 0,
+        };
+        let bytes = electronic_reference.as_bytes();
+        for (i, &b) in bytes.iter().take(MAX_LEN).enumerate() {
+            if i != 0 && i % 4 == 0 {
+                print.buf[print.len] = b' ';
+                print.len += 1;
+            }
+            print.buf[print.len] = b;
+            print.len += 1;
+        }
+        print
+    }
+
+    /// Borrows the print format
+    pub(crate) fn as_str(&self) -> &str {
+        // always ASCII after validation
+        std::str::from_utf8(&self.buf[..self.len]).unwrap_or_default()
+    }
+
+    /// Copy with ASCII letters in upper case
+    pub(crate) fn to_ascii_uppercase(self) -> Self {
+        let mut print = self;
+        print.buf.make_ascii_uppercase();
+        print
+    }
+}
+
+impl Debug for PrintReference {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        Debug::fmt(self.as_str(), f)
+    }
+}
+
+impl Deref for PrintReference {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl Eq for PrintReference {}
+
+impl Hash for PrintReference {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.as_str().hash(state);
+    }
+}
+
+impl PartialEq for PrintReference {
+    fn eq(&self, other: &Self) -> bool {
+        self.as_str() == other.as_str()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn new_test() {
+        assert_eq!(MAX_PRINT_LEN, 31);
+        assert_eq!(PrintReference::new("").as_str(), "");
+        assert_eq!(PrintReference::new("RF18").as_str(), "RF18");
+        assert_eq!(
+            PrintReference::new("RF18539007547034").as_str(),
+            "RF18 5390 0754 7034"
+        );
+
+        let print = PrintReference::new("RF93539007547034928301234");
+        assert_eq!(print.len(), MAX_PRINT_LEN);
+        assert_eq!(PrintReference::new("RF935390075470349283012345"), print);
+    }
+
+    #[test]
+    fn to_ascii_uppercase_test() {
+        let print = PrintReference::new("RF63abcd0754efgh");
+        assert_eq!(print.to_ascii_uppercase().as_str(), "RF63 ABCD 0754 EFGH");
+        assert_eq!(format!("{:?}", print), "\"RF63 abcd 0754 efgh\"");
+    }
+}
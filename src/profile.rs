@@ -0,0 +1,220 @@
+//! Compatibility profiles of banks and countries
+//!
+//! Banks accept creditor references with different restrictions than
+//! ISO 11649 in practice. A [`Profile`] bundles charset, maximum length
+//! and casing rules for generating and validating references.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use iso_11649::{profile::Profile, RfReferenceBuilder};
+//!
+//! let rf = Profile::FinlandStrict
+//!     .build(RfReferenceBuilder::new().segment("1234561"))
+//!     .unwrap();
+//! assert_eq!(rf.to_string(), "RF34 1234 561");
+//!
+//! // check digit of the Finnish reference number is not 1
+//! assert!(Profile::FinlandStrict.parse("RF07 1234 562").is_err());
+//! assert!(Profile::FinlandStrict.parse("RF63 ABCD 0754 EFGH").is_err());
+//! assert!(Profile::SepaDefault.parse("RF63 ABCD 0754 efgh").is_ok());
+//! ```
+
+use crate::{CasePolicy, ParseError, ParseOptions, RfCreditorReference, RfReferenceBuilder};
+
+/// `Profile` is a named set of rules for creditor references
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[non_exhaustive]
+pub enum Profile {
+    /// Rules of ISO 11649 as accepted in SEPA transfers, lowercase letters
+    /// are converted to uppercase
+    SepaDefault,
+    /// Rules of ISO 11649 with lowercase letters rejected, like the paper
+    /// format requires
+    Strict,
+    /// Digits only for banks rejecting letters, e.g. in Germany and the
+    /// Nordic countries
+    NumericOnly,
+    /// Finnish RF references based on national reference numbers of 4 to
+    /// 20 digits with the 7-3-1 check digit as last digit
+    FinlandStrict,
+}
+
+impl Profile {
+    /// Creates the [`ParseOptions`] validating the rules of the profile
+    #[must_use]
+    pub fn parse_options(self) -> ParseOptions {
+        match self {
+            Profile::SepaDefault => ParseOptions::new().case_policy(CasePolicy::Uppercase),
+            Profile::Strict => ParseOptions::new().case_policy(CasePolicy::RejectLowercase),
+            Profile::NumericOnly => ParseOptions::new().require_numeric_body(true),
+            Profile::FinlandStrict => ParseOptions::new().require_numeric_body(true).max_len(24),
+        }
+    }
+
+    /// Parses a `reference` with the rules of the profile
+    ///
+    /// # Errors
+    ///
+    /// Results in [`ParseError`]s when there is some problem with
+    /// parsing the `reference` or it violates the rules of the profile.
+    /// Finnish reference numbers of less than 4 digits result in
+    /// [`ParseError::TooShort`], a wrong Finnish check digit in
+    /// [`ParseError::InvalidChecksum`] with the check digits.
+    ///
+    pub fn parse(self, reference: &str) -> Result<RfCreditorReference<'static>, ParseError> {
+        let rf = self.parse_options().parse(reference)?;
+        match self {
+            Profile::FinlandStrict => finnish_reference(rf, reference),
+            Profile::SepaDefault | Profile::Strict | Profile::NumericOnly => Ok(rf),
+        }
+    }
+
+    /// Builds a [`RfCreditorReference`] with `builder` complying with the
+    /// rules of the profile
+    ///
+    /// # Errors
+    ///
+    /// Results in [`ParseError`]s of [`RfReferenceBuilder::build`] and
+    /// when the reference violates the rules of the profile.
+    ///
+    pub fn build(
+        self,
+        builder: RfReferenceBuilder,
+    ) -> Result<RfCreditorReference<'static>, ParseError> {
+        let builder = match self {
+            Profile::NumericOnly | Profile::FinlandStrict => builder.numeric_only(true),
+            Profile::SepaDefault | Profile::Strict => builder,
+        };
+        self.parse(builder.build()?.as_electronic_str())
+    }
+}
+
+/// Minimum number of digits of Finnish reference numbers
+const FINNISH_MIN_DIGITS: usize = 4;
+
+/// Checks the number of digits and the 7-3-1 check digit of the Finnish
+/// reference number in the body of `rf`
+fn finnish_reference(
+    rf: RfCreditorReference<'static>,
+    input: &str,
+) -> Result<RfCreditorReference<'static>, ParseError> {
+    let electronic = rf.as_electronic_str().as_bytes();
+    let len = electronic.len();
+    let digits = &electronic[4..];
+    let (found, number) = match digits.split_last() {
+        Some((check_digit, number)) if digits.len() >= FINNISH_MIN_DIGITS => {
+            (check_digit - b'0', number)
+        }
+        _ => {
+            return Err(ParseError::TooShort {
+                input: input.to_string(),
+                len,
+                min: len - digits.len() + FINNISH_MIN_DIGITS,
+            })
+        }
+    };
+
+    let sum = number
+        .iter()
+        .rev()
+        .zip([7, 3, 1].iter().cycle())
+        .map(|(digit, weight)| u32::from(digit - b'0') * weight)
+        .sum::<u32>();
+    #[allow(clippy::cast_possible_truncation)]
+    let expected = ((10 - sum % 10) % 10) as u8;
+
+    if found == expected {
+        Ok(rf)
+    } else {
+        Err(ParseError::InvalidChecksum {
+            input: input.to_string(),
+            expected,
+            found,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_test() {
+        let reference = "RF63 ABCD 0754 efgh";
+        assert_eq!(
+            Profile::SepaDefault.parse(reference).unwrap().to_string(),
+            "RF63 ABCD 0754 EFGH"
+        );
+        assert!(Profile::Strict.parse(reference).is_err());
+        assert!(Profile::Strict.parse("RF63 ABCD 0754 EFGH").is_ok());
+        assert!(Profile::NumericOnly.parse(reference).is_err());
+
+        let reference = "RF93539007547034928301234";
+        assert!(Profile::NumericOnly.parse(reference).is_ok());
+        assert!(matches!(
+            Profile::FinlandStrict.parse(reference),
+            Err(ParseError::TooLong {
+                len: 25,
+                max: 24,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn build_test() {
+        assert_eq!(
+            Profile::SepaDefault
+                .build(RfReferenceBuilder::new().segment("abcd0754efgh"))
+                .unwrap()
+                .to_string(),
+            "RF63 ABCD 0754 EFGH"
+        );
+        assert!(Profile::Strict
+            .build(RfReferenceBuilder::new().segment("abcd0754efgh"))
+            .is_err());
+        assert!(matches!(
+            Profile::NumericOnly.build(RfReferenceBuilder::new().segment("INV1")),
+            Err(ParseError::InvalidCharacter { character: 'I', .. })
+        ));
+        assert!(Profile::FinlandStrict
+            .build(RfReferenceBuilder::new().segment("12345678901234567894"))
+            .is_ok());
+        assert!(Profile::FinlandStrict
+            .build(RfReferenceBuilder::new().segment("123456789012345678908"))
+            .is_err());
+    }
+
+    #[test]
+    fn finland_strict_test() {
+        let valid = |number| {
+            Profile::FinlandStrict
+                .build(RfReferenceBuilder::new().segment(number))
+                .unwrap()
+        };
+        assert_eq!(valid("1232").as_electronic_str().len(), 8);
+        assert_eq!(valid("2348236").to_string(), "RF33 2348 236");
+        assert_eq!(
+            Profile::FinlandStrict.parse("RF33 2348 236").unwrap(),
+            valid("2348236")
+        );
+
+        assert!(matches!(
+            Profile::FinlandStrict.build(RfReferenceBuilder::new().segment("2348231")),
+            Err(ParseError::InvalidChecksum {
+                expected: 6,
+                found: 1,
+                ..
+            })
+        ));
+        assert!(matches!(
+            Profile::FinlandStrict.parse("RF71 2348 231"),
+            Err(ParseError::InvalidChecksum { .. })
+        ));
+        assert!(matches!(
+            Profile::FinlandStrict.build(RfReferenceBuilder::new().segment("123")),
+            Err(ParseError::TooShort { len: 7, min: 8, .. })
+        ));
+    }
+}
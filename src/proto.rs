@@ -0,0 +1,109 @@
+//! Protobuf message of creditor references with
+//! [`prost`](https://docs.rs/prost)
+//!
+//! Available with feature `proto`.
+//!
+//! [`CreditorReference`] corresponds to the message of
+//! `proto/creditor_reference.proto` in the repository. The reference is
+//! transferred in canonical electronic format and validated when it is
+//! converted to [`RfCreditorReference`].
+//!
+//! # Examples
+//!
+//! ```rust
+//! use iso_11649::{proto::CreditorReference, RfCreditorReference};
+//! use prost::Message;
+//!
+//! let message = CreditorReference::from(RfCreditorReference::new("539007547034"));
+//! assert_eq!(message.reference, "RF18539007547034");
+//!
+//! let decoded = CreditorReference::decode(message.encode_to_vec().as_slice()).unwrap();
+//! let rf = RfCreditorReference::try_from(decoded).unwrap();
+//! assert_eq!(rf.to_string(), "RF18 5390 0754 7034");
+//!
+//! let invalid = CreditorReference {
+//!     reference: "RF19539007547034".to_string(),
+//! };
+//! assert!(RfCreditorReference::try_from(invalid).is_err());
+//! ```
+
+use crate::{ParseError, RfCreditorReference};
+
+/// `CreditorReference` is the protobuf message `iso11649.CreditorReference`
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct CreditorReference {
+    /// Creditor reference in canonical electronic format without spaces
+    #[prost(string, tag = "1")]
+    pub reference: String,
+}
+
+impl From<&RfCreditorReference<'_>> for CreditorReference {
+    fn from(rf: &RfCreditorReference<'_>) -> Self {
+        Self {
+            reference: rf.to_electronic_string(),
+        }
+    }
+}
+
+impl From<RfCreditorReference<'_>> for CreditorReference {
+    fn from(rf: RfCreditorReference<'_>) -> Self {
+        Self::from(&rf)
+    }
+}
+
+/// Validates the reference like [`RfCreditorReference::parse_str`]
+impl TryFrom<&CreditorReference> for RfCreditorReference<'_> {
+    type Error = ParseError;
+
+    fn try_from(message: &CreditorReference) -> Result<Self, Self::Error> {
+        RfCreditorReference::parse_str(&message.reference)
+    }
+}
+
+/// Validates the reference like [`RfCreditorReference::parse_str`]
+impl TryFrom<CreditorReference> for RfCreditorReference<'_> {
+    type Error = ParseError;
+
+    fn try_from(message: CreditorReference) -> Result<Self, Self::Error> {
+        RfCreditorReference::try_from(&message)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ::prost::Message;
+
+    use super::*;
+
+    #[test]
+    fn conversion_test() {
+        let rf = RfCreditorReference::parse_str("RF71 2348 231").unwrap();
+        let message = CreditorReference::from(&rf);
+        assert_eq!(message.reference, "RF712348231");
+        assert_eq!(RfCreditorReference::try_from(&message).unwrap(), rf);
+
+        let message = CreditorReference {
+            reference: "RF18 5390 0754 703_".to_string(),
+        };
+        assert!(matches!(
+            RfCreditorReference::try_from(message),
+            Err(ParseError::InvalidCharacter { .. })
+        ));
+    }
+
+    #[test]
+    fn wire_test() {
+        let message = CreditorReference::from(RfCreditorReference::new("2348231"));
+        let bytes = message.encode_to_vec();
+        assert_eq!(bytes, b"\x0a\x0bRF712348231");
+        assert_eq!(
+            CreditorReference::decode(bytes.as_slice()).unwrap(),
+            message
+        );
+        assert_eq!(
+            CreditorReference::decode(&b""[..]).unwrap(),
+            CreditorReference::default()
+        );
+    }
+}
@@ -0,0 +1,125 @@
+//! Keyed pseudonymization of creditor references
+//!
+//! Available with feature `pseudonymize`.
+//!
+//! A pseudonym is the hex encoded HMAC-SHA256 of the reference in
+//! electronic format in uppercase. The same reference and key always
+//! result in the same pseudonym, so datasets can still be joined without
+//! exposing the original references.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use iso_11649::{pseudonymize::Pseudonymizer, RfCreditorReference};
+//!
+//! let rf = RfCreditorReference::new("539007547034");
+//!
+//! let pseudonymizer = Pseudonymizer::new(b"secret");
+//! assert_eq!(pseudonymizer.pseudonymize(&rf), rf.pseudonymize(b"secret"));
+//! assert_eq!(pseudonymizer.pseudonymize(&rf).len(), 64);
+//! ```
+
+use std::fmt::Write;
+
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+
+use crate::RfCreditorReference;
+
+/// `Pseudonymizer` creates pseudonyms of creditor references with a key
+///
+/// Prefer it over [`RfCreditorReference::pseudonymize`] for batches,
+/// because the key is only prepared once.
+#[derive(Clone, Debug)]
+pub struct Pseudonymizer {
+    mac: Hmac<Sha256>,
+}
+
+impl Pseudonymizer {
+    /// Creates [`Pseudonymizer`] with secret `key`
+    ///
+    /// # Panics
+    ///
+    /// HMAC takes keys of any length, so no `key` panics.
+    ///
+    #[must_use]
+    pub fn new(key: &[u8]) -> Self {
+        Self {
+            mac: Hmac::new_from_slice(key).expect("HMAC accepts keys of any length"),
+        }
+    }
+
+    /// Creates the pseudonym of `rf`
+    #[must_use]
+    pub fn pseudonymize(&self, rf: &RfCreditorReference<'_>) -> String {
+        let mut mac = self.mac.clone();
+        mac.update(rf.as_electronic_str().to_ascii_uppercase().as_bytes());
+
+        let mut pseudonym = String::with_capacity(64);
+        for b in mac.finalize().into_bytes() {
+            let _ = write!(pseudonym, "{:02x}", b);
+        }
+        pseudonym
+    }
+
+    /// Creates the pseudonyms of all references of `iter` in order
+    pub fn pseudonymize_all<'a, I>(&self, iter: I) -> Vec<String>
+    where
+        I: IntoIterator<Item = &'a RfCreditorReference<'a>>,
+    {
+        iter.into_iter().map(|rf| self.pseudonymize(rf)).collect()
+    }
+}
+
+impl RfCreditorReference<'_> {
+    /// Creates the pseudonym of the reference with secret `key`
+    ///
+    /// See [`Pseudonymizer`].
+    ///
+    /// Available with feature `pseudonymize`.
+    ///
+    #[must_use]
+    pub fn pseudonymize(&self, key: &[u8]) -> String {
+        Pseudonymizer::new(key).pseudonymize(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pseudonymize_test() {
+        let rf = RfCreditorReference::new("539007547034");
+        assert_eq!(
+            rf.pseudonymize(b"secret"),
+            "0c29bad0bddaa109a4c9d128dcff79d9297a6235a965f8f1ffcea02e9e80128e"
+        );
+        assert_ne!(rf.pseudonymize(b"secret"), rf.pseudonymize(b"other"));
+
+        // case and format of the input are not relevant
+        let lowercase = RfCreditorReference::parse_str("RF63 abcd 0754 efgh").unwrap();
+        assert_eq!(
+            lowercase.pseudonymize(b"secret"),
+            "41d9476c65271aca4f09aae5fedc814f937bc990ab5b2f6db4908ab7de3c1bfb"
+        );
+        let uppercase = RfCreditorReference::parse_str("RF63ABCD0754EFGH").unwrap();
+        assert_eq!(
+            uppercase.pseudonymize(b"secret"),
+            lowercase.pseudonymize(b"secret")
+        );
+    }
+
+    #[test]
+    fn pseudonymize_all_test() {
+        let refs = [
+            RfCreditorReference::new("539007547034"),
+            RfCreditorReference::new("2348231"),
+        ];
+        let pseudonymizer = Pseudonymizer::new(b"secret");
+        let pseudonyms = pseudonymizer.pseudonymize_all(&refs);
+        assert_eq!(pseudonyms.len(), 2);
+        assert_eq!(pseudonyms[0], refs[0].pseudonymize(b"secret"));
+        assert_eq!(pseudonyms[1], refs[1].pseudonymize(b"secret"));
+    }
+}
@@ -0,0 +1,132 @@
+//! Random generation of [`RfCreditorReference`]s
+//!
+//! Available with feature `rand`.
+
+use rand::Rng;
+
+use crate::{
+    entropy::RandomConfig, layout::Charset, ParseError, RfCreditorReference, RfReferenceBuilder,
+};
+
+/// Characters of generated bodies, digits followed by uppercase letters
+const CHARS: &[u8] = b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZ";
+
+impl RfCreditorReference<'_> {
+    /// Generates a random [`RfCreditorReference`] with a body of `len`
+    /// characters of `charset`
+    ///
+    /// Letters are generated in uppercase. Useful for test data, demos
+    /// and one-off payment links.
+    ///
+    /// Available with feature `rand`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use iso_11649::{layout::Charset, RfCreditorReference};
+    ///
+    /// let rf = RfCreditorReference::random(12, Charset::Numeric, &mut rand::thread_rng()).unwrap();
+    ///
+    /// assert!(iso_11649::is_valid(&rf.to_string()));
+    /// assert_eq!(rf.as_electronic_str().len(), 16);
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Results in [`ParseError::TooShort`] or [`ParseError::BodyTooLong`] when
+    /// `len` is not within 1 to 21.
+    ///
+    pub fn random(
+        len: usize,
+        charset: Charset,
+        rng: &mut impl Rng,
+    ) -> Result<RfCreditorReference<'static>, ParseError> {
+        let chars = match charset {
+            Charset::Numeric => &CHARS[..10],
+            Charset::Alphabetic => &CHARS[10..],
+            Charset::Alphanumeric => CHARS,
+        };
+
+        let body = (0..len)
+            .map(|_| char::from(chars[rng.gen_range(0..chars.len())]))
+            .collect::<String>();
+
+        RfReferenceBuilder::new().segment(body).build()
+    }
+}
+
+impl RandomConfig {
+    /// Generates a random [`RfCreditorReference`] with charset and body
+    /// length of the config
+    ///
+    /// See [`RfCreditorReference::random`].
+    ///
+    /// Available with feature `rand`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use iso_11649::entropy::RandomConfig;
+    ///
+    /// let config = RandomConfig::for_collision_probability(1_000_000, 1e-6).unwrap();
+    /// let rf = config.generate(&mut rand::thread_rng());
+    ///
+    /// assert_eq!(rf.as_electronic_str().len(), 22);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Not with the valid body lengths of [`RandomConfig`]s.
+    ///
+    #[must_use]
+    pub fn generate(self, rng: &mut impl Rng) -> RfCreditorReference<'static> {
+        RfCreditorReference::random(self.body_len(), self.charset(), rng)
+            .expect("body length of config is valid")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn random_test() {
+        let mut rng = rand::thread_rng();
+        for len in 1..=21 {
+            let rf = RfCreditorReference::random(len, Charset::Numeric, &mut rng).unwrap();
+            assert_eq!(rf.as_electronic_str().len(), len + 4);
+            assert!(crate::is_valid(rf.as_electronic_str()));
+            assert!(rf.as_electronic_str()[4..]
+                .chars()
+                .all(|c| c.is_ascii_digit()));
+        }
+
+        for _ in 0..100 {
+            let rf = RfCreditorReference::random(8, Charset::Alphabetic, &mut rng).unwrap();
+            assert!(crate::is_valid(rf.as_electronic_str()));
+            assert!(rf.as_electronic_str()[4..]
+                .chars()
+                .all(|c| c.is_ascii_uppercase()));
+        }
+
+        assert!(matches!(
+            RfCreditorReference::random(0, Charset::Alphanumeric, &mut rng),
+            Err(ParseError::TooShort { .. })
+        ));
+        assert!(matches!(
+            RfCreditorReference::random(22, Charset::Numeric, &mut rng),
+            Err(ParseError::BodyTooLong { .. })
+        ));
+    }
+
+    #[test]
+    fn generate_test() {
+        let mut rng = rand::thread_rng();
+        let config = RandomConfig::with_charset(Charset::Alphabetic, 1000, 1e-3).unwrap();
+        for _ in 0..100 {
+            let rf = config.generate(&mut rng);
+            assert!(crate::is_valid(rf.as_electronic_str()));
+            assert_eq!(rf.as_electronic_str().len(), config.body_len() + 4);
+        }
+    }
+}
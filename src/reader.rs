@@ -0,0 +1,169 @@
+//! Line-oriented validation of files and other readers
+//!
+//! [`validate_reader`] streams through a reader line by line, so large
+//! exports are validated without loading them into memory.
+//!
+//! # Examples
+//!
+//! ```rust
+//! let export = "RF18 5390 0754 7034\n\nRF19 5390 0754 7034\r\nRF71 2348 231\n";
+//!
+//! let failures = iso_11649::validate_reader(export.as_bytes())
+//!     .filter(|(_, result)| result.is_err())
+//!     .map(|(line, _)| line)
+//!     .collect::<Vec<_>>();
+//!
+//! assert_eq!(failures, [3]);
+//! ```
+
+use std::io::{self, BufRead};
+
+use crate::{ParseError, RfCreditorReference};
+
+/// Validates each line of `reader` like [`RfCreditorReference::parse_str`]
+/// and yields the results with the line numbers starting at 1
+///
+/// Blank lines are skipped. Line endings `\n` and `\r\n` are no part of
+/// the references. Lines with invalid UTF-8 result in
+/// [`ParseError::InvalidCharacter`] at the first invalid byte.
+///
+/// The iteration ends at the first I/O error, which is kept by
+/// [`ReaderValidation::io_error`].
+///
+/// See the [module](crate::reader) for an example.
+pub fn validate_reader<R: BufRead>(reader: R) -> ReaderValidation<R> {
+    ReaderValidation {
+        reader,
+        line: Vec::new(),
+        line_number: 0,
+        io_error: None,
+    }
+}
+
+/// `ReaderValidation` iterates over the validated lines of a reader
+///
+/// Created by [`validate_reader`].
+#[derive(Debug)]
+pub struct ReaderValidation<R> {
+    reader: R,
+    /// Buffer of the current line
+    line: Vec<u8>,
+    /// Number of the current line
+    line_number: usize,
+    /// I/O error ending the iteration
+    io_error: Option<io::Error>,
+}
+
+impl<R> ReaderValidation<R> {
+    /// I/O error ending the iteration early
+    ///
+    /// Check this after the iteration to tell an early end from the end of
+    /// the reader.
+    #[must_use]
+    pub fn io_error(&self) -> Option<&io::Error> {
+        self.io_error.as_ref()
+    }
+
+    /// Number of the last read line
+    #[must_use]
+    pub fn line_number(&self) -> usize {
+        self.line_number
+    }
+}
+
+impl<R: BufRead> Iterator for ReaderValidation<R> {
+    type Item = (usize, Result<RfCreditorReference<'static>, ParseError>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.io_error.is_some() {
+            return None;
+        }
+
+        loop {
+            self.line.clear();
+            match self.reader.read_until(b'\n', &mut self.line) {
+                Ok(0) => return None,
+                Ok(_) => self.line_number += 1,
+                Err(err) => {
+                    self.io_error = Some(err);
+                    return None;
+                }
+            }
+
+            let line = String::from_utf8_lossy(&self.line);
+            let line = line.trim_end_matches(&['\n', '\r'][..]);
+            if !line.trim().is_empty() {
+                return Some((self.line_number, RfCreditorReference::parse_str(line)));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{BufReader, Read};
+
+    use super::*;
+
+    /// Reader failing after its data
+    struct Failing<'a>(&'a [u8]);
+
+    impl Read for Failing<'_> {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            if self.0.is_empty() {
+                Err(io::Error::new(io::ErrorKind::Other, "broken pipe"))
+            } else {
+                self.0.read(buf)
+            }
+        }
+    }
+
+    #[test]
+    fn validate_reader_test() {
+        let export = "RF18 5390 0754 7034\n \nRF18 5390 0754 703_\r\nRF71 2348 231";
+        let results = validate_reader(export.as_bytes()).collect::<Vec<_>>();
+
+        assert_eq!(results.len(), 3);
+        assert_eq!(results[0].0, 1);
+        assert!(results[0].1.is_ok());
+        assert_eq!(results[1].0, 3);
+        assert!(matches!(
+            results[1].1,
+            Err(ParseError::InvalidCharacter { position: 18, .. })
+        ));
+        assert_eq!(results[2].0, 4);
+        assert!(results[2].1.is_ok());
+    }
+
+    #[test]
+    fn invalid_utf8_test() {
+        let results =
+            validate_reader(&b"RF18 5390 0754 \xff\nRF71 2348 231\n"[..]).collect::<Vec<_>>();
+        assert!(matches!(
+            results[0],
+            (
+                1,
+                Err(ParseError::InvalidCharacter {
+                    character: char::REPLACEMENT_CHARACTER,
+                    position: 15,
+                    ..
+                })
+            )
+        ));
+        assert!(matches!(results[1], (2, Ok(_))));
+    }
+
+    #[test]
+    fn io_error_test() {
+        let mut validation = validate_reader(BufReader::new(Failing(b"RF18 5390 0754 7034\n")));
+        assert!(validation.next().is_some());
+        assert!(validation.next().is_none());
+        assert!(validation.next().is_none());
+        assert_eq!(validation.line_number(), 1);
+        assert_eq!(validation.io_error().unwrap().to_string(), "broken pipe");
+
+        let mut validation = validate_reader("".as_bytes());
+        assert!(validation.next().is_none());
+        assert!(validation.io_error().is_none());
+    }
+}
@@ -0,0 +1,73 @@
+//! Conversions of [`RfCreditorReference`]s for
+//! [`redis`](https://docs.rs/redis) commands
+//!
+//! Available with feature `redis`.
+//!
+//! References are written as arguments in canonical electronic format.
+//! Read values are validated like [`RfCreditorReference::parse_str`].
+
+use ::redis::{ErrorKind, FromRedisValue, RedisResult, RedisWrite, ToRedisArgs, Value};
+
+use crate::RfCreditorReference;
+
+impl ToRedisArgs for RfCreditorReference<'_> {
+    fn write_redis_args<W: ?Sized + RedisWrite>(&self, out: &mut W) {
+        out.write_arg(self.as_electronic_str().as_bytes());
+    }
+}
+
+/// Invalid references result in an error of kind [`ErrorKind::TypeError`]
+impl FromRedisValue for RfCreditorReference<'_> {
+    fn from_redis_value(v: &Value) -> RedisResult<Self> {
+        let reference = String::from_redis_value(v)?;
+        RfCreditorReference::parse_str(&reference).map_err(|err| {
+            (
+                ErrorKind::TypeError,
+                "invalid creditor reference",
+                err.to_string(),
+            )
+                .into()
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn to_redis_args_test() {
+        let rf = RfCreditorReference::parse_str("RF18 5390 0754 7034").unwrap();
+        assert_eq!(rf.to_redis_args(), [b"RF18539007547034".to_vec()]);
+        assert_eq!(
+            vec![rf.clone(), RfCreditorReference::new("2348231")].to_redis_args(),
+            [b"RF18539007547034".to_vec(), b"RF712348231".to_vec()]
+        );
+    }
+
+    #[test]
+    fn from_redis_value_test() {
+        let value = Value::BulkString(b"RF712348231".to_vec());
+        assert_eq!(
+            RfCreditorReference::from_redis_value(&value).unwrap(),
+            RfCreditorReference::new("2348231")
+        );
+
+        let members = Value::Array(vec![
+            Value::BulkString(b"RF18539007547034".to_vec()),
+            Value::BulkString(b"RF712348231".to_vec()),
+        ]);
+        assert_eq!(
+            Vec::<RfCreditorReference>::from_redis_value(&members)
+                .unwrap()
+                .len(),
+            2
+        );
+
+        let err =
+            RfCreditorReference::from_redis_value(&Value::BulkString(b"RF702348231".to_vec()))
+                .unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::TypeError);
+        assert!(RfCreditorReference::from_redis_value(&Value::Nil).is_err());
+    }
+}
@@ -0,0 +1,174 @@
+//! Registry of issued creditor references
+//!
+//! [`ReferenceRegistry`] guards against issuing the same reference twice,
+//! e.g. within an invoice batch job.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use iso_11649::{registry::ReferenceRegistry, RfCreditorReference};
+//!
+//! let mut registry = ReferenceRegistry::new();
+//!
+//! assert!(registry.register(&RfCreditorReference::new("539007547034")));
+//! assert!(!registry.register(&RfCreditorReference::new("5390 0754 7034")));
+//! assert_eq!(registry.len(), 1);
+//! ```
+
+use std::collections::{HashSet, VecDeque};
+
+use crate::RfCreditorReference;
+
+/// `ReferenceRegistry` tracks issued creditor references and rejects
+/// duplicates
+///
+/// References are compared in electronic format ignoring case. With a
+/// capacity the oldest references are evicted when the registry is full.
+#[derive(Clone, Debug, Default)]
+pub struct ReferenceRegistry {
+    /// Maximum number of tracked references
+    capacity: Option<usize>,
+    /// Tracked references
+    issued: HashSet<String>,
+    /// Tracked references in order of registration for eviction
+    order: VecDeque<String>,
+}
+
+impl ReferenceRegistry {
+    /// Creates an empty [`ReferenceRegistry`] without capacity limit
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates an empty [`ReferenceRegistry`] tracking at most `capacity`
+    /// references
+    ///
+    /// The oldest reference is evicted when a new reference is registered
+    /// in a full registry.
+    #[must_use]
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            capacity: Some(capacity),
+            issued: HashSet::with_capacity(capacity),
+            order: VecDeque::with_capacity(capacity),
+        }
+    }
+
+    /// Registers `rf`
+    ///
+    /// Returns `false` if `rf` is already registered.
+    #[must_use = "duplicates are reported by the return value"]
+    pub fn register(&mut self, rf: &RfCreditorReference<'_>) -> bool {
+        let key = Self::key(rf);
+        if self.issued.contains(&key) {
+            return false;
+        }
+
+        if let Some(capacity) = self.capacity {
+            if capacity == 0 {
+                return true;
+            }
+            while self.order.len() >= capacity {
+                if let Some(evicted) = self.order.pop_front() {
+                    let _ = self.issued.remove(&evicted);
+                }
+            }
+            self.order.push_back(key.clone());
+        }
+        let _ = self.issued.insert(key);
+        true
+    }
+
+    /// Registers all references of `iter`
+    ///
+    /// Returns the number of duplicates, which are not registered again.
+    pub fn load<'a, I>(&mut self, iter: I) -> usize
+    where
+        I: IntoIterator<Item = &'a RfCreditorReference<'a>>,
+    {
+        iter.into_iter().filter(|rf| !self.register(rf)).count()
+    }
+
+    /// Checks if `rf` is registered
+    #[must_use]
+    pub fn contains(&self, rf: &RfCreditorReference<'_>) -> bool {
+        self.issued.contains(&Self::key(rf))
+    }
+
+    /// Number of registered references
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.issued.len()
+    }
+
+    /// Checks if no reference is registered
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.issued.is_empty()
+    }
+
+    /// Removes all registered references
+    pub fn clear(&mut self) {
+        self.issued.clear();
+        self.order.clear();
+    }
+
+    /// Key of `rf` in electronic format ignoring case
+    fn key(rf: &RfCreditorReference<'_>) -> String {
+        rf.as_electronic_str().to_ascii_uppercase()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn register_test() {
+        let mut registry = ReferenceRegistry::new();
+        let rf = RfCreditorReference::parse_str("RF63 ABCD 0754 EFGH").unwrap();
+        assert!(registry.is_empty());
+        assert!(registry.register(&rf));
+        assert!(!registry.register(&rf));
+        assert!(!registry.register(&RfCreditorReference::parse_str("RF63abcd0754efgh").unwrap()));
+        assert!(registry.contains(&rf));
+        assert_eq!(registry.len(), 1);
+
+        registry.clear();
+        assert!(!registry.contains(&rf));
+    }
+
+    #[test]
+    fn load_test() {
+        let refs = ["1", "2", "3", "2", "1"]
+            .iter()
+            .map(|body| RfCreditorReference::new(body))
+            .collect::<Vec<_>>();
+
+        let mut registry = ReferenceRegistry::new();
+        assert_eq!(registry.load(&refs), 2);
+        assert_eq!(registry.len(), 3);
+        assert_eq!(registry.load(&refs[..1]), 1);
+    }
+
+    #[test]
+    fn capacity_test() {
+        let refs = ["1", "2", "3"]
+            .iter()
+            .map(|body| RfCreditorReference::new(body))
+            .collect::<Vec<_>>();
+
+        let mut registry = ReferenceRegistry::with_capacity(2);
+        assert_eq!(registry.load(&refs), 0);
+        assert_eq!(registry.len(), 2);
+        assert!(!registry.contains(&refs[0]));
+        assert!(registry.contains(&refs[1]));
+        assert!(registry.contains(&refs[2]));
+
+        let mut registry = ReferenceRegistry::with_capacity(0);
+        assert!(registry.register(&refs[0]));
+        assert!(registry.register(&refs[0]));
+        assert!(registry.is_empty());
+    }
+}
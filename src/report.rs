@@ -0,0 +1,396 @@
+//! Structured reports of validated reference lists
+//!
+//! [`validate_iter`] validates all references of an iterator and
+//! aggregates the results into a [`ValidationReport`].
+//!
+//! # Examples
+//!
+//! ```rust
+//! let report = iso_11649::validate_iter(&[
+//!     "RF18 5390 0754 7034",
+//!     "RF19 5390 0754 7034",
+//!     "RF18 5390 0754 703_",
+//!     "RF71 2348 231",
+//! ]);
+//!
+//! assert_eq!((report.valid(), report.invalid()), (2, 2));
+//! assert_eq!(report.failures(), [1, 2]);
+//! assert_eq!(report.count("ISO11649-E003-INVALID-CHECKSUM"), 1);
+//! ```
+//!
+//! With feature `serde` the report serializes to the totals, the counts
+//! of the errors and the failing items with their reasons. Feature `json`
+//! exports it as JSON with [`ValidationReport::to_json`] and all items as
+//! NDJSON with [`ValidationReport::write_ndjson`].
+
+use std::collections::BTreeMap;
+
+use crate::{ParseError, RfCreditorReference};
+
+/// Validates all references of `iter` like
+/// [`RfCreditorReference::parse_str`] and aggregates the results
+///
+/// See the [module](crate::report) for an example.
+pub fn validate_iter<I>(iter: I) -> ValidationReport
+where
+    I: IntoIterator,
+    I::Item: AsRef<str>,
+{
+    iter.into_iter()
+        .map(|reference| RfCreditorReference::parse_str(reference.as_ref()))
+        .collect()
+}
+
+/// `ValidationReport` holds the results of validated references in order
+/// with the indices of the failures and the counts of the errors by
+/// [`ParseError::code`]
+///
+/// With feature `serde` it serializes like
+///
+/// ```json
+/// {
+///   "total": 2,
+///   "valid": 1,
+///   "invalid": 1,
+///   "counts": { "ISO11649-E003-INVALID-CHECKSUM": 1 },
+///   "failures": [
+///     {
+///       "index": 1,
+///       "input": "RF19 5390 0754 7034",
+///       "code": "ISO11649-E003-INVALID-CHECKSUM",
+///       "message": "checksum 19 does not match expected 18 [RF19 5390 0754 7034]"
+///     }
+///   ]
+/// }
+/// ```
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct ValidationReport {
+    /// Results in order of the references
+    results: Vec<Result<RfCreditorReference<'static>, ParseError>>,
+    /// Indices of the failed references
+    failures: Vec<usize>,
+    /// Counts of errors by code
+    counts: BTreeMap<&'static str, usize>,
+}
+
+impl ValidationReport {
+    /// Results in order of the references
+    pub fn results(&self) -> &[Result<RfCreditorReference<'static>, ParseError>] {
+        &self.results
+    }
+
+    /// Takes the results in order of the references
+    #[must_use]
+    pub fn into_results(self) -> Vec<Result<RfCreditorReference<'static>, ParseError>> {
+        self.results
+    }
+
+    /// Number of validated references
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.results.len()
+    }
+
+    /// Checks if no reference is validated
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.results.is_empty()
+    }
+
+    /// Number of valid references
+    #[must_use]
+    pub fn valid(&self) -> usize {
+        self.results.len() - self.failures.len()
+    }
+
+    /// Number of invalid references
+    #[must_use]
+    pub fn invalid(&self) -> usize {
+        self.failures.len()
+    }
+
+    /// Checks if all references are valid
+    #[must_use]
+    pub fn is_all_valid(&self) -> bool {
+        self.failures.is_empty()
+    }
+
+    /// Indices of the invalid references in ascending order
+    #[must_use]
+    pub fn failures(&self) -> &[usize] {
+        &self.failures
+    }
+
+    /// Iterates over the indices and errors of the invalid references
+    pub fn errors(&self) -> impl Iterator<Item = (usize, &ParseError)> {
+        self.failures
+            .iter()
+            .filter_map(move |&index| match &self.results[index] {
+                Err(err) => Some((index, err)),
+                Ok(_) => None,
+            })
+    }
+
+    /// Counts of the errors by [`ParseError::code`]
+    #[must_use]
+    pub fn counts(&self) -> &BTreeMap<&'static str, usize> {
+        &self.counts
+    }
+
+    /// Count of the errors with [`ParseError::code`] `code`
+    #[must_use]
+    pub fn count(&self, code: &str) -> usize {
+        self.counts.get(code).copied().unwrap_or_default()
+    }
+
+    /// Serializes the report to JSON
+    ///
+    /// Available with feature `json`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// let report = iso_11649::validate_iter(&["RF18 5390 0754 7034", "RF18"]);
+    ///
+    /// assert_eq!(
+    ///     report.to_json(),
+    ///     r#"{"total":2,"valid":1,"invalid":1,"counts":{"ISO11649-E006-TOO-SHORT":1},"failures":[{"index":1,"input":"RF18","code":"ISO11649-E006-TOO-SHORT","message":"reference is too short with length 4 (min 5) [RF18]"}]}"#
+    /// );
+    /// ```
+    ///
+    #[cfg(feature = "json")]
+    #[must_use]
+    pub fn to_json(&self) -> String {
+        // serializing strings and numbers never fails
+        serde_json::to_string(self).unwrap_or_default()
+    }
+
+    /// Writes all items of the report as NDJSON to `w`, one JSON object per
+    /// line
+    ///
+    /// Valid items have the reference in electronic format, failing items
+    /// the input and the reason.
+    ///
+    /// Available with feature `json`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// let report = iso_11649::validate_iter(&["RF18 5390 0754 7034", "RF18"]);
+    /// let mut ndjson = Vec::new();
+    /// report.write_ndjson(&mut ndjson).unwrap();
+    ///
+    /// assert_eq!(
+    ///     String::from_utf8(ndjson).unwrap(),
+    ///     r#"{"index":0,"valid":true,"reference":"RF18539007547034"}
+    /// {"index":1,"valid":false,"input":"RF18","code":"ISO11649-E006-TOO-SHORT","message":"reference is too short with length 4 (min 5) [RF18]"}
+    /// "#
+    /// );
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Results in the [`std::io::Error`] of writing to `w`.
+    ///
+    #[cfg(feature = "json")]
+    pub fn write_ndjson(&self, mut w: impl std::io::Write) -> std::io::Result<()> {
+        for (index, result) in self.results.iter().enumerate() {
+            let item = match result {
+                Ok(rf) => Item {
+                    index,
+                    valid: true,
+                    reference: Some(rf.as_electronic_str()),
+                    reason: None,
+                },
+                Err(err) => Item {
+                    index,
+                    valid: false,
+                    reference: None,
+                    reason: Some(Reason::new(err)),
+                },
+            };
+            serde_json::to_writer(&mut w, &item)?;
+            w.write_all(b"\n")?;
+        }
+        w.flush()
+    }
+
+    /// Appends the `result` of the next reference
+    pub(crate) fn push(&mut self, result: Result<RfCreditorReference<'static>, ParseError>) {
+        if let Err(err) = &result {
+            self.failures.push(self.results.len());
+            *self.counts.entry(err.code()).or_default() += 1;
+        }
+        self.results.push(result);
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for ValidationReport {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeStruct;
+
+        let failures = self
+            .errors()
+            .map(|(index, err)| Failure::new(index, err))
+            .collect::<Vec<_>>();
+
+        let mut report = serializer.serialize_struct("ValidationReport", 5)?;
+        report.serialize_field("total", &self.len())?;
+        report.serialize_field("valid", &self.valid())?;
+        report.serialize_field("invalid", &self.invalid())?;
+        report.serialize_field("counts", &self.counts)?;
+        report.serialize_field("failures", &failures)?;
+        report.end()
+    }
+}
+
+/// Failing item of a serialized [`ValidationReport`]
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize)]
+struct Failure<'r> {
+    index: usize,
+    #[serde(flatten)]
+    reason: Reason<'r>,
+}
+
+#[cfg(feature = "serde")]
+impl<'r> Failure<'r> {
+    /// Creates [`Failure`] of the item at `index` with `err`
+    fn new(index: usize, err: &'r ParseError) -> Self {
+        Self {
+            index,
+            reason: Reason::new(err),
+        }
+    }
+}
+
+/// Reason of a failing item of a serialized [`ValidationReport`]
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize)]
+struct Reason<'r> {
+    input: &'r str,
+    code: &'static str,
+    message: String,
+}
+
+#[cfg(feature = "serde")]
+impl<'r> Reason<'r> {
+    /// Creates [`Reason`] of `err`
+    fn new(err: &'r ParseError) -> Self {
+        Self {
+            input: err.input(),
+            code: err.code(),
+            message: err.to_string(),
+        }
+    }
+}
+
+/// Item of the NDJSON export of a [`ValidationReport`]
+#[cfg(feature = "json")]
+#[derive(serde::Serialize)]
+struct Item<'r> {
+    index: usize,
+    valid: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    reference: Option<&'r str>,
+    #[serde(flatten)]
+    reason: Option<Reason<'r>>,
+}
+
+impl FromIterator<Result<RfCreditorReference<'static>, ParseError>> for ValidationReport {
+    fn from_iter<I>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = Result<RfCreditorReference<'static>, ParseError>>,
+    {
+        let mut report = Self::default();
+        for result in iter {
+            report.push(result);
+        }
+        report
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn validate_iter_test() {
+        let references = vec![
+            "RF18 5390 0754 7034".to_string(),
+            "RF19 5390 0754 7034".to_string(),
+            "RF18 5390 0754 703_".to_string(),
+            "RF20 5390 0754 7034".to_string(),
+            "RF".to_string(),
+        ];
+        let report = validate_iter(&references);
+
+        assert_eq!(report.len(), 5);
+        assert_eq!((report.valid(), report.invalid()), (1, 4));
+        assert!(!report.is_all_valid());
+        assert_eq!(report.failures(), [1, 2, 3, 4]);
+        assert_eq!(
+            report.counts().iter().collect::<Vec<_>>(),
+            [
+                (&"ISO11649-E001-INVALID-CHAR", &1),
+                (&"ISO11649-E003-INVALID-CHECKSUM", &2),
+                (&"ISO11649-E006-TOO-SHORT", &1)
+            ]
+        );
+        assert_eq!(report.count("ISO11649-E005-TOO-LONG"), 0);
+        assert_eq!(
+            report.errors().map(|(index, _)| index).collect::<Vec<_>>(),
+            report.failures()
+        );
+        assert!(report.results()[0].is_ok());
+        assert_eq!(report.into_results().len(), 5);
+    }
+
+    #[test]
+    fn empty_test() {
+        let report = validate_iter(Vec::<&str>::new());
+        assert!(report.is_empty());
+        assert!(report.is_all_valid());
+        assert_eq!(report, ValidationReport::default());
+    }
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn json_test() {
+        let report = validate_iter(&["RF18 5390 0754 7034", "RF19 5390 0754 7034", "RF"]);
+        let json = serde_json::to_value(&report).unwrap();
+        assert_eq!(json["total"], 3);
+        assert_eq!(json["valid"], 1);
+        assert_eq!(json["invalid"], 2);
+        assert_eq!(json["counts"]["ISO11649-E003-INVALID-CHECKSUM"], 1);
+        assert_eq!(json["failures"][0]["index"], 1);
+        assert_eq!(json["failures"][1]["input"], "RF");
+        assert_eq!(json["failures"][1]["code"], "ISO11649-E006-TOO-SHORT");
+        assert_eq!(
+            serde_json::from_str::<serde_json::Value>(&report.to_json()).unwrap(),
+            json
+        );
+
+        let mut ndjson = Vec::new();
+        report.write_ndjson(&mut ndjson).unwrap();
+        let lines = String::from_utf8(ndjson).unwrap();
+        let items = lines
+            .lines()
+            .map(|line| serde_json::from_str::<serde_json::Value>(line).unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(items.len(), 3);
+        assert_eq!(items[0]["reference"], "RF18539007547034");
+        assert!(items[0].get("code").is_none());
+        assert_eq!(items[1]["valid"], false);
+        assert_eq!(
+            lines.lines().nth(1).unwrap().matches("\"index\"").count(),
+            1
+        );
+        assert!(items[1].get("reference").is_none());
+        assert_eq!(
+            items[2]["message"],
+            "reference is too short with length 2 (min 5) [RF]"
+        );
+    }
+}
@@ -0,0 +1,192 @@
+//! Zero-copy archives of [`RfCreditorReference`]s with
+//! [`rkyv`](https://docs.rs/rkyv)
+//!
+//! Available with feature `rkyv`.
+//!
+//! A reference is archived as `ArchivedString` in electronic format.
+//! [`access`] validates archives with the checked `rkyv::access` and the
+//! references with [`verify_bytes`], so validated archives are read as
+//! [`ArchivedRfCreditorReference`]s without parsing the references again.
+
+use std::{convert::TryFrom, fmt};
+
+use ::rkyv::{
+    rancor::{Fallible, Source},
+    ser::Writer,
+    string::{ArchivedString, StringResolver},
+    Archive, Deserialize, Place, Serialize,
+};
+
+use crate::{verify_bytes, ParseError, RfCreditorReference};
+
+/// `ArchivedRfCreditorReference` borrows a validated archived
+/// [`RfCreditorReference`]
+///
+/// It is created by [`access`] or from an `ArchivedString` of a validated
+/// archive, which is verified to be a valid reference in electronic format.
+///
+/// # Examples
+///
+/// ```rust
+/// use iso_11649::RfCreditorReference;
+/// use rkyv::rancor::Error;
+///
+/// let rf = RfCreditorReference::new("539007547034");
+/// let bytes = rkyv::to_bytes::<Error>(&rf).unwrap();
+///
+/// let archived = iso_11649::rkyv::access::<Error>(&bytes).unwrap();
+/// assert_eq!(archived.as_str(), "RF18539007547034");
+/// assert_eq!(archived.deserialize().unwrap(), rf);
+/// ```
+#[derive(Clone, Copy, Eq, Hash, PartialEq)]
+pub struct ArchivedRfCreditorReference<'a>(&'a ArchivedString);
+
+impl<'a> ArchivedRfCreditorReference<'a> {
+    /// Borrows the archived reference in electronic format
+    #[must_use]
+    pub fn as_str(&self) -> &'a str {
+        self.0.as_str()
+    }
+
+    /// Deserializes the reference with the validation of
+    /// [`RfCreditorReference::parse_str`]
+    ///
+    /// # Errors
+    ///
+    /// [`ParseError`] of [`RfCreditorReference::parse_str`]
+    pub fn deserialize(&self) -> Result<RfCreditorReference<'static>, ParseError> {
+        RfCreditorReference::parse_str(self.as_str())
+    }
+}
+
+/// Verifies the archived string like [`verify_bytes`]
+impl<'a> TryFrom<&'a ArchivedString> for ArchivedRfCreditorReference<'a> {
+    type Error = ParseError;
+
+    fn try_from(archived: &'a ArchivedString) -> Result<Self, Self::Error> {
+        verify_bytes(archived.as_bytes())?;
+        Ok(Self(archived))
+    }
+}
+
+impl AsRef<str> for ArchivedRfCreditorReference<'_> {
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl fmt::Debug for ArchivedRfCreditorReference<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self.as_str(), f)
+    }
+}
+
+/// Formats the electronic format.
+impl fmt::Display for ArchivedRfCreditorReference<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad(self.as_str())
+    }
+}
+
+impl PartialEq<RfCreditorReference<'_>> for ArchivedRfCreditorReference<'_> {
+    fn eq(&self, other: &RfCreditorReference<'_>) -> bool {
+        self.as_str() == other.as_electronic_str()
+    }
+}
+
+/// Accesses the archived reference at the root of `bytes`
+///
+/// The archive is validated by `rkyv::access` and the reference like
+/// [`verify_bytes`].
+///
+/// # Errors
+///
+/// `E` of an invalid archive or of the [`ParseError`] of an invalid
+/// reference
+pub fn access<E: Source>(bytes: &[u8]) -> Result<ArchivedRfCreditorReference<'_>, E> {
+    let archived = ::rkyv::access::<ArchivedString, E>(bytes)?;
+    ArchivedRfCreditorReference::try_from(archived).map_err(Source::new)
+}
+
+impl Archive for RfCreditorReference<'_> {
+    type Archived = ArchivedString;
+    type Resolver = StringResolver;
+
+    fn resolve(&self, resolver: Self::Resolver, out: Place<Self::Archived>) {
+        ArchivedString::resolve_from_str(self.as_electronic_str(), resolver, out);
+    }
+}
+
+impl<S> Serialize<S> for RfCreditorReference<'_>
+where
+    S: Fallible + Writer + ?Sized,
+    S::Error: Source,
+{
+    fn serialize(&self, serializer: &mut S) -> Result<Self::Resolver, S::Error> {
+        ArchivedString::serialize_from_str(self.as_electronic_str(), serializer)
+    }
+}
+
+/// Deserializes with the validation of [`RfCreditorReference::parse_str`]
+impl<'a, D> Deserialize<RfCreditorReference<'a>, D> for ArchivedString
+where
+    D: Fallible + ?Sized,
+    D::Error: Source,
+{
+    fn deserialize(&self, _deserializer: &mut D) -> Result<RfCreditorReference<'a>, D::Error> {
+        RfCreditorReference::parse_str(self.as_str()).map_err(Source::new)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ::rkyv::rancor::Error;
+
+    use super::*;
+
+    #[test]
+    fn archive_test() {
+        let references = vec![
+            RfCreditorReference::new("539007547034"),
+            RfCreditorReference::new("2348231"),
+        ];
+        let bytes = ::rkyv::to_bytes::<Error>(&references).unwrap();
+
+        let archived =
+            ::rkyv::access::<::rkyv::vec::ArchivedVec<ArchivedString>, Error>(&bytes).unwrap();
+        let archived = archived
+            .iter()
+            .map(ArchivedRfCreditorReference::try_from)
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        assert_eq!(archived.len(), 2);
+        assert_eq!(archived[0].as_str(), "RF18539007547034");
+        assert_eq!(archived[1].to_string(), "RF712348231");
+        assert_eq!(archived[1], references[1]);
+
+        let deserialized = ::rkyv::from_bytes::<Vec<RfCreditorReference>, Error>(&bytes).unwrap();
+        assert_eq!(deserialized, references);
+    }
+
+    #[test]
+    fn access_test() {
+        let rf = RfCreditorReference::new("2348231");
+        let bytes = ::rkyv::to_bytes::<Error>(&rf).unwrap();
+        let archived = access::<Error>(&bytes).unwrap();
+        assert_eq!(archived, rf);
+        assert_eq!(archived.deserialize(), Ok(rf));
+
+        assert!(access::<Error>(&bytes[1..]).is_err());
+    }
+
+    #[test]
+    fn invalid_archive_test() {
+        for reference in ["RF19539007547034", "RF18 5390 0754 7034", "RF"] {
+            let bytes = ::rkyv::to_bytes::<Error>(&reference.to_string()).unwrap();
+            assert!(access::<Error>(&bytes).is_err(), "{}", reference);
+        }
+
+        let bytes = ::rkyv::to_bytes::<Error>(&"RF19539007547034".to_string()).unwrap();
+        assert!(::rkyv::from_bytes::<RfCreditorReference, Error>(&bytes).is_err());
+    }
+}
@@ -0,0 +1,147 @@
+//! Scoring of candidate strings
+//!
+//! Free-text payment messages contain strings which may be intended as
+//! creditor references. [`score`] estimates how likely a candidate is a
+//! creditor reference, so candidates can be ranked instead of only
+//! accepted or rejected.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use iso_11649::score::{likelihood, score, Likelihood};
+//!
+//! assert_eq!(score("RF18 5390 0754 7034"), 1.0);
+//! assert!(score("RF19 5390 0754 7034") > score("Invoice 4711"));
+//!
+//! assert_eq!(likelihood("rf18 5390 0754 7034"), Likelihood::Likely);
+//! assert_eq!(likelihood("Invoice 4711"), Likelihood::Unlikely);
+//! ```
+
+use crate::{is_valid, IDENTIFIER, MAX_LEN, MIN_LEN};
+
+/// Score of the identifier `RF`
+const SCORE_IDENTIFIER: f32 = 0.3;
+/// Score of the identifier in lowercase `rf`
+const SCORE_IDENTIFIER_LOWERCASE: f32 = 0.2;
+/// Score of numeric check digits
+const SCORE_CHECK_DIGITS: f32 = 0.1;
+/// Score of a valid length
+const SCORE_LEN: f32 = 0.1;
+/// Score of valid characters only
+const SCORE_CHARSET: f32 = 0.2;
+/// Score of a matching checksum
+const SCORE_CHECKSUM: f32 = 0.3;
+
+/// `Likelihood` is the classification of a [`score`]
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum Likelihood {
+    /// Most likely no creditor reference
+    Unlikely,
+    /// Possibly a creditor reference with several problems
+    Possible,
+    /// Likely a creditor reference with a typo or wrong case
+    Likely,
+    /// Valid creditor reference
+    Certain,
+}
+
+impl Likelihood {
+    /// Classifies `score`
+    #[must_use]
+    pub fn from_score(score: f32) -> Self {
+        if score >= 1.0 {
+            Likelihood::Certain
+        } else if score >= 0.6 {
+            Likelihood::Likely
+        } else if score >= 0.4 {
+            Likelihood::Possible
+        } else {
+            Likelihood::Unlikely
+        }
+    }
+}
+
+/// Estimates how likely `candidate` is an intended creditor reference
+///
+/// The score is within `0.0` and `1.0`, where `1.0` is a valid creditor
+/// reference. Identifier, check digits, length, characters and checksum
+/// are considered. Spaces are ignored.
+#[must_use]
+pub fn score(candidate: &str) -> f32 {
+    let electronic = candidate.chars().filter(|&c| c != ' ').collect::<String>();
+    let len = electronic.chars().count();
+
+    let mut score = 0.0;
+    match electronic.get(..2) {
+        Some(identifier) if identifier == IDENTIFIER => score += SCORE_IDENTIFIER,
+        Some(identifier) if identifier.eq_ignore_ascii_case(IDENTIFIER) => {
+            score += SCORE_IDENTIFIER_LOWERCASE;
+        }
+        _ => {}
+    }
+    if electronic
+        .get(2..4)
+        .map_or(false, |digits| digits.bytes().all(|b| b.is_ascii_digit()))
+    {
+        score += SCORE_CHECK_DIGITS;
+    }
+    if (MIN_LEN..=MAX_LEN).contains(&len) {
+        score += SCORE_LEN;
+    }
+    if len > 0 && electronic.chars().all(|c| c.is_ascii_alphanumeric()) {
+        score += SCORE_CHARSET;
+    }
+    if electronic.is_ascii() && is_valid(&electronic.to_ascii_uppercase()) {
+        score += SCORE_CHECKSUM;
+    }
+
+    // rounding errors of the sum must not prevent a score of 1.0
+    if score > 0.99 {
+        1.0
+    } else {
+        score
+    }
+}
+
+/// Classifies how likely `candidate` is an intended creditor reference
+///
+/// See [`score`].
+#[must_use]
+pub fn likelihood(candidate: &str) -> Likelihood {
+    Likelihood::from_score(score(candidate))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn score_test() {
+        assert!((score("RF18 5390 0754 7034") - 1.0).abs() < f32::EPSILON);
+        assert!((score("RF18539007547034") - 1.0).abs() < f32::EPSILON);
+
+        let lowercase = score("rf18 5390 0754 7034");
+        let checksum = score("RF19 5390 0754 7034");
+        let charset = score("RF18 5390_0754 7034");
+        let identifier = score("XX18 5390 0754 7034");
+        let text = score("Invoice 4711");
+        assert!(lowercase < 1.0);
+        assert!(checksum < lowercase);
+        assert!(charset < checksum);
+        assert!(identifier < charset);
+        assert!(text < identifier);
+
+        assert!(score("").abs() < f32::EPSILON);
+        assert!(score("RF18 5390 0754 7034 ÄÖ") < 1.0);
+    }
+
+    #[test]
+    fn likelihood_test() {
+        assert_eq!(likelihood("RF18 5390 0754 7034"), Likelihood::Certain);
+        assert_eq!(likelihood("rf18 5390 0754 7034"), Likelihood::Likely);
+        assert_eq!(likelihood("RF19 5390 0754 7034"), Likelihood::Likely);
+        assert_eq!(likelihood("RF18 5390_0754 7034"), Likelihood::Possible);
+        assert_eq!(likelihood("Invoice 4711"), Likelihood::Unlikely);
+        assert!(Likelihood::Certain > Likelihood::Likely);
+    }
+}
@@ -0,0 +1,233 @@
+//! Sequential generation of creditor references
+//!
+//! [`SequentialGenerator`] issues references with a monotonically
+//! increasing counter in the body. The counter is persisted with a
+//! [`CounterStore`] after every issued reference, so there are no gaps
+//! or duplicates across restarts.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use iso_11649::sequential::{MemoryCounter, SequentialGenerator};
+//!
+//! let mut generator = SequentialGenerator::new(MemoryCounter::new(41))
+//!     .prefix("4711")
+//!     .width(6);
+//!
+//! let rf = generator.next_reference().unwrap();
+//! assert_eq!(&rf.as_electronic_str()[4..], "4711000042");
+//!
+//! let rf = generator.next_reference().unwrap();
+//! assert_eq!(&rf.as_electronic_str()[4..], "4711000043");
+//! ```
+
+use std::{
+    fs,
+    io::{self, ErrorKind},
+    path::PathBuf,
+};
+
+use crate::{RfCreditorReference, RfReferenceBuilder};
+
+/// `CounterStore` persists the counter of a [`SequentialGenerator`]
+pub trait CounterStore {
+    /// Loads the last issued counter value
+    ///
+    /// # Errors
+    ///
+    /// Results in [`io::Error`] when the counter can't be loaded.
+    ///
+    fn load(&mut self) -> io::Result<u64>;
+
+    /// Stores `value` as last issued counter value
+    ///
+    /// # Errors
+    ///
+    /// Results in [`io::Error`] when the counter can't be stored.
+    ///
+    fn store(&mut self, value: u64) -> io::Result<()>;
+}
+
+/// `MemoryCounter` keeps the counter in memory only
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct MemoryCounter(u64);
+
+impl MemoryCounter {
+    /// Creates [`MemoryCounter`] with last issued counter `value`
+    #[must_use]
+    pub fn new(value: u64) -> Self {
+        Self(value)
+    }
+}
+
+impl CounterStore for MemoryCounter {
+    fn load(&mut self) -> io::Result<u64> {
+        Ok(self.0)
+    }
+
+    fn store(&mut self, value: u64) -> io::Result<()> {
+        self.0 = value;
+        Ok(())
+    }
+}
+
+/// `FileCounter` persists the counter as decimal number in a file
+///
+/// A missing file is treated as counter `0`. The file is replaced
+/// atomically by writing a temporary file and renaming it.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FileCounter {
+    path: PathBuf,
+}
+
+impl FileCounter {
+    /// Creates [`FileCounter`] persisting to `path`
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+}
+
+impl CounterStore for FileCounter {
+    fn load(&mut self) -> io::Result<u64> {
+        match fs::read_to_string(&self.path) {
+            Ok(content) => content
+                .trim()
+                .parse()
+                .map_err(|err| io::Error::new(ErrorKind::InvalidData, err)),
+            Err(err) if err.kind() == ErrorKind::NotFound => Ok(0),
+            Err(err) => Err(err),
+        }
+    }
+
+    fn store(&mut self, value: u64) -> io::Result<()> {
+        let mut tmp_path = self.path.clone().into_os_string();
+        tmp_path.push(".tmp");
+        fs::write(&tmp_path, value.to_string())?;
+        fs::rename(&tmp_path, &self.path)
+    }
+}
+
+/// `SequentialGenerator` issues creditor references with increasing
+/// counter
+///
+/// The body consists of an optional fixed prefix followed by the counter,
+/// padded with leading zeros to the configured width.
+#[derive(Clone, Debug)]
+pub struct SequentialGenerator<S> {
+    prefix: String,
+    store: S,
+    width: usize,
+}
+
+impl<S: CounterStore> SequentialGenerator<S> {
+    /// Creates [`SequentialGenerator`] with counter persisted in `store`
+    pub fn new(store: S) -> Self {
+        Self {
+            prefix: String::new(),
+            store,
+            width: 0,
+        }
+    }
+
+    /// Sets the fixed `prefix` of the body
+    #[must_use]
+    pub fn prefix(mut self, prefix: &str) -> Self {
+        self.prefix = prefix.to_string();
+        self
+    }
+
+    /// Sets the `width` the counter is padded to with leading zeros
+    #[must_use]
+    pub fn width(mut self, width: usize) -> Self {
+        self.width = width;
+        self
+    }
+
+    /// Issues the next [`RfCreditorReference`]
+    ///
+    /// The counter is only stored when the reference is valid, so failed
+    /// calls don't leave gaps.
+    ///
+    /// # Errors
+    ///
+    /// Results in [`io::Error`] when the counter can't be loaded or
+    /// stored, and with [`ErrorKind::InvalidData`] wrapping the
+    /// [`ParseError`](crate::ParseError) when the prefix is invalid or the
+    /// body gets too long.
+    ///
+    pub fn next_reference(&mut self) -> io::Result<RfCreditorReference<'static>> {
+        let value = self
+            .store
+            .load()?
+            .checked_add(1)
+            .ok_or_else(|| io::Error::new(ErrorKind::InvalidData, "counter overflow"))?;
+
+        let rf = RfReferenceBuilder::new()
+            .segment(self.prefix.as_str())
+            .segment(format!("{:0width$}", value, width = self.width))
+            .build()
+            .map_err(|err| io::Error::new(ErrorKind::InvalidData, err))?;
+
+        self.store.store(value)?;
+        Ok(rf)
+    }
+
+    /// Consumes the generator and returns the [`CounterStore`]
+    pub fn into_store(self) -> S {
+        self.store
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn memory_counter_test() {
+        let mut generator = SequentialGenerator::new(MemoryCounter::default());
+        for expected in ["1", "2", "3"] {
+            let rf = generator.next_reference().unwrap();
+            assert_eq!(&rf.as_electronic_str()[4..], expected);
+        }
+        assert_eq!(generator.into_store(), MemoryCounter::new(3));
+    }
+
+    #[test]
+    fn no_gap_test() {
+        let mut generator =
+            SequentialGenerator::new(MemoryCounter::new(999)).prefix("12345678901234567890");
+        let err = generator.next_reference().unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidData);
+        assert_eq!(generator.into_store(), MemoryCounter::new(999));
+
+        let mut generator = SequentialGenerator::new(MemoryCounter::new(7)).prefix("INV-");
+        assert!(generator.next_reference().is_err());
+        assert_eq!(generator.into_store(), MemoryCounter::new(7));
+
+        let mut generator = SequentialGenerator::new(MemoryCounter::new(u64::MAX));
+        assert!(generator.next_reference().is_err());
+    }
+
+    #[test]
+    fn file_counter_test() {
+        let path = std::env::temp_dir().join(format!(
+            "iso_11649_file_counter_test_{}",
+            std::process::id()
+        ));
+        let _ = fs::remove_file(&path);
+
+        let mut generator = SequentialGenerator::new(FileCounter::new(&path)).width(4);
+        let rf = generator.next_reference().unwrap();
+        assert_eq!(&rf.as_electronic_str()[4..], "0001");
+
+        // restart continues with persisted counter
+        let mut generator = SequentialGenerator::new(FileCounter::new(&path)).width(4);
+        let rf = generator.next_reference().unwrap();
+        assert_eq!(&rf.as_electronic_str()[4..], "0002");
+        assert_eq!(fs::read_to_string(&path).unwrap(), "2");
+
+        fs::write(&path, "invalid").unwrap();
+        assert!(generator.next_reference().is_err());
+        fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,505 @@
+//! Serialization and deserialization with [`serde`](https://docs.rs/serde)
+//!
+//! Available with feature `serde`.
+//!
+//! [`RfCreditorReference`] serializes as string in the canonical electronic
+//! format. It deserializes from strings in electronic or print format with
+//! the full validation of [`RfCreditorReference::parse_str`]. Invalid
+//! references fail with the message of the [`ParseError`](crate::ParseError).
+//!
+//! # Examples
+//!
+//! ```rust
+//! use iso_11649::RfCreditorReference;
+//!
+//! #[derive(Debug, serde::Deserialize, serde::Serialize)]
+//! struct Invoice {
+//!     number: u32,
+//!     reference: RfCreditorReference<'static>,
+//! }
+//!
+//! let invoice: Invoice =
+//!     serde_json::from_str(r#"{"number":4711,"reference":"RF18 5390 0754 7034"}"#).unwrap();
+//! assert_eq!(invoice.reference.as_electronic_str(), "RF18539007547034");
+//! assert_eq!(
+//!     serde_json::to_string(&invoice).unwrap(),
+//!     r#"{"number":4711,"reference":"RF18539007547034"}"#
+//! );
+//!
+//! let err = serde_json::from_str::<Invoice>(r#"{"number":4712,"reference":"RF19 5390 0754 7034"}"#)
+//!     .unwrap_err();
+//! assert!(err
+//!     .to_string()
+//!     .starts_with("checksum 19 does not match expected 18"));
+//! ```
+//!
+//! The modules [`electronic`] and [`print`] choose the serialized format of
+//! fields with `#[serde(with = "...")]`. The module [`borrowed`]
+//! deserializes without allocation when the input is in canonical format.
+//!
+//! As map key, e.g. of a `HashMap<RfCreditorReference, Payment>`, the
+//! reference is a string key in electronic format.
+//!
+//! [`ElectronicReference`] serializes and deserializes without heap
+//! allocation in a buffer bounded by [`MAX_LEN`](crate::MAX_LEN). Only the
+//! errors of invalid references allocate.
+
+use std::{borrow::Cow, fmt, marker::PhantomData};
+
+use ::serde::{
+    de::{self, Visitor},
+    Deserialize, Deserializer, Serialize, Serializer,
+};
+
+use crate::{
+    parse_bytes, print::PrintReference, ElectronicReference, ParseError, RfCreditorReference,
+};
+
+impl Serialize for RfCreditorReference<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_electronic_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for RfCreditorReference<'_> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_str(ReferenceVisitor)
+    }
+}
+
+/// Visitor validating strings to [`RfCreditorReference`]
+struct ReferenceVisitor;
+
+impl Visitor<'_> for ReferenceVisitor {
+    type Value = RfCreditorReference<'static>;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("an ISO 11649 creditor reference")
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<Self::Value, E> {
+        RfCreditorReference::parse_str(v).map_err(E::custom)
+    }
+
+    fn visit_bytes<E: de::Error>(self, v: &[u8]) -> Result<Self::Value, E> {
+        parse_bytes(v)
+            .map(RfCreditorReference::from)
+            .map_err(E::custom)
+    }
+}
+
+/// Visitor validating strings to [`RfCreditorReference`] borrowing from the
+/// input of lifetime `'a`
+struct BorrowedVisitor<'a>(PhantomData<&'a str>);
+
+impl<'de: 'a, 'a> Visitor<'de> for BorrowedVisitor<'a> {
+    type Value = RfCreditorReference<'a>;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("an ISO 11649 creditor reference")
+    }
+
+    fn visit_borrowed_str<E: de::Error>(self, v: &'de str) -> Result<Self::Value, E> {
+        parse_borrowed(v).map_err(E::custom)
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<Self::Value, E> {
+        RfCreditorReference::parse_str(v).map_err(E::custom)
+    }
+
+    fn visit_bytes<E: de::Error>(self, v: &[u8]) -> Result<Self::Value, E> {
+        ReferenceVisitor.visit_bytes(v)
+    }
+}
+
+/// Parses `reference` like [`RfCreditorReference::parse_str`] and borrows
+/// it when it is in canonical electronic format
+fn parse_borrowed(reference: &str) -> Result<RfCreditorReference<'_>, ParseError> {
+    let electronic_reference = parse_bytes(reference.as_bytes())?;
+    if electronic_reference.as_str() != reference {
+        return RfCreditorReference::parse_str(reference);
+    }
+
+    Ok(RfCreditorReference {
+        checksum: electronic_reference.checksum(),
+        creditor_reference: PrintReference::new(reference),
+        electronic_reference: Cow::Borrowed(reference),
+    })
+}
+
+impl Serialize for ElectronicReference {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for ElectronicReference {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_str(ElectronicVisitor)
+    }
+}
+
+/// Visitor validating strings and bytes to [`ElectronicReference`]
+struct ElectronicVisitor;
+
+impl Visitor<'_> for ElectronicVisitor {
+    type Value = ElectronicReference;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("an ISO 11649 creditor reference")
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<Self::Value, E> {
+        parse_bytes(v.as_bytes()).map_err(E::custom)
+    }
+
+    fn visit_bytes<E: de::Error>(self, v: &[u8]) -> Result<Self::Value, E> {
+        parse_bytes(v).map_err(E::custom)
+    }
+}
+
+/// Serialization in electronic format without spaces
+///
+/// Usable with `#[serde(with = "iso_11649::serde::electronic")]`. This is
+/// the format of the [`Serialize`] implementation of
+/// [`RfCreditorReference`].
+///
+/// # Examples
+///
+/// ```rust
+/// use iso_11649::RfCreditorReference;
+///
+/// #[derive(serde::Serialize)]
+/// struct Payment {
+///     #[serde(with = "iso_11649::serde::electronic")]
+///     reference: RfCreditorReference<'static>,
+/// }
+///
+/// let payment = Payment {
+///     reference: RfCreditorReference::new("539007547034"),
+/// };
+/// assert_eq!(
+///     serde_json::to_string(&payment).unwrap(),
+///     r#"{"reference":"RF18539007547034"}"#
+/// );
+/// ```
+pub mod electronic {
+    use ::serde::{Deserialize, Deserializer, Serializer};
+
+    use crate::RfCreditorReference;
+
+    /// Serializes `rf` in electronic format
+    ///
+    /// # Errors
+    ///
+    /// Results in the error of `serializer`.
+    ///
+    pub fn serialize<S: Serializer>(
+        rf: &RfCreditorReference<'_>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(rf.as_electronic_str())
+    }
+
+    /// Deserializes a reference in electronic or print format
+    ///
+    /// # Errors
+    ///
+    /// Results in the error of `deserializer` with the message of the
+    /// [`ParseError`](crate::ParseError) for invalid references.
+    ///
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<RfCreditorReference<'static>, D::Error> {
+        RfCreditorReference::deserialize(deserializer)
+    }
+}
+
+/// Serialization in print format with spaces
+///
+/// Usable with `#[serde(with = "iso_11649::serde::print")]` for output to
+/// humans.
+///
+/// # Examples
+///
+/// ```rust
+/// use iso_11649::RfCreditorReference;
+///
+/// #[derive(serde::Deserialize, serde::Serialize)]
+/// struct InvoiceView {
+///     #[serde(with = "iso_11649::serde::print")]
+///     reference: RfCreditorReference<'static>,
+/// }
+///
+/// let view: InvoiceView = serde_json::from_str(r#"{"reference":"RF18539007547034"}"#).unwrap();
+/// assert_eq!(
+///     serde_json::to_string(&view).unwrap(),
+///     r#"{"reference":"RF18 5390 0754 7034"}"#
+/// );
+/// ```
+pub mod print {
+    use ::serde::{Deserialize, Deserializer, Serializer};
+
+    use crate::RfCreditorReference;
+
+    /// Serializes `rf` in print format
+    ///
+    /// # Errors
+    ///
+    /// Results in the error of `serializer`.
+    ///
+    pub fn serialize<S: Serializer>(
+        rf: &RfCreditorReference<'_>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(rf.creditor_reference.as_str())
+    }
+
+    /// Deserializes a reference in electronic or print format
+    ///
+    /// # Errors
+    ///
+    /// Results in the error of `deserializer` with the message of the
+    /// [`ParseError`](crate::ParseError) for invalid references.
+    ///
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<RfCreditorReference<'static>, D::Error> {
+        RfCreditorReference::deserialize(deserializer)
+    }
+}
+
+/// Zero-copy deserialization borrowing from the input
+///
+/// Usable with `#[serde(borrow, with = "iso_11649::serde::borrowed")]` for
+/// fields of type `RfCreditorReference<'a>`. References in canonical
+/// electronic format are borrowed from the input without allocation, all
+/// other references are validated and copied like with the [`Deserialize`]
+/// implementation of [`RfCreditorReference`]. Only deserializers providing
+/// borrowed strings, e.g. `serde_json::from_str` for strings without escape
+/// sequences, allow borrowing.
+///
+/// Serialization is in electronic format.
+///
+/// # Examples
+///
+/// ```rust
+/// use iso_11649::RfCreditorReference;
+///
+/// #[derive(serde::Deserialize)]
+/// struct Payment<'a> {
+///     amount: u64,
+///     #[serde(borrow, with = "iso_11649::serde::borrowed")]
+///     reference: RfCreditorReference<'a>,
+/// }
+///
+/// let json = r#"[{"amount":100,"reference":"RF18539007547034"},{"amount":200,"reference":"RF71 2348 231"}]"#;
+/// let payments: Vec<Payment> = serde_json::from_str(json).unwrap();
+///
+/// assert_eq!(payments[0].reference.as_electronic_str(), "RF18539007547034");
+/// assert_eq!(payments[1].reference.as_electronic_str(), "RF712348231");
+/// ```
+pub mod borrowed {
+    use std::marker::PhantomData;
+
+    use ::serde::{Deserializer, Serializer};
+
+    use super::BorrowedVisitor;
+    use crate::RfCreditorReference;
+
+    /// Serializes `rf` in electronic format
+    ///
+    /// # Errors
+    ///
+    /// Results in the error of `serializer`.
+    ///
+    pub fn serialize<S: Serializer>(
+        rf: &RfCreditorReference<'_>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        super::electronic::serialize(rf, serializer)
+    }
+
+    /// Deserializes a reference in electronic or print format and borrows
+    /// it from the input when it is in canonical electronic format
+    ///
+    /// # Errors
+    ///
+    /// Results in the error of `deserializer` with the message of the
+    /// [`ParseError`](crate::ParseError) for invalid references.
+    ///
+    pub fn deserialize<'de: 'a, 'a, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<RfCreditorReference<'a>, D::Error> {
+        deserializer.deserialize_str(BorrowedVisitor(PhantomData))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+
+    #[test]
+    fn serialize_test() {
+        let rf = RfCreditorReference::parse_str("RF18 5390 0754 7034").unwrap();
+        assert_eq!(serde_json::to_string(&rf).unwrap(), r#""RF18539007547034""#);
+    }
+
+    #[test]
+    fn deserialize_test() {
+        for input in [r#""RF18539007547034""#, r#""RF18 5390 0754 7034""#] {
+            let rf = serde_json::from_str::<RfCreditorReference>(input).unwrap();
+            assert_eq!(rf.as_electronic_str(), "RF18539007547034");
+            assert_eq!(rf.to_string(), "RF18 5390 0754 7034");
+        }
+
+        let rf = RfCreditorReference::new("2348231");
+        let json = serde_json::to_string(&rf).unwrap();
+        assert_eq!(
+            serde_json::from_str::<RfCreditorReference>(&json).unwrap(),
+            rf
+        );
+    }
+
+    #[derive(Debug, Deserialize, PartialEq, Serialize)]
+    struct Formats {
+        #[serde(with = "electronic")]
+        electronic: RfCreditorReference<'static>,
+        #[serde(with = "print")]
+        print: RfCreditorReference<'static>,
+    }
+
+    #[test]
+    fn with_test() {
+        let rf = RfCreditorReference::new("539007547034");
+        let formats = Formats {
+            electronic: rf.clone(),
+            print: rf,
+        };
+        let json = serde_json::to_string(&formats).unwrap();
+        assert_eq!(
+            json,
+            r#"{"electronic":"RF18539007547034","print":"RF18 5390 0754 7034"}"#
+        );
+        assert_eq!(serde_json::from_str::<Formats>(&json).unwrap(), formats);
+
+        let swapped = r#"{"electronic":"RF18 5390 0754 7034","print":"RF18539007547034"}"#;
+        assert_eq!(serde_json::from_str::<Formats>(swapped).unwrap(), formats);
+        assert!(serde_json::from_str::<Formats>(
+            r#"{"electronic":"RF19539007547034","print":"RF18539007547034"}"#
+        )
+        .is_err());
+    }
+
+    #[derive(Debug, Deserialize)]
+    struct Payment<'a> {
+        #[serde(borrow, with = "borrowed")]
+        reference: RfCreditorReference<'a>,
+    }
+
+    #[test]
+    fn borrowed_test() {
+        let json = r#"{"reference":"RF18539007547034"}"#;
+        let payment = serde_json::from_str::<Payment>(json).unwrap();
+        assert!(matches!(
+            payment.reference.electronic_reference,
+            Cow::Borrowed(_)
+        ));
+        assert_eq!(payment.reference, RfCreditorReference::new("539007547034"));
+
+        for json in [
+            r#"{"reference":"RF18 5390 0754 7034"}"#,
+            r#"{"reference":" RF18539007547034"}"#,
+            r#"{"reference":"RF18\u00353900754703\u0034"}"#,
+        ] {
+            let payment = serde_json::from_str::<Payment>(json).unwrap();
+            assert!(matches!(
+                payment.reference.electronic_reference,
+                Cow::Owned(_)
+            ));
+            assert_eq!(payment.reference.as_electronic_str(), "RF18539007547034");
+        }
+
+        assert!(serde_json::from_str::<Payment>(r#"{"reference":"RF19539007547034"}"#).is_err());
+        assert_eq!(
+            parse_borrowed("RF712348231").unwrap(),
+            RfCreditorReference::new("2348231")
+        );
+    }
+
+    #[test]
+    fn bytes_test() {
+        use ::serde::de::value::{BytesDeserializer, Error};
+
+        let rf = RfCreditorReference::deserialize(BytesDeserializer::<Error>::new(b"RF712348231"))
+            .unwrap();
+        assert_eq!(rf, RfCreditorReference::new("2348231"));
+        let electronic =
+            ElectronicReference::deserialize(BytesDeserializer::<Error>::new(b"RF712348231"))
+                .unwrap();
+        assert_eq!(electronic.as_str(), "RF712348231");
+
+        assert!(
+            RfCreditorReference::deserialize(BytesDeserializer::<Error>::new(b"RF71\xff")).is_err()
+        );
+        assert!(
+            ElectronicReference::deserialize(BytesDeserializer::<Error>::new(b"RF702348231"))
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn electronic_reference_test() {
+        let electronic = parse_bytes(b"RF18 5390 0754 7034").unwrap();
+        let json = serde_json::to_string(&electronic).unwrap();
+        assert_eq!(json, r#""RF18539007547034""#);
+        assert_eq!(
+            serde_json::from_str::<ElectronicReference>(&json).unwrap(),
+            electronic
+        );
+        assert!(serde_json::from_str::<ElectronicReference>(r#""RF19539007547034""#).is_err());
+    }
+
+    #[test]
+    fn map_key_test() {
+        let mut payments = HashMap::new();
+        let _ = payments.insert(RfCreditorReference::new("539007547034"), 100);
+        let json = serde_json::to_string(&payments).unwrap();
+        assert_eq!(json, r#"{"RF18539007547034":100}"#);
+        assert_eq!(
+            serde_json::from_str::<HashMap<RfCreditorReference, u32>>(&json).unwrap(),
+            payments
+        );
+
+        let payments = serde_json::from_str::<HashMap<RfCreditorReference, u32>>(
+            r#"{"RF18 5390 0754 7034":100,"RF712348231":200}"#,
+        )
+        .unwrap();
+        assert_eq!(payments[&RfCreditorReference::new("539007547034")], 100);
+        assert_eq!(payments[&RfCreditorReference::new("2348231")], 200);
+
+        assert!(serde_json::from_str::<HashMap<RfCreditorReference, u32>>(
+            r#"{"RF19539007547034":100}"#
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn deserialize_error_test() {
+        let err = serde_json::from_str::<RfCreditorReference>(r#""RF18 5390 0754 703_""#)
+            .unwrap_err()
+            .to_string();
+        assert!(err.starts_with("invalid character"), "{}", err);
+
+        let err = serde_json::from_str::<RfCreditorReference>("4711")
+            .unwrap_err()
+            .to_string();
+        assert!(
+            err.contains("expected an ISO 11649 creditor reference"),
+            "{}",
+            err
+        );
+    }
+}
@@ -0,0 +1,101 @@
+//! Batch validation in parallel lanes
+//!
+//! Available with feature `simd`.
+//!
+//! References are validated in chunks of [`LANES`]. Each reference of a
+//! chunk is copied into a buffer of fixed length with the body padded by
+//! leading zeros, which do not change the remainder. So all lanes run the
+//! same branchless steps, which the compiler vectorizes.
+
+use crate::{is_valid, iso7064::VALUES_36, IDENTIFIER, MAX_LEN, MIN_LEN};
+
+/// Number of references validated in parallel
+const LANES: usize = 8;
+
+/// Validates `references` like [`is_valid`]
+///
+/// References containing spaces are validated by the scalar fallback.
+pub(crate) fn is_valid_batch(references: &[&str]) -> Vec<bool> {
+    let mut results = Vec::with_capacity(references.len());
+
+    for chunk in references.chunks(LANES) {
+        let mut buf = [[b'0'; MAX_LEN]; LANES];
+        let mut valid = [false; LANES];
+
+        for (lane, reference) in chunk.iter().enumerate() {
+            let bytes = reference.as_bytes();
+            if bytes.contains(&b' ') {
+                continue;
+            }
+            let len = bytes.len();
+            valid[lane] = (MIN_LEN..=MAX_LEN).contains(&len)
+                && &bytes[..2] == IDENTIFIER.as_bytes()
+                && bytes[2..4].iter().all(u8::is_ascii_digit);
+            if valid[lane] {
+                // body padded by leading zeros, identifier and check digits moved to the end
+                buf[lane][MAX_LEN - len..MAX_LEN - 4].copy_from_slice(&bytes[4..]);
+                buf[lane][MAX_LEN - 4..].copy_from_slice(&bytes[..4]);
+            }
+        }
+
+        let mut remainder = [0u32; LANES];
+        let mut invalid = [false; LANES];
+        for i in 0..MAX_LEN {
+            for lane in 0..LANES {
+                let value = u32::from(VALUES_36[usize::from(buf[lane][i])]);
+                let factor = if value < 10 { 10 } else { 100 };
+                invalid[lane] |= value >= 36;
+                remainder[lane] = (remainder[lane] * factor + value) % 97;
+            }
+        }
+
+        for (lane, reference) in chunk.iter().enumerate() {
+            results.push(if reference.as_bytes().contains(&b' ') {
+                is_valid(reference)
+            } else {
+                valid[lane] && !invalid[lane] && remainder[lane] == 1
+            });
+        }
+    }
+
+    results
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::RfCreditorReference;
+
+    #[test]
+    fn is_valid_batch_test() {
+        let mut references = vec![
+            String::new(),
+            "RF18".to_string(),
+            "RF18 5390 0754 7034".to_string(),
+            "RF19 5390 0754 7034".to_string(),
+            "RF18539007547034".to_string(),
+            "RF19539007547034".to_string(),
+            "rf18539007547034".to_string(),
+            "RF1A539007547034".to_string(),
+            "RF18539007547034_123".to_string(),
+            "RF18539007547034älsö".to_string(),
+            "RF63ABCD0754efgh".to_string(),
+            "RF93539007547034928301234".to_string(),
+            "RF93539007547034928301234X".to_string(),
+        ];
+        for n in 0..500u64 {
+            let rf = RfCreditorReference::from_u64(n * 7919);
+            references.push(rf.to_electronic_string());
+            references.push(format!("{}Z", rf.as_electronic_str()));
+        }
+        references.push(RfCreditorReference::new(&"Z".repeat(21)).to_electronic_string());
+
+        let references = references.iter().map(String::as_str).collect::<Vec<_>>();
+        let results = is_valid_batch(&references);
+        assert_eq!(results.len(), references.len());
+        for (reference, result) in references.iter().zip(results) {
+            assert_eq!(result, is_valid(reference), "{}", reference);
+        }
+        assert!(is_valid_batch(&[]).is_empty());
+    }
+}
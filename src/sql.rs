@@ -0,0 +1,439 @@
+//! SQL support for creditor references
+//!
+//! [`check_constraint`] generates a database-native expression for `CHECK`
+//! constraints of reference columns in Postgres, `MySQL` and `SQLite`. It only
+//! checks length, identifier and characters of the canonical electronic
+//! format, not the check digits, so it is a first line of defense in the
+//! schema while [`RfCreditorReference::parse_str`] stays the source of
+//! truth.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use iso_11649::sql::{check_constraint, Dialect};
+//!
+//! assert_eq!(
+//!     check_constraint(Dialect::Postgres, "reference"),
+//!     "char_length(reference) BETWEEN 5 AND 25 AND reference ~ '^RF[0-9]{2}[0-9A-Za-z]{1,21}$'"
+//! );
+//! ```
+//!
+//! # Database libraries
+//!
+//! References are stored as text in canonical electronic format. Values
+//! read from the database are validated like
+//! [`RfCreditorReference::parse_str`], so invalid stored values surface as
+//! errors of the database library.
+//!
+//! - feature `diesel`: `ToSql<Text>`, `FromSql<Text>`, `AsExpression<Text>`
+//!   and `Queryable` for [Diesel](https://diesel.rs)
+//! - feature `sqlx`: `Type`, `Encode` and `Decode` for all databases of
+//!   [sqlx](https://docs.rs/sqlx) with text types, e.g. Postgres, `MySQL` and
+//!   `SQLite`
+//! - feature `postgres`: `ToSql` and `FromSql` of
+//!   [postgres-types](https://docs.rs/postgres-types) for `TEXT`, `VARCHAR`
+//!   and similar columns with tokio-postgres and postgres
+//! - feature `rusqlite`: `ToSql` and `FromSql` for
+//!   [rusqlite](https://docs.rs/rusqlite)
+//! - feature `sea-orm`: `TryGetable` for entity models of
+//!   [SeaORM](https://www.sea-ql.org/SeaORM) and everything of feature
+//!   `sea-query`
+//! - feature `sea-query`: `Into<Value>`, `ValueType` and `Nullable` for
+//!   [SeaQuery](https://docs.rs/sea-query) query builders
+//!
+//! [`RfCreditorReference::parse_str`]: crate::RfCreditorReference::parse_str
+
+use crate::{MAX_LEN, MIN_LEN};
+
+/// Regular expression of the canonical electronic format
+///
+/// Matches the identifier `RF`, two digits and up to 21 alphanumeric
+/// characters. Usable in `pattern` of JSON schemas, `OpenAPI` definitions
+/// and HTML forms. The check digits are not validated.
+pub const PATTERN: &str = "^RF[0-9]{2}[0-9A-Za-z]{1,21}$";
+
+/// `Dialect` selects the SQL dialect of [`check_constraint`]
+///
+/// New variants may be added in future versions.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[non_exhaustive]
+pub enum Dialect {
+    /// Postgres with the `~` operator
+    Postgres,
+    /// `MySQL` 8.0 and later with `REGEXP_LIKE`
+    MySql,
+    /// `SQLite` with `GLOB`, as `REGEXP` needs an extension
+    Sqlite,
+}
+
+/// Generates a `CHECK` constraint expression approximating valid references
+/// of `column` in `dialect`
+///
+/// The expression checks the length and [`PATTERN`] of the canonical
+/// electronic format, but not the check digits. `column` is inserted
+/// unchanged, so quote it if needed.
+///
+/// # Examples
+///
+/// ```rust
+/// use iso_11649::sql::{check_constraint, Dialect};
+///
+/// let ddl = format!(
+///     "CREATE TABLE invoice (reference TEXT NOT NULL CHECK ({}))",
+///     check_constraint(Dialect::Sqlite, "reference")
+/// );
+/// assert!(ddl.contains("reference GLOB 'RF[0-9][0-9]*'"));
+/// ```
+///
+#[must_use]
+pub fn check_constraint(dialect: Dialect, column: &str) -> String {
+    match dialect {
+        Dialect::Postgres => format!(
+            "char_length({0}) BETWEEN {1} AND {2} AND {0} ~ '{3}'",
+            column, MIN_LEN, MAX_LEN, PATTERN
+        ),
+        Dialect::MySql => format!(
+            "CHAR_LENGTH({0}) BETWEEN {1} AND {2} AND REGEXP_LIKE({0}, '{3}', 'c')",
+            column, MIN_LEN, MAX_LEN, PATTERN
+        ),
+        Dialect::Sqlite => format!(
+            "length({0}) BETWEEN {1} AND {2} AND {0} GLOB 'RF[0-9][0-9]*' AND substr({0}, 5) NOT GLOB '*[^0-9A-Za-z]*'",
+            column, MIN_LEN, MAX_LEN
+        ),
+    }
+}
+
+#[cfg(feature = "diesel")]
+mod diesel {
+    use ::diesel::{
+        backend::Backend,
+        deserialize::{self, FromSql},
+        serialize::{self, Output, ToSql},
+        sql_types::Text,
+    };
+
+    use crate::RfCreditorReference;
+
+    impl<DB> ToSql<Text, DB> for RfCreditorReference<'_>
+    where
+        DB: Backend,
+        str: ToSql<Text, DB>,
+    {
+        fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, DB>) -> serialize::Result {
+            <str as ToSql<Text, DB>>::to_sql(self.as_electronic_str(), out)
+        }
+    }
+
+    impl<DB> FromSql<Text, DB> for RfCreditorReference<'_>
+    where
+        DB: Backend,
+        String: FromSql<Text, DB>,
+    {
+        fn from_sql(bytes: DB::RawValue<'_>) -> deserialize::Result<Self> {
+            let reference = <String as FromSql<Text, DB>>::from_sql(bytes)?;
+            Ok(RfCreditorReference::parse_str(&reference)?)
+        }
+    }
+}
+
+#[cfg(feature = "sqlx")]
+mod sqlx {
+    use ::sqlx::{encode::IsNull, error::BoxDynError, Database, Decode, Encode, Type};
+
+    use crate::RfCreditorReference;
+
+    impl<DB> Type<DB> for RfCreditorReference<'_>
+    where
+        DB: Database,
+        str: Type<DB>,
+    {
+        fn type_info() -> DB::TypeInfo {
+            <str as Type<DB>>::type_info()
+        }
+
+        fn compatible(ty: &DB::TypeInfo) -> bool {
+            <str as Type<DB>>::compatible(ty)
+        }
+    }
+
+    impl<'q, DB> Encode<'q, DB> for RfCreditorReference<'_>
+    where
+        DB: Database,
+        String: Encode<'q, DB>,
+    {
+        fn encode_by_ref(
+            &self,
+            buf: &mut <DB as Database>::ArgumentBuffer<'q>,
+        ) -> Result<IsNull, BoxDynError> {
+            <String as Encode<'q, DB>>::encode(self.to_electronic_string(), buf)
+        }
+    }
+
+    impl<'r, DB> Decode<'r, DB> for RfCreditorReference<'_>
+    where
+        DB: Database,
+        &'r str: Decode<'r, DB>,
+    {
+        fn decode(value: <DB as Database>::ValueRef<'r>) -> Result<Self, BoxDynError> {
+            let reference = <&str as Decode<'r, DB>>::decode(value)?;
+            Ok(RfCreditorReference::parse_str(reference)?)
+        }
+    }
+}
+
+#[cfg(feature = "postgres")]
+mod postgres {
+    use std::error::Error;
+
+    use ::bytes::BytesMut;
+    use ::postgres_types::{to_sql_checked, FromSql, IsNull, ToSql, Type};
+
+    use crate::RfCreditorReference;
+
+    impl ToSql for RfCreditorReference<'_> {
+        fn to_sql(
+            &self,
+            ty: &Type,
+            out: &mut BytesMut,
+        ) -> Result<IsNull, Box<dyn Error + Sync + Send>> {
+            self.as_electronic_str().to_sql(ty, out)
+        }
+
+        fn accepts(ty: &Type) -> bool {
+            <&str as ToSql>::accepts(ty)
+        }
+
+        to_sql_checked!();
+    }
+
+    impl<'a> FromSql<'a> for RfCreditorReference<'_> {
+        fn from_sql(ty: &Type, raw: &'a [u8]) -> Result<Self, Box<dyn Error + Sync + Send>> {
+            let reference = <&str as FromSql>::from_sql(ty, raw)?;
+            Ok(RfCreditorReference::parse_str(reference)?)
+        }
+
+        fn accepts(ty: &Type) -> bool {
+            <&str as FromSql>::accepts(ty)
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use crate::ParseError;
+
+        #[test]
+        fn to_sql_test() {
+            let rf = RfCreditorReference::new("539007547034");
+            let mut buf = BytesMut::new();
+            assert!(matches!(rf.to_sql(&Type::TEXT, &mut buf), Ok(IsNull::No)));
+            assert_eq!(&buf[..], b"RF18539007547034");
+
+            assert!(<RfCreditorReference as ToSql>::accepts(&Type::VARCHAR));
+            assert!(!<RfCreditorReference as ToSql>::accepts(&Type::INT4));
+            assert!(rf.to_sql_checked(&Type::INT4, &mut buf).is_err());
+        }
+
+        #[test]
+        fn from_sql_test() {
+            assert_eq!(
+                RfCreditorReference::from_sql(&Type::TEXT, b"RF712348231").unwrap(),
+                RfCreditorReference::new("2348231")
+            );
+
+            let err = RfCreditorReference::from_sql(&Type::TEXT, b"RF702348231").unwrap_err();
+            assert!(matches!(
+                err.downcast_ref::<ParseError>(),
+                Some(ParseError::InvalidChecksum { .. })
+            ));
+        }
+    }
+}
+
+#[cfg(feature = "rusqlite")]
+mod rusqlite {
+    use ::rusqlite::types::{FromSql, FromSqlError, FromSqlResult, ToSql, ToSqlOutput, ValueRef};
+
+    use crate::RfCreditorReference;
+
+    impl ToSql for RfCreditorReference<'_> {
+        fn to_sql(&self) -> ::rusqlite::Result<ToSqlOutput<'_>> {
+            Ok(ToSqlOutput::from(self.as_electronic_str()))
+        }
+    }
+
+    impl FromSql for RfCreditorReference<'_> {
+        fn column_result(value: ValueRef<'_>) -> FromSqlResult<Self> {
+            RfCreditorReference::parse_str(value.as_str()?)
+                .map_err(|err| FromSqlError::Other(Box::new(err)))
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use ::rusqlite::Connection;
+
+        use super::*;
+
+        #[test]
+        fn roundtrip_test() {
+            let conn = Connection::open_in_memory().unwrap();
+            let _ = conn
+                .execute("CREATE TABLE invoice (reference TEXT NOT NULL)", [])
+                .unwrap();
+
+            let rf = RfCreditorReference::new("539007547034");
+            let _ = conn
+                .execute("INSERT INTO invoice (reference) VALUES (?1)", [&rf])
+                .unwrap();
+            let stored: String = conn
+                .query_row("SELECT reference FROM invoice", [], |row| row.get(0))
+                .unwrap();
+            assert_eq!(stored, "RF18539007547034");
+            let read: RfCreditorReference = conn
+                .query_row("SELECT reference FROM invoice", [], |row| row.get(0))
+                .unwrap();
+            assert_eq!(read, rf);
+
+            let _ = conn
+                .execute("UPDATE invoice SET reference = 'RF19539007547034'", [])
+                .unwrap();
+            assert!(conn
+                .query_row("SELECT reference FROM invoice", [], |row| {
+                    row.get::<_, RfCreditorReference>(0)
+                })
+                .is_err());
+        }
+    }
+}
+
+#[cfg(feature = "sea-orm")]
+mod sea_orm {
+    use ::sea_orm::{ColIdx, DbErr, QueryResult, TryGetError, TryGetable};
+
+    use crate::RfCreditorReference;
+
+    impl TryGetable for RfCreditorReference<'static> {
+        fn try_get_by<I: ColIdx>(res: &QueryResult, index: I) -> Result<Self, TryGetError> {
+            let reference = String::try_get_by(res, index)?;
+            RfCreditorReference::parse_str(&reference).map_err(|err| {
+                TryGetError::DbErr(DbErr::TryIntoErr {
+                    from: "String",
+                    into: "RfCreditorReference",
+                    source: Box::new(err),
+                })
+            })
+        }
+    }
+}
+
+#[cfg(any(feature = "sea-orm", feature = "sea-query"))]
+mod sea_query {
+    #[cfg(all(feature = "sea-orm", not(feature = "sea-query")))]
+    use ::sea_orm::sea_query::{
+        ArrayType, ColumnType, Nullable, StringLen, Value, ValueType, ValueTypeErr,
+    };
+    #[cfg(feature = "sea-query")]
+    use ::sea_query::{ArrayType, ColumnType, Nullable, StringLen, Value, ValueType, ValueTypeErr};
+
+    use crate::RfCreditorReference;
+    use crate::MAX_LEN;
+
+    impl From<RfCreditorReference<'_>> for Value {
+        fn from(rf: RfCreditorReference<'_>) -> Self {
+            Value::String(Some(Box::new(rf.to_electronic_string())))
+        }
+    }
+
+    impl ValueType for RfCreditorReference<'static> {
+        fn try_from(v: Value) -> Result<Self, ValueTypeErr> {
+            match v {
+                Value::String(Some(reference)) => {
+                    RfCreditorReference::parse_str(&reference).map_err(|_| ValueTypeErr)
+                }
+                _ => Err(ValueTypeErr),
+            }
+        }
+
+        fn type_name() -> String {
+            "RfCreditorReference".to_string()
+        }
+
+        fn array_type() -> ArrayType {
+            ArrayType::String
+        }
+
+        #[allow(clippy::cast_possible_truncation)]
+        fn column_type() -> ColumnType {
+            ColumnType::String(StringLen::N(MAX_LEN as u32))
+        }
+    }
+
+    impl Nullable for RfCreditorReference<'static> {
+        fn null() -> Value {
+            Value::String(None)
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[test]
+        fn value_test() {
+            let rf = RfCreditorReference::new("539007547034");
+            let value = Value::from(rf.clone());
+            assert_eq!(
+                value,
+                Value::String(Some(Box::new("RF18539007547034".to_string())))
+            );
+            assert_eq!(
+                <RfCreditorReference as ValueType>::try_from(value).unwrap(),
+                rf
+            );
+
+            assert!(
+                <RfCreditorReference as ValueType>::try_from(Value::String(Some(Box::new(
+                    "RF19539007547034".to_string()
+                ))))
+                .is_err()
+            );
+            assert!(
+                <RfCreditorReference as ValueType>::try_from(RfCreditorReference::null()).is_err()
+            );
+            assert_eq!(
+                RfCreditorReference::column_type(),
+                ColumnType::String(StringLen::N(25))
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pattern_test() {
+        assert_eq!(
+            PATTERN,
+            format!(
+                "^{}[0-9]{{2}}[0-9A-Za-z]{{1,{}}}$",
+                crate::IDENTIFIER,
+                MAX_LEN - 4
+            )
+        );
+    }
+
+    #[test]
+    fn check_constraint_test() {
+        assert_eq!(
+            check_constraint(Dialect::MySql, "`reference`"),
+            "CHAR_LENGTH(`reference`) BETWEEN 5 AND 25 AND REGEXP_LIKE(`reference`, '^RF[0-9]{2}[0-9A-Za-z]{1,21}$', 'c')"
+        );
+        assert_eq!(
+            check_constraint(Dialect::Sqlite, "\"reference\""),
+            "length(\"reference\") BETWEEN 5 AND 25 AND \"reference\" GLOB 'RF[0-9][0-9]*' AND substr(\"reference\", 5) NOT GLOB '*[^0-9A-Za-z]*'"
+        );
+    }
+}
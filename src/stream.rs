@@ -0,0 +1,144 @@
+//! Validation of async streams of references
+//!
+//! Available with feature `futures`.
+//!
+//! [`ValidateStreamExt::validate_references`] wraps a
+//! [`Stream`] of references, e.g. lines of an async file or messages of a
+//! Kafka consumer, and yields the validated references. Rejected
+//! references are yielded with their error.
+//!
+//! # Examples
+//!
+//! ```rust,ignore
+//! use futures::StreamExt;
+//! use iso_11649::stream::ValidateStreamExt;
+//!
+//! let lines = futures::stream::iter(vec![
+//!     "RF18 5390 0754 7034".to_string(),
+//!     "RF19 5390 0754 7034".to_string(),
+//! ]);
+//! let results = lines.validate_references().collect::<Vec<_>>().await;
+//!
+//! assert!(results[0].is_ok());
+//! assert_eq!(results[1].as_ref().unwrap_err().0, "RF19 5390 0754 7034");
+//! ```
+
+use std::{
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use futures_core::Stream;
+
+use crate::{ParseError, RfCreditorReference};
+
+/// `ValidatedStream` yields the validated references of the wrapped stream
+///
+/// Created by [`ValidateStreamExt::validate_references`].
+#[derive(Clone, Debug)]
+#[must_use = "streams do nothing unless polled"]
+pub struct ValidatedStream<S> {
+    stream: S,
+}
+
+impl<S> ValidatedStream<S> {
+    /// Wraps `stream` of references
+    pub fn new(stream: S) -> Self {
+        Self { stream }
+    }
+
+    /// Unwraps the stream of references
+    pub fn into_inner(self) -> S {
+        self.stream
+    }
+}
+
+impl<S> Stream for ValidatedStream<S>
+where
+    S: Stream<Item = String> + Unpin,
+{
+    type Item = Result<RfCreditorReference<'static>, (String, ParseError)>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        Pin::new(&mut self.stream).poll_next(cx).map(|reference| {
+            reference.map(|reference| {
+                RfCreditorReference::parse_str(&reference).map_err(|err| (reference, err))
+            })
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.stream.size_hint()
+    }
+}
+
+/// `ValidateStreamExt` adds the validation combinator to streams of
+/// references
+///
+/// Streams which are not [`Unpin`] are validated after pinning them with
+/// `Box::pin`.
+pub trait ValidateStreamExt: Stream<Item = String> + Sized {
+    /// Validates the references of the stream like
+    /// [`RfCreditorReference::parse_str`]
+    ///
+    /// Invalid references are yielded as error together with the
+    /// [`ParseError`].
+    fn validate_references(self) -> ValidatedStream<Self> {
+        ValidatedStream::new(self)
+    }
+}
+
+impl<S> ValidateStreamExt for S where S: Stream<Item = String> + Sized {}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        collections::VecDeque,
+        sync::Arc,
+        task::{Wake, Waker},
+    };
+
+    use super::*;
+
+    /// Ready stream of references
+    struct Lines(VecDeque<String>);
+
+    impl Stream for Lines {
+        type Item = String;
+
+        fn poll_next(mut self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<Option<String>> {
+            Poll::Ready(self.0.pop_front())
+        }
+    }
+
+    struct NoopWaker;
+
+    impl Wake for NoopWaker {
+        fn wake(self: Arc<Self>) {}
+    }
+
+    #[test]
+    fn validate_references_test() {
+        let lines = Lines(
+            ["RF18 5390 0754 7034", "RF19 5390 0754 7034"]
+                .iter()
+                .map(|&line| line.to_string())
+                .collect(),
+        );
+        let mut stream = lines.validate_references();
+
+        let waker = Waker::from(Arc::new(NoopWaker));
+        let mut cx = Context::from_waker(&waker);
+        let mut poll = || Pin::new(&mut stream).poll_next(&mut cx);
+
+        assert!(matches!(poll(), Poll::Ready(Some(Ok(_)))));
+        match poll() {
+            Poll::Ready(Some(Err((reference, err)))) => {
+                assert_eq!(reference, "RF19 5390 0754 7034");
+                assert!(matches!(err, ParseError::InvalidChecksum { .. }));
+            }
+            _ => panic!("expected rejected reference"),
+        }
+        assert!(matches!(poll(), Poll::Ready(None)));
+    }
+}
@@ -0,0 +1,295 @@
+//! Template-driven generation of creditor references
+//!
+//! A [`Template`] expands an existing numbering convention like
+//! `INV{year}{counter:06}` to the body of a reference.
+//!
+//! Placeholders are
+//!
+//! - `{year}` as `YYYY` and `{yy}` as `YY`
+//! - `{month}` as `MM` and `{day}` as `DD`
+//! - `{counter}` and `{counter:N}` zero-padded to at least `N` digits
+//!
+//! Other characters of the template are literals and must be 0-9, a-z or
+//! A-Z.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use iso_11649::{
+//!     template::{Template, TemplateContext},
+//!     timestamp::IssueDate,
+//! };
+//!
+//! let template = Template::parse("INV{year}{counter:06}").unwrap();
+//! let ctx = TemplateContext::new(IssueDate::new(2024, 8, 15).unwrap(), 42);
+//!
+//! let rf = template.generate(&ctx).unwrap();
+//! assert_eq!(rf.to_string(), "RF57 INV2 0240 0004 2");
+//!
+//! assert!(Template::parse("INV-{year}").is_err());
+//! assert!(Template::parse("{year}{month}{day}{counter:14}").is_err());
+//! ```
+
+use std::{fmt::Write, str::FromStr};
+
+use crate::{
+    timestamp::IssueDate, ParseError, RfCreditorReference, RfReferenceBuilder, MAX_BODY_LEN,
+};
+
+/// Part of a [`Template`]
+#[derive(Clone, Debug, Eq, PartialEq)]
+enum Part {
+    /// Literal characters
+    Literal(String),
+    /// Year as `YYYY`
+    Year,
+    /// Year as `YY`
+    ShortYear,
+    /// Month as `MM`
+    Month,
+    /// Day as `DD`
+    Day,
+    /// Counter with minimum width
+    Counter(usize),
+}
+
+impl Part {
+    /// Minimum length of the expanded part
+    fn min_len(&self) -> usize {
+        match self {
+            Part::Literal(literal) => literal.len(),
+            Part::Year => 4,
+            Part::ShortYear | Part::Month | Part::Day => 2,
+            Part::Counter(width) => (*width).max(1),
+        }
+    }
+}
+
+/// `TemplateContext` holds the values of the placeholders of a [`Template`]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct TemplateContext {
+    date: IssueDate,
+    counter: u64,
+}
+
+impl TemplateContext {
+    /// Creates [`TemplateContext`] with the `date` of the date placeholders
+    /// and the `counter` of `{counter}`
+    #[must_use]
+    pub fn new(date: IssueDate, counter: u64) -> Self {
+        Self { date, counter }
+    }
+}
+
+/// `Template` generates [`RfCreditorReference`]s from a numbering
+/// convention
+///
+/// See the [module](self) for the syntax.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Template {
+    parts: Vec<Part>,
+}
+
+impl Template {
+    /// Parses a `template`
+    ///
+    /// # Errors
+    ///
+    /// Results in [`ParseError::InvalidCharacter`] for invalid literals and
+    /// at the `{` of unknown or unclosed placeholders,
+    /// [`ParseError::TooShort`] for an empty `template` and
+    /// [`ParseError::BodyTooLong`] when the expanded template always
+    /// exceeds [`MAX_BODY_LEN`].
+    ///
+    pub fn parse(template: &str) -> Result<Self, ParseError> {
+        let invalid = |character, position| ParseError::InvalidCharacter {
+            input: template.to_string(),
+            character,
+            position,
+        };
+
+        let mut parts = Vec::new();
+        let mut chars = template.chars().enumerate();
+        while let Some((position, character)) = chars.next() {
+            match character {
+                '{' => {
+                    let mut name = String::new();
+                    loop {
+                        match chars.next() {
+                            Some((_, '}')) => break,
+                            Some((_, c)) => name.push(c),
+                            None => return Err(invalid('{', position)),
+                        }
+                    }
+                    parts.push(Self::placeholder(&name).ok_or_else(|| invalid('{', position))?);
+                }
+                c if c.is_ascii_alphanumeric() => {
+                    if let Some(Part::Literal(literal)) = parts.last_mut() {
+                        literal.push(c);
+                    } else {
+                        parts.push(Part::Literal(c.to_string()));
+                    }
+                }
+                c => return Err(invalid(c, position)),
+            }
+        }
+
+        let len = parts
+            .iter()
+            .fold(0, |len: usize, part| len.saturating_add(part.min_len()));
+        if len == 0 {
+            Err(ParseError::TooShort {
+                input: template.to_string(),
+                len,
+                min: 1,
+            })
+        } else if len > MAX_BODY_LEN {
+            Err(ParseError::BodyTooLong {
+                input: template.to_string(),
+                len,
+                max: MAX_BODY_LEN,
+            })
+        } else {
+            Ok(Self { parts })
+        }
+    }
+
+    /// Generates the [`RfCreditorReference`] with the values of `ctx`
+    ///
+    /// # Errors
+    ///
+    /// Results in [`ParseError::BodyTooLong`] when the counter exceeds its
+    /// width and the body gets longer than [`MAX_BODY_LEN`].
+    ///
+    pub fn generate(
+        &self,
+        ctx: &TemplateContext,
+    ) -> Result<RfCreditorReference<'static>, ParseError> {
+        RfReferenceBuilder::new().segment(self.expand(ctx)).build()
+    }
+
+    /// Expands the template to the body with the values of `ctx`
+    #[must_use]
+    pub fn expand(&self, ctx: &TemplateContext) -> String {
+        let mut body = String::new();
+        for part in &self.parts {
+            let _ = match part {
+                Part::Literal(literal) => write!(body, "{}", literal),
+                Part::Year => write!(body, "{:04}", ctx.date.year()),
+                Part::ShortYear => write!(body, "{:02}", ctx.date.year() % 100),
+                Part::Month => write!(body, "{:02}", ctx.date.month()),
+                Part::Day => write!(body, "{:02}", ctx.date.day()),
+                Part::Counter(width) => write!(body, "{:0width$}", ctx.counter, width = width),
+            };
+        }
+        body
+    }
+
+    /// Part of the placeholder `name`
+    fn placeholder(name: &str) -> Option<Part> {
+        match name {
+            "year" => Some(Part::Year),
+            "yy" => Some(Part::ShortYear),
+            "month" => Some(Part::Month),
+            "day" => Some(Part::Day),
+            "counter" => Some(Part::Counter(0)),
+            _ => name
+                .strip_prefix("counter:")
+                .filter(|width| !width.is_empty() && width.bytes().all(|b| b.is_ascii_digit()))
+                .and_then(|width| width.parse().ok())
+                .map(Part::Counter),
+        }
+    }
+}
+
+impl FromStr for Template {
+    type Err = ParseError;
+
+    fn from_str(template: &str) -> Result<Self, Self::Err> {
+        Self::parse(template)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_test() {
+        assert!(Template::parse("INV{year}{counter:06}").is_ok());
+        assert!("{yy}{month}{day}{counter}".parse::<Template>().is_ok());
+
+        assert!(matches!(
+            Template::parse("INV-{year}"),
+            Err(ParseError::InvalidCharacter {
+                character: '-',
+                position: 3,
+                ..
+            })
+        ));
+        assert!(matches!(
+            Template::parse("INV{date}"),
+            Err(ParseError::InvalidCharacter {
+                character: '{',
+                position: 3,
+                ..
+            })
+        ));
+        assert!(matches!(
+            Template::parse("INV{counter:x}"),
+            Err(ParseError::InvalidCharacter { position: 3, .. })
+        ));
+        assert!(matches!(
+            Template::parse("INV{year"),
+            Err(ParseError::InvalidCharacter { position: 3, .. })
+        ));
+        assert!(matches!(
+            Template::parse(""),
+            Err(ParseError::TooShort { .. })
+        ));
+        assert!(matches!(
+            Template::parse("{year}{month}{day}{counter:14}"),
+            Err(ParseError::BodyTooLong { len: 22, .. })
+        ));
+        assert!(matches!(
+            Template::parse("{counter:18446744073709551615}{counter:1}"),
+            Err(ParseError::BodyTooLong {
+                len: usize::MAX,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn generate_test() {
+        let date = IssueDate::new(2024, 8, 15).unwrap();
+
+        let template = Template::parse("{yy}{month}{day}{counter:4}").unwrap();
+        let ctx = TemplateContext::new(date, 42);
+        assert_eq!(template.expand(&ctx), "2408150042");
+        assert_eq!(
+            template.generate(&ctx).unwrap().as_electronic_str(),
+            "RF522408150042"
+        );
+
+        let template = Template::parse("INV{yy}{counter:3}").unwrap();
+        assert_eq!(
+            template.expand(&TemplateContext::new(date, 1000)),
+            "INV241000"
+        );
+
+        let template = Template::parse("{year}{month}{day}{counter:13}").unwrap();
+        assert!(template.generate(&TemplateContext::new(date, 1)).is_ok());
+        assert!(matches!(
+            template.generate(&TemplateContext::new(date, 10_000_000_000_000)),
+            Err(ParseError::BodyTooLong { len: 22, .. })
+        ));
+        assert!(matches!(
+            Template::parse("{counter:18446744073709551615}{counter:1}"),
+            Err(ParseError::BodyTooLong {
+                len: usize::MAX,
+                ..
+            })
+        ));
+    }
+}
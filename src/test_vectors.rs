@@ -0,0 +1,139 @@
+//! Test vectors of creditor references
+//!
+//! Available with feature `test-util`.
+//!
+//! Known-valid references, known-invalid references by the kind of
+//! [`ParseError`](crate::ParseError) and generation pairs for reuse in
+//! tests of downstream crates.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use iso_11649::{test_vectors, RfCreditorReference};
+//!
+//! for reference in test_vectors::VALID {
+//!     assert!(RfCreditorReference::parse_str(reference).is_ok());
+//! }
+//! for (body, reference) in test_vectors::GENERATION {
+//!     assert_eq!(RfCreditorReference::new(body).to_string(), *reference);
+//! }
+//! ```
+
+/// Valid references in electronic and print format
+pub const VALID: &[&str] = &[
+    "RF18539007547034",
+    "RF712348231",
+    "RF18 5390 0754 7034",
+    " RF18 5390 0754 7034 ",
+    "RF63 ABCD 0754 efgh",
+    "RF63ABCD0754EFGH",
+    "RF06 SOS",
+    "RF93539007547034928301234",
+];
+
+/// Invalid references resulting in
+/// [`ParseError::InvalidCharacter`](crate::ParseError::InvalidCharacter)
+pub const INVALID_CHARACTER: &[&str] = &[
+    "RF18539007547034_123",
+    "RF18539007547034älsö",
+    "RF18539007547034@",
+    "RF18 5390-0754 7034",
+];
+
+/// Invalid references resulting in
+/// [`ParseError::InvalidCheckDigits`](crate::ParseError::InvalidCheckDigits)
+pub const INVALID_CHECK_DIGITS: &[&str] = &["RF1A539007547034", "RFAB 2348 231"];
+
+/// Invalid references resulting in
+/// [`ParseError::InvalidChecksum`](crate::ParseError::InvalidChecksum)
+pub const INVALID_CHECKSUM: &[&str] = &[
+    "RF19 5390 0754 7034",
+    "RF18 5390 0754 7043",
+    "RF72 2348 231",
+    "RF63 ABCD 0754 EFGI",
+];
+
+/// Invalid references resulting in
+/// [`ParseError::InvalidIdentifier`](crate::ParseError::InvalidIdentifier)
+pub const INVALID_IDENTIFIER: &[&str] = &[
+    "18539007547034",
+    "18 5390 0754 7034",
+    "XF18 5390 0754 7034",
+    "rf18539007547034",
+];
+
+/// Invalid references resulting in
+/// [`ParseError::TooLong`](crate::ParseError::TooLong)
+pub const TOO_LONG: &[&str] = &[
+    "RF18539007547034928TOOLONG",
+    "RF93 5390 0754 7034 9283 0123 45",
+];
+
+/// Invalid references resulting in
+/// [`ParseError::TooShort`](crate::ParseError::TooShort)
+pub const TOO_SHORT: &[&str] = &["", "RF", "RF18", "RF18    "];
+
+/// Bodies resulting in
+/// [`ParseError::BodyTooLong`](crate::ParseError::BodyTooLong) on generation
+pub const BODY_TOO_LONG: &[&str] = &["5390075470349283012345", "RF5390075470349283012345"];
+
+/// Bodies and the references in print format generated from them
+pub const GENERATION: &[(&str, &str)] = &[
+    ("539007547034", "RF18 5390 0754 7034"),
+    ("5390 0754 7034", "RF18 5390 0754 7034"),
+    ("2348231", "RF71 2348 231"),
+    ("ABCD0754EFGH", "RF63 ABCD 0754 EFGH"),
+    ("SOS", "RF06 SOS"),
+    ("539007547034928301234", "RF93 5390 0754 7034 9283 0123 4"),
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ParseError, RfCreditorReference};
+
+    #[test]
+    fn valid_test() {
+        for reference in VALID {
+            assert!(crate::is_valid(reference), "{}", reference);
+            assert!(
+                RfCreditorReference::parse_str(reference).is_ok(),
+                "{}",
+                reference
+            );
+        }
+    }
+
+    #[test]
+    fn invalid_test() {
+        let categories = [
+            (INVALID_CHARACTER, "ISO11649-E001-INVALID-CHAR"),
+            (INVALID_CHECK_DIGITS, "ISO11649-E002-INVALID-CHECK-DIGITS"),
+            (INVALID_CHECKSUM, "ISO11649-E003-INVALID-CHECKSUM"),
+            (INVALID_IDENTIFIER, "ISO11649-E004-INVALID-IDENTIFIER"),
+            (TOO_LONG, "ISO11649-E005-TOO-LONG"),
+            (TOO_SHORT, "ISO11649-E006-TOO-SHORT"),
+        ];
+
+        for (references, code) in categories {
+            for reference in references {
+                assert!(!crate::is_valid(reference), "{}", reference);
+                let err = RfCreditorReference::parse_str(reference).unwrap_err();
+                assert_eq!(err.code(), code, "{}", reference);
+            }
+        }
+    }
+
+    #[test]
+    fn generation_test() {
+        for (body, reference) in GENERATION {
+            assert_eq!(RfCreditorReference::new(body).to_string(), *reference);
+        }
+        for body in BODY_TOO_LONG {
+            assert!(matches!(
+                RfCreditorReference::try_new(body),
+                Err(ParseError::BodyTooLong { .. })
+            ));
+        }
+    }
+}
@@ -0,0 +1,312 @@
+//! Creditor references with embedded issue date
+//!
+//! The body starts with the issue date as `YYMMDD` followed by a sequence
+//! number, so the issue date of a reference can be decoded, e.g. to expire
+//! stale payment links. Years are within 2000 to 2099.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use iso_11649::timestamp::{decode, IssueDate, TimestampGenerator};
+//!
+//! let date = IssueDate::new(2024, 8, 15).unwrap();
+//! let mut generator = TimestampGenerator::new().width(4);
+//!
+//! let rf = generator.next_reference(date).unwrap();
+//! assert_eq!(&rf.as_electronic_str()[4..], "2408150001");
+//!
+//! assert_eq!(decode(&rf), Some((date, 1)));
+//! ```
+
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::{ParseError, RfCreditorReference, RfReferenceBuilder, GEN_PREFIX, MAX_BODY_LEN};
+
+/// Length of the encoded date `YYMMDD`
+const DATE_LEN: usize = 6;
+
+/// Seconds of a day
+const SECS_PER_DAY: u64 = 86_400;
+
+/// `IssueDate` is the date embedded in a creditor reference
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct IssueDate {
+    year: u16,
+    month: u8,
+    day: u8,
+}
+
+impl IssueDate {
+    /// Creates [`IssueDate`] if `year` is within 2000 to 2099 and `month`
+    /// and `day` are a valid date
+    #[must_use]
+    pub fn new(year: u16, month: u8, day: u8) -> Option<Self> {
+        let days_in_month = match month {
+            1 | 3 | 5 | 7 | 8 | 10 | 12 => 31,
+            4 | 6 | 9 | 11 => 30,
+            // years 2000 to 2099 are leap years when divisible by 4
+            2 if year % 4 == 0 => 29,
+            2 => 28,
+            _ => return None,
+        };
+
+        if (2000..2100).contains(&year) && (1..=days_in_month).contains(&day) {
+            Some(Self { year, month, day })
+        } else {
+            None
+        }
+    }
+
+    /// Creates [`IssueDate`] from `time` in UTC
+    ///
+    /// Returns `None` if `time` is not within 2000 to 2099.
+    #[must_use]
+    pub fn from_system_time(time: SystemTime) -> Option<Self> {
+        let days = time.duration_since(UNIX_EPOCH).ok()?.as_secs() / SECS_PER_DAY;
+        Self::from_days(i64::try_from(days).ok()?)
+    }
+
+    /// The year
+    #[must_use]
+    pub fn year(self) -> u16 {
+        self.year
+    }
+
+    /// The month from 1 to 12
+    #[must_use]
+    pub fn month(self) -> u8 {
+        self.month
+    }
+
+    /// The day of month from 1 to 31
+    #[must_use]
+    pub fn day(self) -> u8 {
+        self.day
+    }
+
+    /// Days since 1970-01-01
+    #[must_use]
+    pub fn days(self) -> i64 {
+        // civil to days algorithm of Howard Hinnant for years after 2000
+        let (year, month) = if self.month <= 2 {
+            (i64::from(self.year) - 1, i64::from(self.month) + 9)
+        } else {
+            (i64::from(self.year), i64::from(self.month) - 3)
+        };
+        let era = year / 400;
+        let year_of_era = year - era * 400;
+        let day_of_year = (153 * month + 2) / 5 + i64::from(self.day) - 1;
+        let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+        era * 146_097 + day_of_era - 719_468
+    }
+
+    /// Creates [`IssueDate`] from `days` since 1970-01-01
+    fn from_days(days: i64) -> Option<Self> {
+        // days to civil algorithm of Howard Hinnant for dates after 1970
+        let days = days + 719_468;
+        let era = days / 146_097;
+        let day_of_era = days - era * 146_097;
+        let year_of_era =
+            (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+        let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+        let month = (5 * day_of_year + 2) / 153;
+        let day = day_of_year - (153 * month + 2) / 5 + 1;
+        let month = if month < 10 { month + 3 } else { month - 9 };
+        let year = year_of_era + era * 400 + i64::from(month <= 2);
+
+        Self::new(
+            u16::try_from(year).ok()?,
+            u8::try_from(month).ok()?,
+            u8::try_from(day).ok()?,
+        )
+    }
+
+    /// Encodes the date as `YYMMDD`
+    fn encode(self) -> String {
+        format!("{:02}{:02}{:02}", self.year % 100, self.month, self.day)
+    }
+}
+
+/// `TimestampGenerator` issues creditor references with the issue date
+/// followed by a sequence number
+///
+/// The sequence starts at 1 and is reset when the date changes.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TimestampGenerator {
+    date: Option<IssueDate>,
+    sequence: u64,
+    width: usize,
+}
+
+impl TimestampGenerator {
+    /// Creates [`TimestampGenerator`] with sequence width 6
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            date: None,
+            sequence: 0,
+            width: 6,
+        }
+    }
+
+    /// Sets the `width` the sequence is padded to with leading zeros
+    ///
+    /// The body has at most 21 characters, so `width` is at most 15.
+    #[must_use]
+    pub fn width(mut self, width: usize) -> Self {
+        self.width = width;
+        self
+    }
+
+    /// Issues the next [`RfCreditorReference`] for `date`
+    ///
+    /// # Errors
+    ///
+    /// Results in [`ParseError::BodyTooLong`] when the sequence exceeds the
+    /// available length of the body.
+    ///
+    pub fn next_reference(
+        &mut self,
+        date: IssueDate,
+    ) -> Result<RfCreditorReference<'static>, ParseError> {
+        let sequence = if self.date == Some(date) {
+            self.sequence + 1
+        } else {
+            1
+        };
+
+        let encoded = date.encode();
+        if self.width > MAX_BODY_LEN - encoded.len() {
+            return Err(ParseError::BodyTooLong {
+                len: encoded.len().saturating_add(self.width),
+                input: encoded,
+                max: MAX_BODY_LEN,
+            });
+        }
+
+        let rf = RfReferenceBuilder::new()
+            .segment(encoded)
+            .segment(format!("{:0width$}", sequence, width = self.width))
+            .build()?;
+
+        self.date = Some(date);
+        self.sequence = sequence;
+        Ok(rf)
+    }
+}
+
+impl Default for TimestampGenerator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Decodes the issue date and sequence number of `rf`
+///
+/// Returns `None` if the body does not start with a valid date followed
+/// by a numeric sequence.
+#[must_use]
+pub fn decode(rf: &RfCreditorReference<'_>) -> Option<(IssueDate, u64)> {
+    let body = &rf.as_electronic_str()[GEN_PREFIX.len()..];
+    let date = body.get(..DATE_LEN)?;
+    let sequence = body.get(DATE_LEN..)?;
+
+    if !date.bytes().all(|b| b.is_ascii_digit()) || !sequence.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+
+    let date = IssueDate::new(
+        2000 + date[..2].parse::<u16>().ok()?,
+        date[2..4].parse().ok()?,
+        date[4..].parse().ok()?,
+    )?;
+    Some((date, sequence.parse().ok()?))
+}
+
+/// Checks if `rf` was issued more than `max_age_days` before `today`
+///
+/// References without decodable issue date are expired.
+#[must_use]
+pub fn is_expired(rf: &RfCreditorReference<'_>, today: IssueDate, max_age_days: u32) -> bool {
+    decode(rf).map_or(true, |(date, _)| {
+        today.days() - date.days() > i64::from(max_age_days)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+
+    #[test]
+    fn issue_date_test() {
+        assert!(IssueDate::new(2024, 2, 29).is_some());
+        assert!(IssueDate::new(2023, 2, 29).is_none());
+        assert!(IssueDate::new(2000, 2, 29).is_some());
+        assert!(IssueDate::new(2024, 4, 31).is_none());
+        assert!(IssueDate::new(2024, 13, 1).is_none());
+        assert!(IssueDate::new(1999, 12, 31).is_none());
+        assert!(IssueDate::new(2100, 1, 1).is_none());
+
+        assert_eq!(IssueDate::new(2000, 1, 1).unwrap().days(), 10_957);
+        assert_eq!(IssueDate::new(2024, 8, 15).unwrap().days(), 19_950);
+
+        for days in [10_957, 11_016, 11_017, 19_950, 47_481] {
+            assert_eq!(IssueDate::from_days(days).unwrap().days(), days);
+        }
+        assert_eq!(IssueDate::from_days(10_956), None);
+        assert_eq!(IssueDate::from_days(47_482), None);
+
+        let time = UNIX_EPOCH + Duration::from_secs(19_950 * SECS_PER_DAY + 3_600);
+        assert_eq!(
+            IssueDate::from_system_time(time),
+            IssueDate::new(2024, 8, 15)
+        );
+        assert_eq!(IssueDate::from_system_time(UNIX_EPOCH), None);
+    }
+
+    #[test]
+    fn next_reference_test() {
+        let date = IssueDate::new(2024, 8, 15).unwrap();
+        let next_date = IssueDate::new(2024, 8, 16).unwrap();
+        let mut generator = TimestampGenerator::new();
+
+        let rf = generator.next_reference(date).unwrap();
+        assert_eq!(&rf.as_electronic_str()[4..], "240815000001");
+        let rf = generator.next_reference(date).unwrap();
+        assert_eq!(&rf.as_electronic_str()[4..], "240815000002");
+        let rf = generator.next_reference(next_date).unwrap();
+        assert_eq!(&rf.as_electronic_str()[4..], "240816000001");
+
+        let mut generator = TimestampGenerator::new().width(16);
+        assert!(matches!(
+            generator.next_reference(date),
+            Err(ParseError::BodyTooLong { len: 22, .. })
+        ));
+        let mut generator = TimestampGenerator::new().width(usize::MAX);
+        assert!(matches!(
+            generator.next_reference(date),
+            Err(ParseError::BodyTooLong {
+                len: usize::MAX,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn decode_test() {
+        let date = IssueDate::new(2024, 8, 15).unwrap();
+        let rf = TimestampGenerator::new().next_reference(date).unwrap();
+        assert_eq!(decode(&rf), Some((date, 1)));
+
+        assert_eq!(decode(&RfCreditorReference::new("241315000001")), None);
+        assert_eq!(decode(&RfCreditorReference::new("240815A")), None);
+        assert_eq!(decode(&RfCreditorReference::new("24081")), None);
+
+        let today = IssueDate::new(2024, 9, 14).unwrap();
+        assert!(!is_expired(&rf, today, 30));
+        assert!(is_expired(&rf, today, 29));
+        assert!(is_expired(&RfCreditorReference::new("ABC"), today, 30));
+    }
+}
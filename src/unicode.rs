@@ -0,0 +1,98 @@
+//! Normalization of Unicode digits and full-width characters
+//!
+//! Available with feature `unicode`.
+
+/// First code points of the contiguous Unicode decimal digit ranges `0..=9`
+///
+/// All ranges of general category `Nd` besides ASCII as of Unicode 17.0,
+/// including the supplementary planes, e.g. Osmanya from U+104A0 and the
+/// mathematical digits from U+1D7CE.
+const DECIMAL_ZEROS: &[u32] = &[
+    0x0660, 0x06F0, 0x07C0, 0x0966, 0x09E6, 0x0A66, 0x0AE6, 0x0B66, 0x0BE6, 0x0C66, 0x0CE6, 0x0D66,
+    0x0DE6, 0x0E50, 0x0ED0, 0x0F20, 0x1040, 0x1090, 0x17E0, 0x1810, 0x1946, 0x19D0, 0x1A80, 0x1A90,
+    0x1B50, 0x1BB0, 0x1C40, 0x1C50, 0xA620, 0xA8D0, 0xA900, 0xA9D0, 0xA9F0, 0xAA50, 0xABF0, 0xFF10,
+    0x104A0, 0x10D30, 0x10D40, 0x11066, 0x110F0, 0x11136, 0x111D0, 0x112F0, 0x11450, 0x114D0,
+    0x11650, 0x116C0, 0x116D0, 0x116DA, 0x11730, 0x118E0, 0x11950, 0x11BF0, 0x11C50, 0x11D50,
+    0x11DA0, 0x11DE0, 0x11F50, 0x16130, 0x16A60, 0x16AC0, 0x16B50, 0x16D70, 0x1CCF0, 0x1D7CE,
+    0x1D7D8, 0x1D7E2, 0x1D7EC, 0x1D7F6, 0x1E140, 0x1E2F0, 0x1E4F0, 0x1E5F1, 0x1E950, 0x1FBF0,
+];
+
+/// First code point of full-width uppercase Latin letters
+const FULLWIDTH_UPPER_A: u32 = 0xFF21;
+/// First code point of full-width lowercase Latin letters
+const FULLWIDTH_LOWER_A: u32 = 0xFF41;
+/// Ideographic (full-width) space
+const IDEOGRAPHIC_SPACE: char = '\u{3000}';
+
+/// Maps Unicode decimal digits, full-width Latin letters and full-width
+/// spaces in `reference` to their ASCII equivalents
+///
+/// All other characters are kept unchanged.
+///
+/// # Examples
+///
+/// ```rust
+/// use iso_11649::unicode::normalize;
+///
+/// assert_eq!(normalize("ＲＦ１８\u{3000}５３９０"), "RF18 5390");
+/// assert_eq!(normalize("RF١٨ ٥٣٩٠"), "RF18 5390");
+/// ```
+///
+#[must_use]
+pub fn normalize(reference: &str) -> String {
+    reference.chars().map(normalize_char).collect()
+}
+
+/// Maps a single character to its ASCII equivalent
+fn normalize_char(c: char) -> char {
+    if c.is_ascii() {
+        return c;
+    }
+    if c == IDEOGRAPHIC_SPACE {
+        return ' ';
+    }
+
+    let code = u32::from(c);
+    if let Some(zero) = DECIMAL_ZEROS
+        .iter()
+        .find(|&&zero| (zero..zero + 10).contains(&code))
+    {
+        return char::from_digit(code - zero, 10).unwrap_or(c);
+    }
+    if (FULLWIDTH_UPPER_A..FULLWIDTH_UPPER_A + 26).contains(&code) {
+        return char::from_u32(code - FULLWIDTH_UPPER_A + u32::from('A')).unwrap_or(c);
+    }
+    if (FULLWIDTH_LOWER_A..FULLWIDTH_LOWER_A + 26).contains(&code) {
+        return char::from_u32(code - FULLWIDTH_LOWER_A + u32::from('a')).unwrap_or(c);
+    }
+
+    c
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalize_test() {
+        assert_eq!(normalize("RF18 5390 0754 7034"), "RF18 5390 0754 7034");
+        assert_eq!(
+            normalize("ＲＦ１８　５３９０　０７５４　７０３４"),
+            "RF18 5390 0754 7034"
+        );
+        assert_eq!(normalize("ＲＦ６３ａｂｃｄ"), "RF63abcd");
+        assert_eq!(normalize("RF۱۸۵۳۹۰"), "RF185390");
+        assert_eq!(normalize("RF१८५३९०"), "RF185390");
+        assert_eq!(normalize("RF18 älsö"), "RF18 älsö");
+    }
+
+    #[test]
+    fn supplementary_planes_test() {
+        // Osmanya, mathematical bold, Kawi and segmented digits
+        assert_eq!(normalize("RF\u{104A1}\u{104A8}"), "RF18");
+        assert_eq!(normalize("RF\u{1D7CF}\u{1D7D6}"), "RF18");
+        assert_eq!(normalize("RF\u{11F51}\u{11F58}"), "RF18");
+        assert_eq!(normalize("RF\u{1FBF1}\u{1FBF8}"), "RF18");
+        assert_eq!(normalize("RF\u{1FBFA}"), "RF\u{1FBFA}");
+    }
+}
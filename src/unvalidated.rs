@@ -0,0 +1,146 @@
+//! Creditor references stored as received
+//!
+//! [`UnvalidatedReference`] keeps whatever a customer sent, so ingestion
+//! never fails, and validates it on demand.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use iso_11649::UnvalidatedReference;
+//!
+//! let valid = UnvalidatedReference::from("RF18 5390 0754 7034");
+//! assert!(valid.is_valid());
+//! assert_eq!(valid.validate().unwrap().as_electronic_str(), "RF18539007547034");
+//!
+//! let invalid = UnvalidatedReference::from("RF19 5390 0754 7034");
+//! assert!(!invalid.is_valid());
+//! assert_eq!(invalid.as_str(), "RF19 5390 0754 7034");
+//! ```
+
+use crate::{ParseError, RfCreditorReference};
+
+/// `UnvalidatedReference` is a creditor reference input kept unchanged
+///
+/// With feature `serde` it deserializes from any string and serializes
+/// back unchanged.
+#[derive(Clone, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
+pub struct UnvalidatedReference(String);
+
+impl UnvalidatedReference {
+    /// Creates [`UnvalidatedReference`] of `reference` as received
+    pub fn new(reference: impl Into<String>) -> Self {
+        Self(reference.into())
+    }
+
+    /// Borrows the reference as received
+    #[must_use]
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Takes the reference as received
+    #[must_use]
+    pub fn into_inner(self) -> String {
+        self.0
+    }
+
+    /// Checks if the reference is valid like [`crate::is_valid`]
+    #[must_use]
+    pub fn is_valid(&self) -> bool {
+        crate::is_valid(&self.0)
+    }
+
+    /// Validates the reference like [`RfCreditorReference::parse_str`]
+    ///
+    /// # Errors
+    ///
+    /// Results in [`ParseError`]s when there is some problem with
+    /// parsing the reference.
+    ///
+    pub fn validate(&self) -> Result<RfCreditorReference<'static>, ParseError> {
+        RfCreditorReference::parse_str(&self.0)
+    }
+}
+
+impl AsRef<str> for UnvalidatedReference {
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+/// Formats the reference as received.
+impl std::fmt::Display for UnvalidatedReference {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.pad(&self.0)
+    }
+}
+
+impl From<String> for UnvalidatedReference {
+    fn from(reference: String) -> Self {
+        Self(reference)
+    }
+}
+
+impl From<&str> for UnvalidatedReference {
+    fn from(reference: &str) -> Self {
+        Self(reference.to_string())
+    }
+}
+
+/// Keeps the reference in electronic format.
+impl From<&RfCreditorReference<'_>> for UnvalidatedReference {
+    fn from(rf: &RfCreditorReference<'_>) -> Self {
+        Self(rf.to_electronic_string())
+    }
+}
+
+impl From<UnvalidatedReference> for String {
+    fn from(reference: UnvalidatedReference) -> Self {
+        reference.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn validate_test() {
+        let reference = UnvalidatedReference::new(" RF71 2348 231 ");
+        assert!(reference.is_valid());
+        assert_eq!(
+            reference.validate().unwrap(),
+            RfCreditorReference::new("2348231")
+        );
+        assert_eq!(reference.to_string(), " RF71 2348 231 ");
+        assert_eq!(String::from(reference), " RF71 2348 231 ");
+
+        let reference = UnvalidatedReference::from("RF71 2348 23_");
+        assert!(!reference.is_valid());
+        assert!(matches!(
+            reference.validate(),
+            Err(ParseError::InvalidCharacter { .. })
+        ));
+        assert_eq!(reference.into_inner(), "RF71 2348 23_");
+
+        let rf = RfCreditorReference::new("539007547034");
+        assert_eq!(UnvalidatedReference::from(&rf).as_str(), "RF18539007547034");
+        assert!(!UnvalidatedReference::default().is_valid());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_test() {
+        for json in [r#""RF18 5390 0754 7034""#, r#""not a reference""#, r#""""#] {
+            let reference = serde_json::from_str::<UnvalidatedReference>(json).unwrap();
+            assert_eq!(serde_json::to_string(&reference).unwrap(), json);
+        }
+        assert!(
+            serde_json::from_str::<UnvalidatedReference>(r#""RF18 5390 0754 7034""#)
+                .unwrap()
+                .is_valid()
+        );
+    }
+}
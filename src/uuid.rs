@@ -0,0 +1,128 @@
+//! Encoding of [`Uuid`]s in [`RfCreditorReference`]s
+//!
+//! Available with feature `uuid`.
+//!
+//! A [`Uuid`] needs 25 base-36 characters, more than the 21 characters of
+//! the reference body. So the 64-bit halves are encoded in a pair of
+//! references with 13 base-36 characters each.
+
+use ::uuid::Uuid;
+
+use crate::{RfCreditorReference, RfReferenceBuilder, GEN_PREFIX};
+
+/// Digits of base-36 encoding
+const BASE36: &[u8] = b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZ";
+
+/// Number of base-36 characters of an encoded [`u64`]
+const U64_BASE36_LEN: usize = 13;
+
+impl RfCreditorReference<'_> {
+    /// Encodes `uuid` in a pair of references with the high and low
+    /// 64-bit halves as base-36 bodies
+    ///
+    /// See [`Self::to_uuid`] for decoding.
+    ///
+    /// Available with feature `uuid`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use iso_11649::RfCreditorReference;
+    /// use uuid::Uuid;
+    ///
+    /// let uuid = Uuid::from_u128(0x67e5_5044_10b1_426f_9247_bb68_0e5f_e0c8);
+    /// let (high, low) = RfCreditorReference::from_uuid(uuid);
+    ///
+    /// assert_eq!(high.as_electronic_str().len(), 17);
+    /// assert_eq!(RfCreditorReference::to_uuid(&high, &low), Some(uuid));
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// A half of 64 bits has at most 13 base-36 characters, so it never panics.
+    ///
+    #[must_use]
+    pub fn from_uuid(uuid: Uuid) -> (RfCreditorReference<'static>, RfCreditorReference<'static>) {
+        let value = uuid.as_u128();
+        #[allow(clippy::cast_possible_truncation)]
+        let (high, low) = ((value >> 64) as u64, value as u64);
+
+        let encode = |half| {
+            RfReferenceBuilder::new()
+                .segment(encode_base36(half))
+                .build()
+                .expect("13 base-36 characters fit into the body")
+        };
+        (encode(high), encode(low))
+    }
+
+    /// Decodes a [`Uuid`] from a pair of references created by
+    /// [`Self::from_uuid`]
+    ///
+    /// Returns `None` if a body is no base-36 encoded 64-bit value.
+    ///
+    /// Available with feature `uuid`.
+    ///
+    #[must_use]
+    pub fn to_uuid(high: &RfCreditorReference<'_>, low: &RfCreditorReference<'_>) -> Option<Uuid> {
+        let high = decode_base36(&high.as_electronic_str()[GEN_PREFIX.len()..])?;
+        let low = decode_base36(&low.as_electronic_str()[GEN_PREFIX.len()..])?;
+        Some(Uuid::from_u128((u128::from(high) << 64) | u128::from(low)))
+    }
+}
+
+/// Encodes `value` in uppercase base-36 padded to [`U64_BASE36_LEN`]
+fn encode_base36(mut value: u64) -> String {
+    let mut digits = [b'0'; U64_BASE36_LEN];
+    for digit in digits.iter_mut().rev() {
+        #[allow(clippy::cast_possible_truncation)]
+        let n = (value % 36) as usize;
+        *digit = BASE36[n];
+        value /= 36;
+    }
+    digits.iter().map(|&b| char::from(b)).collect()
+}
+
+/// Decodes a base-36 `body` of [`U64_BASE36_LEN`] characters
+fn decode_base36(body: &str) -> Option<u64> {
+    if body.len() == U64_BASE36_LEN {
+        u64::from_str_radix(body, 36).ok()
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn base36_test() {
+        assert_eq!(encode_base36(0), "0000000000000");
+        assert_eq!(encode_base36(35), "000000000000Z");
+        assert_eq!(encode_base36(u64::MAX), "3W5E11264SGSF");
+        for value in [0, 1, 36, 1 << 32, u64::MAX] {
+            assert_eq!(decode_base36(&encode_base36(value)), Some(value));
+        }
+        assert_eq!(decode_base36("3w5e11264sgsf"), Some(u64::MAX));
+        assert_eq!(decode_base36("3W5E11264SGSG"), None);
+        assert_eq!(decode_base36("123"), None);
+    }
+
+    #[test]
+    fn uuid_test() {
+        for uuid in [
+            Uuid::nil(),
+            Uuid::from_u128(u128::MAX),
+            Uuid::from_u128(0x67e5_5044_10b1_426f_9247_bb68_0e5f_e0c8),
+        ] {
+            let (high, low) = RfCreditorReference::from_uuid(uuid);
+            assert!(crate::is_valid(high.as_electronic_str()));
+            assert!(crate::is_valid(low.as_electronic_str()));
+            assert_eq!(RfCreditorReference::to_uuid(&high, &low), Some(uuid));
+        }
+
+        let rf = RfCreditorReference::new("539007547034");
+        assert_eq!(RfCreditorReference::to_uuid(&rf, &rf), None);
+    }
+}
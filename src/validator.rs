@@ -0,0 +1,134 @@
+//! Integration with the [`validator`](https://docs.rs/validator) crate
+//!
+//! Available with feature `validator`.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use iso_11649::validator::{validate_rf_reference, RfReferenceField};
+//! use validator::Validate;
+//!
+//! #[derive(Validate)]
+//! struct PaymentRequest {
+//!     #[validate(email)]
+//!     email: String,
+//!     #[validate(custom(function = "validate_rf_reference"))]
+//!     reference: String,
+//!     #[validate(nested)]
+//!     remittance: RfReferenceField,
+//! }
+//!
+//! let mut request = PaymentRequest {
+//!     email: "billing@example.com".to_string(),
+//!     reference: "RF18 5390 0754 7034".to_string(),
+//!     remittance: RfReferenceField::from("RF712348231"),
+//! };
+//! assert!(request.validate().is_ok());
+//!
+//! request.reference = "RF19 5390 0754 7034".to_string();
+//! request.remittance = RfReferenceField::from("RF702348231");
+//! let errors = request.validate().unwrap_err();
+//! assert_eq!(
+//!     errors.field_errors()["reference"][0].code,
+//!     "ISO11649-E003-INVALID-CHECKSUM"
+//! );
+//! assert!(errors.errors().contains_key("remittance"));
+//! ```
+
+use std::borrow::Cow;
+
+use ::validator::{Validate, ValidationError, ValidationErrors};
+
+use crate::{ParseError, RfCreditorReference};
+
+/// Validates `value` as creditor reference
+///
+/// Usable with `#[validate(custom(function = "validate_rf_reference"))]`.
+///
+/// # Errors
+///
+/// Results in a [`ValidationError`] with the code of the [`ParseError`]
+/// (see [`ParseError::code`]) and its message.
+///
+pub fn validate_rf_reference(value: &str) -> Result<(), ValidationError> {
+    RfCreditorReference::parse_str(value)
+        .map(|_| ())
+        .map_err(|err| to_validation_error(&err))
+}
+
+/// `RfReferenceField` is a creditor reference input validated with
+/// [`Validate`]
+///
+/// Usable with `#[validate(nested)]` in request structs.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
+pub struct RfReferenceField(pub String);
+
+impl RfReferenceField {
+    /// Parses the field to [`RfCreditorReference`]
+    ///
+    /// # Errors
+    ///
+    /// Results in [`ParseError`]s when there is some problem with
+    /// parsing the field.
+    ///
+    pub fn parse(&self) -> Result<RfCreditorReference<'static>, ParseError> {
+        RfCreditorReference::parse_str(&self.0)
+    }
+}
+
+impl From<String> for RfReferenceField {
+    fn from(value: String) -> Self {
+        Self(value)
+    }
+}
+
+impl From<&str> for RfReferenceField {
+    fn from(value: &str) -> Self {
+        Self(value.to_string())
+    }
+}
+
+impl Validate for RfReferenceField {
+    fn validate(&self) -> Result<(), ValidationErrors> {
+        validate_rf_reference(&self.0).map_err(|err| {
+            let mut errors = ValidationErrors::new();
+            errors.add("reference", err);
+            errors
+        })
+    }
+}
+
+/// Converts a [`ParseError`] to [`ValidationError`]
+fn to_validation_error(err: &ParseError) -> ValidationError {
+    let mut validation_error = ValidationError::new(err.code());
+    validation_error.message = Some(Cow::from(err.to_string()));
+    validation_error
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn validate_rf_reference_test() {
+        assert!(validate_rf_reference("RF18 5390 0754 7034").is_ok());
+
+        let err = validate_rf_reference("RF19 5390 0754 7034").unwrap_err();
+        assert_eq!(err.code, "ISO11649-E003-INVALID-CHECKSUM");
+        assert!(err.message.is_some());
+    }
+
+    #[test]
+    fn rf_reference_field_test() {
+        assert!(RfReferenceField::from("RF18 5390 0754 7034")
+            .validate()
+            .is_ok());
+
+        let errors = RfReferenceField::from("RF18 5390_0754 7034")
+            .validate()
+            .unwrap_err();
+        assert!(errors.field_errors().contains_key("reference"));
+    }
+}
@@ -0,0 +1,272 @@
+//! Field-level validation of creditor references in web forms
+//!
+//! Available with feature `web`.
+//!
+//! [`validate_field`] is cheap enough to run on every keystroke, e.g. in
+//! Yew or Leptos payment forms compiled to WASM. Inputs that are the
+//! beginning of a reference, too short or with a checksum not matching
+//! yet, are [`FieldStatus::Incomplete`] instead of invalid.
+//! [`validate_submitted_field`] validates the complete input on blur or
+//! submit, so its checksum mismatches are invalid. Invalid inputs of a
+//! reference length come with a suggested correction where one is found.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use iso_11649::web::{validate_field, validate_submitted_field, FieldStatus};
+//!
+//! assert_eq!(validate_field("RF18").status, FieldStatus::Incomplete);
+//!
+//! let field = validate_field("RF18 5390 0754 7043");
+//! assert_eq!(field.status, FieldStatus::Incomplete);
+//! assert!(field.message.is_some());
+//! assert_eq!(field.suggestion, None);
+//!
+//! let field = validate_submitted_field("RF18 5390 0754 7043");
+//! assert_eq!(field.status, FieldStatus::Invalid);
+//! assert!(field.suggestion.is_some());
+//!
+//! let field = validate_field("rf18 5390 0754 7034");
+//! assert_eq!(field.status, FieldStatus::Invalid);
+//! assert_eq!(field.suggestion.as_deref(), Some("RF18 5390 0754 7034"));
+//!
+//! assert!(validate_field("RF18539007547034").is_valid());
+//! ```
+
+use crate::{
+    correction::{normalize_confusables, suggest_corrections},
+    ParseError, RfCreditorReference, IDENTIFIER, MAX_LEN, MIN_LEN,
+};
+
+/// Status of a form field of a creditor reference
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum FieldStatus {
+    /// the field is empty or blank
+    Empty,
+    /// the input is the beginning of a reference, too short or with a
+    /// checksum not matching yet
+    Incomplete,
+    /// the input is a valid reference
+    Valid,
+    /// the input is no valid reference
+    Invalid,
+}
+
+/// `FieldValidation` is the result of validating a form field
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FieldValidation {
+    /// status of the field
+    pub status: FieldStatus,
+    /// message of the [`ParseError`] for incomplete and invalid inputs
+    pub message: Option<String>,
+    /// print format of a valid input or of a suggested correction of an
+    /// invalid input
+    pub suggestion: Option<String>,
+}
+
+impl FieldValidation {
+    /// Checks if the status is [`FieldStatus::Valid`]
+    #[must_use]
+    pub fn is_valid(&self) -> bool {
+        self.status == FieldStatus::Valid
+    }
+}
+
+/// Validates the `input` of a form field like
+/// [`RfCreditorReference::parse_str`] while it is typed
+///
+/// Well-formed inputs which are too short or whose checksum doesn't match
+/// are [`FieldStatus::Incomplete`]. Invalid inputs of a length between
+/// [`MIN_LEN`] and [`MAX_LEN`] get a suggestion of
+/// [`normalize_confusables`], an uppercase identifier or the first of
+/// [`suggest_corrections`].
+#[must_use]
+pub fn validate_field(input: &str) -> FieldValidation {
+    validate_field_with(input, false, ToString::to_string)
+}
+
+/// Validates the complete `input` of a submitted form field like
+/// [`validate_field`]
+///
+/// Inputs which are too short or whose checksum doesn't match are
+/// [`FieldStatus::Invalid`] with suggestions.
+#[must_use]
+pub fn validate_submitted_field(input: &str) -> FieldValidation {
+    validate_field_with(input, true, ToString::to_string)
+}
+
+/// Validates the `input` of a form field like [`validate_field`] with
+/// messages in the language of `locale`
+///
+/// Available with feature `i18n` too.
+#[cfg(feature = "i18n")]
+#[must_use]
+pub fn validate_field_localized(input: &str, locale: &str) -> FieldValidation {
+    validate_field_with(input, false, |err| err.localized_message(locale))
+}
+
+/// Validates the `input` of a submitted form field like
+/// [`validate_submitted_field`] with messages in the language of `locale`
+///
+/// Available with feature `i18n` too.
+#[cfg(feature = "i18n")]
+#[must_use]
+pub fn validate_submitted_field_localized(input: &str, locale: &str) -> FieldValidation {
+    validate_field_with(input, true, |err| err.localized_message(locale))
+}
+
+/// Validates `input` with messages of `message`, `complete` if the input is
+/// submitted
+fn validate_field_with(
+    input: &str,
+    complete: bool,
+    message: impl Fn(&ParseError) -> String,
+) -> FieldValidation {
+    if input.trim().is_empty() {
+        return FieldValidation {
+            status: FieldStatus::Empty,
+            message: None,
+            suggestion: None,
+        };
+    }
+
+    let err = match RfCreditorReference::parse_str(input) {
+        Ok(rf) => {
+            return FieldValidation {
+                status: FieldStatus::Valid,
+                message: None,
+                suggestion: Some(rf.to_string()),
+            }
+        }
+        Err(err) => err,
+    };
+    let status = match err {
+        ParseError::TooShort { .. } | ParseError::InvalidChecksum { .. }
+            if !complete && is_well_formed(input) =>
+        {
+            FieldStatus::Incomplete
+        }
+        _ => FieldStatus::Invalid,
+    };
+    let len = input.chars().filter(|&c| c != ' ').count();
+    let suggestion = match status {
+        FieldStatus::Invalid if (MIN_LEN..=MAX_LEN).contains(&len) => suggest(input),
+        _ => None,
+    };
+    FieldValidation {
+        status,
+        message: Some(message(&err)),
+        suggestion: suggestion.map(|rf| rf.to_string()),
+    }
+}
+
+/// Checks if `input` has the identifier, check digits and body characters
+/// of a reference as far as it goes
+fn is_well_formed(input: &str) -> bool {
+    input
+        .chars()
+        .filter(|&c| c != ' ')
+        .enumerate()
+        .all(|(i, c)| match i {
+            0 | 1 => IDENTIFIER[i..].starts_with(c),
+            2 | 3 => c.is_ascii_digit(),
+            _ => c.is_ascii_alphanumeric(),
+        })
+}
+
+/// Suggests a correction of the invalid `input`
+fn suggest(input: &str) -> Option<RfCreditorReference<'static>> {
+    if let Ok((rf, _)) = normalize_confusables(input) {
+        return Some(rf);
+    }
+    let trimmed = input.trim_start();
+    if trimmed.len() >= 2 && trimmed.is_char_boundary(2) {
+        let uppercase = trimmed[..2].to_ascii_uppercase() + &trimmed[2..];
+        if let Ok(rf) = RfCreditorReference::parse_str(&uppercase) {
+            return Some(rf);
+        }
+    }
+    suggest_corrections(input, 1).into_iter().next()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn validate_field_test() {
+        for input in ["", "   "] {
+            let field = validate_field(input);
+            assert_eq!(field.status, FieldStatus::Empty);
+            assert_eq!(field.message, None);
+        }
+
+        let field = validate_field("RF7");
+        assert_eq!(field.status, FieldStatus::Incomplete);
+        assert!(field.message.is_some());
+        assert_eq!(field.suggestion, None);
+
+        let field = validate_field(" RF712348231");
+        assert!(field.is_valid());
+        assert_eq!(field.message, None);
+        assert_eq!(field.suggestion.as_deref(), Some("RF71 2348 231"));
+    }
+
+    #[test]
+    fn suggestion_test() {
+        let field = validate_field("RF7I 2348 231");
+        assert_eq!(field.status, FieldStatus::Invalid);
+        assert_eq!(field.suggestion.as_deref(), Some("RF71 2348 231"));
+
+        let field = validate_field("Rf71 2348 231");
+        assert_eq!(field.suggestion.as_deref(), Some("RF71 2348 231"));
+
+        let field = validate_submitted_field("RF71 2348 213");
+        assert_eq!(field.status, FieldStatus::Invalid);
+        assert_eq!(
+            field.message,
+            Some(
+                RfCreditorReference::parse_str("RF71 2348 213")
+                    .unwrap_err()
+                    .to_string()
+            )
+        );
+        assert!(field.suggestion.is_some());
+
+        let field = validate_field("XY71 2348 231 ####");
+        assert_eq!(field.status, FieldStatus::Invalid);
+        assert_eq!(field.suggestion, None);
+    }
+
+    #[test]
+    fn incomplete_test() {
+        for input in ["R", "RF1", "RF18 5390 0754 70", "RF18 5390 0754 7043"] {
+            let field = validate_field(input);
+            assert_eq!(field.status, FieldStatus::Incomplete, "{}", input);
+            assert!(field.message.is_some());
+            assert_eq!(field.suggestion, None);
+        }
+
+        for input in ["X", "RFA", "RF18 5390 #"] {
+            let field = validate_field(input);
+            assert_eq!(field.status, FieldStatus::Invalid, "{}", input);
+            assert_eq!(field.suggestion, None);
+        }
+
+        let field = validate_submitted_field("RF18 5390 0754 70");
+        assert_eq!(field.status, FieldStatus::Invalid);
+        assert!(field.suggestion.is_some());
+        assert_eq!(validate_submitted_field("RF1").status, FieldStatus::Invalid);
+        assert_eq!(validate_submitted_field("RF1").suggestion, None);
+    }
+
+    #[cfg(feature = "i18n")]
+    #[test]
+    fn localized_test() {
+        let field = validate_field_localized("XX18 5390 0754 7034", "de");
+        assert_eq!(
+            field.message.as_deref(),
+            Some("Kennung ist nicht RF [XX18 5390 0754 7034]")
+        );
+    }
+}
@@ -0,0 +1,55 @@
+//! Routes of axum with the extractors of feature `axum`
+
+use axum_crate::{
+    body::{to_bytes, Body},
+    http::{Request, StatusCode},
+    routing::get,
+    Router,
+};
+use iso_11649::http::{RfPath, RfQuery};
+use tower::ServiceExt;
+
+async fn invoice(RfPath(rf): RfPath) -> String {
+    rf.to_electronic_string()
+}
+
+async fn payment(RfQuery(rf): RfQuery) -> String {
+    rf.to_electronic_string()
+}
+
+/// Status and body of the response of the router to `uri`
+fn call(uri: &str) -> (StatusCode, String) {
+    let router = Router::new()
+        .route("/invoices/:reference", get(invoice))
+        .route("/payments", get(payment));
+    let request = Request::get(uri).body(Body::empty()).unwrap();
+
+    tokio::runtime::Builder::new_current_thread()
+        .build()
+        .unwrap()
+        .block_on(async {
+            let response = router.oneshot(request).await.unwrap();
+            let status = response.status();
+            let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+            (status, String::from_utf8(body.to_vec()).unwrap())
+        })
+}
+
+#[test]
+fn router_test() {
+    assert_eq!(
+        call("/invoices/RF712348231"),
+        (StatusCode::OK, "RF712348231".to_string())
+    );
+    assert_eq!(
+        call("/payments?reference=RF71+2348+231"),
+        (StatusCode::OK, "RF712348231".to_string())
+    );
+    assert_eq!(call("/payments").0, StatusCode::BAD_REQUEST);
+
+    let (status, body) = call("/invoices/RF702348231");
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    let problem = serde_json::from_str::<serde_json::Value>(&body).unwrap();
+    assert_eq!(problem["type"], "urn:iso11649:problem:invalid-checksum");
+    assert_eq!(problem["code"], "ISO11649-E003-INVALID-CHECKSUM");
+}
@@ -0,0 +1,31 @@
+//! Columns of Diesel with the mapping of feature `diesel` on SQLite
+
+use diesel::{dsl::select, sql_types::Text, Connection, IntoSql, RunQueryDsl, SqliteConnection};
+use iso_11649::RfCreditorReference;
+
+#[test]
+fn sqlite_test() {
+    let mut conn = SqliteConnection::establish(":memory:").unwrap();
+
+    let rf = RfCreditorReference::new("539007547034");
+    let reference = select(rf.clone().into_sql::<Text>())
+        .get_result::<String>(&mut conn)
+        .unwrap();
+    assert_eq!(reference, "RF18539007547034");
+    assert_eq!(
+        select(rf.clone().into_sql::<Text>())
+            .get_result::<RfCreditorReference>(&mut conn)
+            .unwrap(),
+        rf
+    );
+    assert_eq!(
+        select("RF18 5390 0754 7034".into_sql::<Text>())
+            .get_result::<RfCreditorReference>(&mut conn)
+            .unwrap(),
+        rf
+    );
+
+    assert!(select("RF19539007547034".into_sql::<Text>())
+        .get_result::<RfCreditorReference>(&mut conn)
+        .is_err());
+}
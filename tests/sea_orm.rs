@@ -0,0 +1,50 @@
+//! Columns of SeaORM with the mapping of feature `sea-orm`
+
+use std::collections::BTreeMap;
+
+use iso_11649::RfCreditorReference;
+use sea_orm::{
+    ConnectionTrait, DbBackend, MockDatabase, QueryResult, Statement, TryGetError, TryGetable,
+    Value,
+};
+
+/// Result row of a mock database with `reference` in the column `reference`
+fn row(reference: &str) -> QueryResult {
+    let mut row = BTreeMap::new();
+    let _ = row.insert("reference", Value::from(reference));
+    let conn = MockDatabase::new(DbBackend::Sqlite)
+        .append_query_results([[row]])
+        .into_connection();
+
+    tokio::runtime::Builder::new_current_thread()
+        .build()
+        .unwrap()
+        .block_on(conn.query_one(Statement::from_string(
+            DbBackend::Sqlite,
+            "SELECT reference FROM invoice",
+        )))
+        .unwrap()
+        .unwrap()
+}
+
+#[test]
+fn try_get_by_test() {
+    assert_eq!(
+        RfCreditorReference::try_get_by(&row("RF18539007547034"), "reference").unwrap(),
+        RfCreditorReference::new("539007547034")
+    );
+    assert_eq!(
+        RfCreditorReference::try_get_by(&row("RF18 5390 0754 7034"), "reference").unwrap(),
+        RfCreditorReference::new("539007547034")
+    );
+}
+
+#[test]
+fn invalid_test() {
+    match RfCreditorReference::try_get_by(&row("RF19539007547034"), "reference") {
+        Err(TryGetError::DbErr(err)) => assert!(err.to_string().contains("RfCreditorReference")),
+        result => panic!("unexpected {:?}", result),
+    }
+
+    assert!(RfCreditorReference::try_get_by(&row("RF18539007547034"), "invoice").is_err());
+}
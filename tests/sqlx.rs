@@ -0,0 +1,59 @@
+//! Columns of sqlx with the mapping of feature `sqlx` on SQLite
+
+use iso_11649::RfCreditorReference;
+use sqlx::{query_scalar, Connection, SqliteConnection};
+
+/// Runs `test` with a connection to an in-memory database
+fn with_connection<F, Fut>(test: F)
+where
+    F: FnOnce(SqliteConnection) -> Fut,
+    Fut: std::future::Future<Output = ()>,
+{
+    tokio::runtime::Builder::new_current_thread()
+        .build()
+        .unwrap()
+        .block_on(async {
+            let conn = SqliteConnection::connect("sqlite::memory:").await.unwrap();
+            test(conn).await;
+        });
+}
+
+#[test]
+fn round_trip_test() {
+    with_connection(|mut conn| async move {
+        let rf = RfCreditorReference::new("539007547034");
+        let reference = query_scalar::<_, String>("SELECT ?")
+            .bind(rf.clone())
+            .fetch_one(&mut conn)
+            .await
+            .unwrap();
+        assert_eq!(reference, "RF18539007547034");
+        assert_eq!(
+            query_scalar::<_, RfCreditorReference>("SELECT ?")
+                .bind(rf.clone())
+                .fetch_one(&mut conn)
+                .await
+                .unwrap(),
+            rf
+        );
+        assert_eq!(
+            query_scalar::<_, RfCreditorReference>("SELECT 'RF18 5390 0754 7034'")
+                .fetch_one(&mut conn)
+                .await
+                .unwrap(),
+            rf
+        );
+    });
+}
+
+#[test]
+fn invalid_test() {
+    with_connection(|mut conn| async move {
+        let err = query_scalar::<_, RfCreditorReference>("SELECT 'RF19539007547034'")
+            .fetch_one(&mut conn)
+            .await
+            .unwrap_err();
+        assert!(matches!(err, sqlx::Error::ColumnDecode { .. }));
+        assert!(err.to_string().contains("checksum 19"));
+    });
+}